@@ -197,9 +197,14 @@ fn read_labels<'l, E, Reader: Read<E> + Seek>(
     labels: &'l mut [u8],
 ) -> Result<Option<&'l str>, Error<E>> {
     const NAME_PTR_MASK: u16 = 0xC0_00;
+    // names are limited to 255 octets, each pointer must be followed by at
+    // least a 1 octet label, this bounds the number of pointers in a valid
+    // name, and prevents looping forever on malicious pointer loops
+    const MAX_PTRS: u8 = 128;
 
     let mut labels_idx: usize = 0;
     let mut seek_to: u16 = 0;
+    let mut ptrs: u8 = 0;
 
     loop {
         let mut buf: [u8; 2] = [0; 2];
@@ -212,6 +217,10 @@ fn read_labels<'l, E, Reader: Read<E> + Seek>(
 
         // if pointer
         if n == 2 && sequence & NAME_PTR_MASK != 0 {
+            ptrs += 1;
+            if ptrs > MAX_PTRS {
+                return Err(Error::UnexpectedEof);
+            }
             let ptr: u16 = sequence & !NAME_PTR_MASK;
             if seek_to == 0 {
                 seek_to = reader.stream_position();
@@ -315,6 +324,9 @@ impl<'a, W5500: Udp> Response<'a, W5500> {
     /// * [`Error::Other`]
     /// * [`Error::UnexpectedEof`]
     ///
+    /// [`Error::UnexpectedEof`] is also returned for malformed names, for
+    /// example names with compression pointers that form a loop.
+    ///
     /// If any error occurs the entire response should be discarded,
     /// and you should not continue to call `next_rr`.
     pub fn next_rr(&mut self) -> Result<Option<ResourceRecord>, Error<W5500::Error>> {
@@ -252,3 +252,17 @@ fn ptr_response() {
     );
     assert_eq!(response.next_rr().unwrap(), None);
 }
+
+/// Compression pointers that form a loop must not hang
+#[test]
+fn label_compression_loop() {
+    const RESPONSE: [u8; 24] = [
+        0x12, 0x34, 0x81, 0x80, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x0C, 0x00,
+        0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x3C, 0x00, 0x00,
+    ];
+    let mut w5500: MockW5500 = MockW5500::new(&RESPONSE);
+    let mut buf: [u8; 16] = [0; 16];
+    let mut response = CLIENT.response(&mut w5500, &mut buf, 0x1234).unwrap();
+
+    assert_eq!(response.next_rr(), Err(w5500_dns::hl::Error::UnexpectedEof));
+}
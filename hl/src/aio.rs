@@ -0,0 +1,821 @@
+//! Asynchronous W5500 traits.
+//!
+//! These traits mirror the blocking traits at the crate root, but are built
+//! on the [`w5500_ll::aio::Registers`] trait.
+//!
+//! Instead of polling socket registers the receiving methods await the W5500
+//! interrupt pin with the [`embedded_hal_async::digital::Wait`] trait.
+//! The W5500 drives the interrupt pin low while any unmasked interrupt is
+//! pending, the binding methods in this module unmask the socket interrupts
+//! that they rely upon.
+//!
+//! # Embassy
+//!
+//! Do not wrap the blocking traits with a `block_on` style executor inside
+//! of an async task.
+//! The blocking methods return [`Error::WouldBlock`] instead of yielding,
+//! retrying them in a loop starves every other task on the executor.
+//!
+//! Instead use the [`w5500_ll::aio::Registers`] implementation of
+//! `w5500_ll::eh1::vdm::W5500` with an async SPI bus, and pass the W5500
+//! interrupt pin to the methods in this module, for example an
+//! `embassy_stm32::exti::ExtiInput`, which implements the
+//! [`embedded_hal_async::digital::Wait`] trait.
+//!
+//! The blocking [`crate::TcpReader`] and [`crate::TcpWriter`] implement
+//! synchronous traits that cannot be awaited, use the async [`TcpReader`]
+//! and [`TcpWriter`] from the [`Tcp`] trait in this module instead.
+//!
+//! [`embedded_hal_async::digital::Wait`]: https://docs.rs/embedded-hal-async/0.2.0-alpha.0/embedded_hal_async/digital/trait.Wait.html
+mod interrupt_context;
+
+pub use interrupt_context::InterruptContext;
+
+use crate::{Error, UdpHeader, STABLE_READ_ATTEMPTS};
+use core::{cmp::min, convert::Infallible};
+use eha0a::digital::Wait;
+use w5500_ll::{
+    aio::Registers, net::SocketAddrV4, Protocol, Sn, SocketCommand, SocketInterrupt,
+    SocketInterruptMask, SocketMode, SocketStatus,
+};
+
+/// Reads SN_RX_RSR until two consecutive reads agree.
+async fn sn_rx_rsr_stable<T, E>(w5500: &mut T, sn: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let mut prev: u16 = w5500.sn_rx_rsr(sn).await?;
+    for _ in 1..STABLE_READ_ATTEMPTS {
+        let next: u16 = w5500.sn_rx_rsr(sn).await?;
+        if next == prev {
+            break;
+        }
+        prev = next;
+    }
+    Ok(prev)
+}
+
+/// Reads SN_TX_FSR until two consecutive reads agree.
+async fn sn_tx_fsr_stable<T, E>(w5500: &mut T, sn: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let mut prev: u16 = w5500.sn_tx_fsr(sn).await?;
+    for _ in 1..STABLE_READ_ATTEMPTS {
+        let next: u16 = w5500.sn_tx_fsr(sn).await?;
+        if next == prev {
+            break;
+        }
+        prev = next;
+    }
+    Ok(prev)
+}
+
+/// Wait for a socket to reach a specific status.
+async fn wait_for_status<T, E>(w5500: &mut T, sn: Sn, status: SocketStatus) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    while w5500.sn_sr(sn).await? != Ok(status) {}
+    Ok(())
+}
+
+/// Wait for a complete datagram to be buffered in the socket RX buffer.
+async fn wait_for_datagram<T, E, P>(w5500: &mut T, irq: &mut P, sn: Sn) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+    P: Wait<Error = Infallible>,
+{
+    loop {
+        // clear the interrupt before checking the buffer, a datagram that
+        // arrives after the check will raise the interrupt again
+        w5500.set_sn_ir(sn, SocketInterrupt::RECV_MASK).await?;
+
+        let rsr: u16 = sn_rx_rsr_stable(w5500, sn).await?;
+        if let Some(len) = rsr.checked_sub(UdpHeader::LEN) {
+            let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+            let ptr: u16 = w5500.sn_rx_rd(sn).await?;
+            w5500.sn_rx_buf(sn, ptr, &mut header).await?;
+            if len >= UdpHeader::deser(header).len {
+                return Ok(());
+            }
+        }
+
+        match irq.wait_for_low().await {
+            Ok(()) => (),
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// Wait for data to be buffered in the socket RX buffer, or for the TCP
+/// connection to close.
+///
+/// Returns the value of SN_RX_RSR, which is zero when the connection has
+/// closed without any buffered data.
+async fn wait_for_data<T, E, P>(w5500: &mut T, irq: &mut P, sn: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+    P: Wait<Error = Infallible>,
+{
+    loop {
+        // clear the interrupt before checking the buffer, data that arrives
+        // after the check will raise the interrupt again
+        w5500.set_sn_ir(sn, SocketInterrupt::RECV_MASK).await?;
+
+        let rsr: u16 = sn_rx_rsr_stable(w5500, sn).await?;
+        if rsr != 0 || w5500.sn_sr(sn).await? != Ok(SocketStatus::Established) {
+            return Ok(rsr);
+        }
+
+        match irq.wait_for_low().await {
+            Ok(()) => (),
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// Wait for the SENDOK or TIMEOUT interrupt, clearing the interrupt that
+/// was raised.
+async fn wait_for_send<T, E, P>(w5500: &mut T, irq: &mut P, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+    P: Wait<Error = Infallible>,
+{
+    loop {
+        let sn_ir: SocketInterrupt = w5500.sn_ir(sn).await?;
+        if sn_ir.sendok_raised() {
+            w5500.set_sn_ir(sn, SocketInterrupt::SENDOK_MASK).await?;
+            return Ok(());
+        } else if sn_ir.timeout_raised() {
+            w5500.set_sn_ir(sn, SocketInterrupt::TIMEOUT_MASK).await?;
+            return Err(Error::SendTimeout);
+        }
+
+        match irq.wait_for_low().await {
+            Ok(()) => (),
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// Opens a socket in UDP mode with the given socket mode, and unmasks the
+/// receive interrupt for the socket.
+async fn udp_open<T, E>(w5500: &mut T, sn: Sn, port: u16, mode: SocketMode) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    w5500.set_sn_cr(sn, SocketCommand::Close).await?;
+    // This will not hang, the socket status will always change to closed
+    // after a close command.
+    // (unless you do somthing silly like holding the W5500 in reset)
+    wait_for_status(w5500, sn, SocketStatus::Closed).await?;
+    w5500.set_sn_port(sn, port).await?;
+    w5500.set_sn_mr(sn, mode).await?;
+    w5500
+        .set_sn_imr(sn, SocketInterruptMask::ALL_MASKED.unmask_recv())
+        .await?;
+    let simr: u8 = w5500.simr().await?;
+    w5500.set_simr(simr | sn.bitmask()).await?;
+    w5500.set_sn_cr(sn, SocketCommand::Open).await?;
+    // This will not hang, the socket status will always change to Udp
+    // after a open command with SN_MR set to UDP.
+    // (unless you do somthing silly like holding the W5500 in reset)
+    wait_for_status(w5500, sn, SocketStatus::Udp).await?;
+    Ok(())
+}
+
+/// An asynchronous W5500 UDP socket trait.
+///
+/// This is the asynchronous equivalent of [`crate::Udp`].
+///
+/// The receiving methods take the W5500 interrupt pin as an argument, and
+/// only poll the socket registers after the interrupt pin has been driven low.
+/// The pin error type must be [`Infallible`], which is the case for the input
+/// pins of most HALs.
+///
+/// The interrupt pin is shared by all sockets, and stays low until every
+/// pending unmasked interrupt has been cleared.
+/// Interrupts that are not cleared will cause the receiving methods to poll
+/// the socket registers continuously, mask any interrupts that you are not
+/// servicing.
+///
+/// # Differences from the blocking trait
+///
+/// This trait only provides a subset of the [`crate::Udp`] methods:
+/// binding with [`udp_bind`](Self::udp_bind) and
+/// [`udp_bind_unicast`](Self::udp_bind_unicast), receiving with
+/// [`udp_recv_from`](Self::udp_recv_from),
+/// [`udp_peek_from`](Self::udp_peek_from) and
+/// [`udp_peek_from_header`](Self::udp_peek_from_header), and sending with
+/// [`udp_send_to`](Self::udp_send_to) and [`udp_send`](Self::udp_send).
+///
+/// There are no asynchronous equivalents of the other methods, such as
+/// [`crate::Udp::udp_bind_multicast`], [`crate::Udp::udp_recv_into`],
+/// [`crate::Udp::udp_drain`], and [`crate::Udp::udp_send_if_free`].
+///
+/// The socket status is only checked with debug assertions, the `strict`
+/// feature does not apply to this trait and [`Error::InvalidState`] is
+/// never returned.
+pub trait Udp: Registers {
+    /// Binds the socket to the given port.
+    ///
+    /// This will close the socket, which will reset the RX and TX buffers.
+    ///
+    /// This also unmasks the receive interrupt for the socket, and masks all
+    /// other interrupts for the socket.
+    ///
+    /// See [`crate::Udp::udp_bind`] for more information.
+    ///
+    /// # Example
+    ///
+    /// Bind the first socket to port 8080.
+    ///
+    /// ```no_run
+    /// # async fn f<W: w5500_hl::ll::aio::Registers>(w5500: &mut W) -> Result<(), W::Error> {
+    /// use w5500_hl::{aio::Udp, ll::Sn::Sn0};
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// # Ok(()) }
+    /// ```
+    async fn udp_bind(&mut self, sn: Sn, port: u16) -> Result<(), Self::Error> {
+        const MODE: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Udp);
+        udp_open(self, sn, port, MODE).await
+    }
+
+    /// Binds the socket to the given port, ignoring broadcast datagrams.
+    ///
+    /// See [`crate::Udp::udp_bind_unicast`] for more information.
+    ///
+    /// # Example
+    ///
+    /// Bind the first socket to port 8080, ignoring broadcasts.
+    ///
+    /// ```no_run
+    /// # async fn f<W: w5500_hl::ll::aio::Registers>(w5500: &mut W) -> Result<(), W::Error> {
+    /// use w5500_hl::{aio::Udp, ll::Sn::Sn0};
+    ///
+    /// w5500.udp_bind_unicast(Sn0, 8080).await?;
+    /// # Ok(()) }
+    /// ```
+    async fn udp_bind_unicast(&mut self, sn: Sn, port: u16) -> Result<(), Self::Error> {
+        const MODE: SocketMode = SocketMode::DEFAULT
+            .set_protocol(Protocol::Udp)
+            .enable_bcastb();
+        udp_open(self, sn, port, MODE).await
+    }
+
+    /// Receives a single datagram message on the socket.
+    /// On success, returns the number of bytes read and the origin.
+    ///
+    /// This waits on the interrupt pin `irq` until a complete datagram has
+    /// been buffered.
+    ///
+    /// If a message is too long to fit in the supplied buffer, excess bytes
+    /// will be discarded.
+    ///
+    /// See [`crate::Udp::udp_recv_from`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W, P>(w5500: &mut W, irq: &mut P) -> Result<(), W::Error>
+    /// # where
+    /// #     W: w5500_hl::ll::aio::Registers,
+    /// #     P: eha0a::digital::Wait<Error = core::convert::Infallible>,
+    /// # {
+    /// use w5500_hl::{aio::Udp, ll::Sn::Sn0};
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// let mut buf = [0; 10];
+    /// let (number_of_bytes, src_addr) = w5500.udp_recv_from(irq, Sn0, &mut buf).await?;
+    ///
+    /// let filled_buf = &mut buf[..number_of_bytes.into()];
+    /// # Ok(()) }
+    /// ```
+    async fn udp_recv_from<P>(
+        &mut self,
+        irq: &mut P,
+        sn: Sn,
+        buf: &mut [u8],
+    ) -> Result<(u16, SocketAddrV4), Self::Error>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        wait_for_datagram(self, irq, sn).await?;
+
+        debug_assert_eq!(self.sn_sr(sn).await?, Ok(SocketStatus::Udp));
+
+        let mut ptr: u16 = self.sn_rx_rd(sn).await?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(sn, ptr, &mut header).await?;
+        ptr = ptr.wrapping_add(UdpHeader::LEN);
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        let read_size: u16 = min(header.len, buf.len().try_into().unwrap_or(u16::MAX));
+        if read_size != 0 {
+            self.sn_rx_buf(sn, ptr, &mut buf[..read_size.into()])
+                .await?;
+        }
+        ptr = ptr.wrapping_add(header.len);
+        self.set_sn_rx_rd(sn, ptr).await?;
+        self.set_sn_cr(sn, SocketCommand::Recv).await?;
+        Ok((read_size, header.origin))
+    }
+
+    /// Receives a single datagram message on the socket, without removing it
+    /// from the queue.
+    /// On success, returns the number of bytes read and the UDP header.
+    ///
+    /// This waits on the interrupt pin `irq` until a complete datagram has
+    /// been buffered.
+    ///
+    /// See [`crate::Udp::udp_peek_from`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W, P>(w5500: &mut W, irq: &mut P) -> Result<(), W::Error>
+    /// # where
+    /// #     W: w5500_hl::ll::aio::Registers,
+    /// #     P: eha0a::digital::Wait<Error = core::convert::Infallible>,
+    /// # {
+    /// use w5500_hl::{aio::Udp, ll::Sn::Sn0};
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// let mut buf = [0; 10];
+    /// let (number_of_bytes, udp_header) = w5500.udp_peek_from(irq, Sn0, &mut buf).await?;
+    ///
+    /// let filled_buf = &mut buf[..number_of_bytes.into()];
+    /// # Ok(()) }
+    /// ```
+    async fn udp_peek_from<P>(
+        &mut self,
+        irq: &mut P,
+        sn: Sn,
+        buf: &mut [u8],
+    ) -> Result<(u16, UdpHeader), Self::Error>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        wait_for_datagram(self, irq, sn).await?;
+
+        debug_assert_eq!(self.sn_sr(sn).await?, Ok(SocketStatus::Udp));
+
+        let mut ptr: u16 = self.sn_rx_rd(sn).await?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(sn, ptr, &mut header).await?;
+        ptr = ptr.wrapping_add(UdpHeader::LEN);
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        let read_size: u16 = min(header.len, buf.len().try_into().unwrap_or(u16::MAX));
+        if read_size != 0 {
+            self.sn_rx_buf(sn, ptr, &mut buf[..read_size.into()])
+                .await?;
+        }
+
+        Ok((read_size, header))
+    }
+
+    /// Receives the origin and size of the next datagram available on the
+    /// socket, without removing it from the queue.
+    ///
+    /// This waits on the interrupt pin `irq` until a complete datagram has
+    /// been buffered.
+    ///
+    /// See [`crate::Udp::udp_peek_from_header`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W, P>(w5500: &mut W, irq: &mut P) -> Result<(), W::Error>
+    /// # where
+    /// #     W: w5500_hl::ll::aio::Registers,
+    /// #     P: eha0a::digital::Wait<Error = core::convert::Infallible>,
+    /// # {
+    /// use w5500_hl::{aio::Udp, ll::Sn::Sn0, UdpHeader};
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// let udp_header: UdpHeader = w5500.udp_peek_from_header(irq, Sn0).await?;
+    /// # Ok(()) }
+    /// ```
+    async fn udp_peek_from_header<P>(
+        &mut self,
+        irq: &mut P,
+        sn: Sn,
+    ) -> Result<UdpHeader, Self::Error>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        wait_for_datagram(self, irq, sn).await?;
+
+        debug_assert_eq!(self.sn_sr(sn).await?, Ok(SocketStatus::Udp));
+
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        let ptr: u16 = self.sn_rx_rd(sn).await?;
+        self.sn_rx_buf(sn, ptr, &mut header).await?;
+        Ok(UdpHeader::deser(header))
+    }
+
+    /// Sends data on the socket to the given address.
+    /// On success, returns the number of bytes written.
+    ///
+    /// See [`crate::Udp::udp_send_to`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W: w5500_hl::ll::aio::Registers>(w5500: &mut W) -> Result<(), W::Error> {
+    /// use w5500_hl::{
+    ///     aio::Udp,
+    ///     ll::Sn::Sn0,
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST).await?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok(()) }
+    /// ```
+    async fn udp_send_to(
+        &mut self,
+        sn: Sn,
+        buf: &[u8],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Self::Error> {
+        self.set_sn_dest(sn, addr).await?;
+        self.udp_send(sn, buf).await
+    }
+
+    /// Sends data to the currently configured destination.
+    /// On success, returns the number of bytes written.
+    ///
+    /// See [`crate::Udp::udp_send`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W: w5500_hl::ll::aio::Registers>(w5500: &mut W) -> Result<(), W::Error> {
+    /// use w5500_hl::{
+    ///     aio::Udp,
+    ///     ll::Sn::Sn0,
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080).await?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST).await?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// // send the same to the same destination
+    /// let tx_bytes: u16 = w5500.udp_send(Sn0, &buf).await?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok(()) }
+    /// ```
+    async fn udp_send(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn).await?, Ok(SocketStatus::Udp));
+
+        let data_len: u16 = u16::try_from(buf.len()).unwrap_or(u16::MAX);
+        let free_size: u16 = sn_tx_fsr_stable(self, sn).await?;
+        let tx_bytes: u16 = min(data_len, free_size);
+        if tx_bytes != 0 {
+            let ptr: u16 = self.sn_tx_wr(sn).await?;
+            self.set_sn_tx_buf(sn, ptr, &buf[..tx_bytes.into()]).await?;
+            self.set_sn_tx_wr(sn, ptr.wrapping_add(tx_bytes)).await?;
+            self.set_sn_cr(sn, SocketCommand::Send).await?;
+        }
+        Ok(tx_bytes)
+    }
+}
+
+/// Implement the async UDP trait for any structure that implements
+/// [`w5500_ll::aio::Registers`].
+impl<T> Udp for T where T: Registers {}
+
+/// Asynchronous streaming reader for a TCP socket buffer.
+///
+/// This is the asynchronous equivalent of [`crate::TcpReader`], each method
+/// behaves like the [`crate::io::Read`] or [`crate::io::Seek`] method of the
+/// same name.
+///
+/// Created with [`Tcp::tcp_reader`].
+///
+/// # Example
+///
+/// See [`Tcp::tcp_reader`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TcpReader<'w, W5500> {
+    w5500: &'w mut W5500,
+    sn: Sn,
+    head_ptr: u16,
+    tail_ptr: u16,
+    ptr: u16,
+}
+
+impl<'w, W5500: Registers> TcpReader<'w, W5500> {
+    /// Read data from the socket buffer, returning the number of bytes read.
+    ///
+    /// Returns `0` when the end of the buffered data has been reached.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<u16, W5500::Error> {
+        let read_size: u16 = min(self.remain(), buf.len().try_into().unwrap_or(u16::MAX));
+        if read_size != 0 {
+            self.w5500
+                .sn_rx_buf(self.sn, self.ptr, &mut buf[..usize::from(read_size)])
+                .await?;
+            self.ptr = self.ptr.wrapping_add(read_size);
+        }
+        Ok(read_size)
+    }
+
+    /// Read the exact number of bytes required to fill `buf`.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::UnexpectedEof`]
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error<W5500::Error>> {
+        let buf_len: u16 = buf.len().try_into().unwrap_or(u16::MAX);
+        if self.remain() < buf_len {
+            Err(Error::UnexpectedEof)
+        } else {
+            self.w5500.sn_rx_buf(self.sn, self.ptr, buf).await?;
+            self.ptr = self.ptr.wrapping_add(buf_len);
+            Ok(())
+        }
+    }
+
+    /// Mark the data up to the current position as read.
+    ///
+    /// Data that is not marked as read will be returned by the next reader.
+    pub async fn done(self) -> Result<(), W5500::Error> {
+        self.w5500.set_sn_rx_rd(self.sn, self.ptr).await?;
+        self.w5500.set_sn_cr(self.sn, SocketCommand::Recv).await?;
+        Ok(())
+    }
+
+    /// Total length of the buffered data.
+    pub fn stream_len(&self) -> u16 {
+        self.tail_ptr.wrapping_sub(self.head_ptr)
+    }
+
+    /// Current position in the buffered data.
+    pub fn stream_position(&self) -> u16 {
+        self.ptr.wrapping_sub(self.head_ptr)
+    }
+
+    /// Remaining data after the current position.
+    pub fn remain(&self) -> u16 {
+        self.tail_ptr.wrapping_sub(self.ptr)
+    }
+}
+
+/// Asynchronous streaming writer for a TCP socket buffer.
+///
+/// This is the asynchronous equivalent of [`crate::TcpWriter`], each method
+/// behaves like the [`crate::io::Write`] or [`crate::io::Seek`] method of the
+/// same name, except for [`send`](TcpWriter::send) which also waits for the
+/// data to be sent.
+///
+/// Created with [`Tcp::tcp_writer`].
+///
+/// # Example
+///
+/// See [`Tcp::tcp_writer`].
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TcpWriter<'w, W5500> {
+    w5500: &'w mut W5500,
+    sn: Sn,
+    head_ptr: u16,
+    tail_ptr: u16,
+    ptr: u16,
+}
+
+impl<'w, W5500: Registers> TcpWriter<'w, W5500> {
+    /// Write data to the socket buffer, returning the number of bytes
+    /// written.
+    ///
+    /// Returns `0` when the socket buffer is full.
+    pub async fn write(&mut self, buf: &[u8]) -> Result<u16, W5500::Error> {
+        let write_size: u16 = min(self.remain(), buf.len().try_into().unwrap_or(u16::MAX));
+        if write_size != 0 {
+            self.w5500
+                .set_sn_tx_buf(self.sn, self.ptr, &buf[..usize::from(write_size)])
+                .await?;
+            self.ptr = self.ptr.wrapping_add(write_size);
+        }
+        Ok(write_size)
+    }
+
+    /// Write all of `buf` to the socket buffer.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    pub async fn write_all(&mut self, buf: &[u8]) -> Result<(), Error<W5500::Error>> {
+        let buf_len: u16 = buf.len().try_into().unwrap_or(u16::MAX);
+        if self.remain() < buf_len {
+            Err(Error::OutOfMemory)
+        } else {
+            self.w5500.set_sn_tx_buf(self.sn, self.ptr, buf).await?;
+            self.ptr = self.ptr.wrapping_add(buf_len);
+            Ok(())
+        }
+    }
+
+    /// Send the data written, and wait on the interrupt pin `irq` until the
+    /// data has been sent.
+    ///
+    /// The SENDOK and TIMEOUT interrupts must be unmasked for the socket.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::SendTimeout`]
+    pub async fn send<P>(self, irq: &mut P) -> Result<(), Error<W5500::Error>>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        const SEND_IR: u8 = SocketInterrupt::SENDOK_MASK | SocketInterrupt::TIMEOUT_MASK;
+        self.w5500.set_sn_ir(self.sn, SEND_IR).await?;
+        self.w5500.set_sn_tx_wr(self.sn, self.ptr).await?;
+        self.w5500.set_sn_cr(self.sn, SocketCommand::Send).await?;
+        wait_for_send(self.w5500, irq, self.sn).await
+    }
+
+    /// Total length of the free space when the writer was created.
+    pub fn stream_len(&self) -> u16 {
+        self.tail_ptr.wrapping_sub(self.head_ptr)
+    }
+
+    /// Current position in the free space.
+    pub fn stream_position(&self) -> u16 {
+        self.ptr.wrapping_sub(self.head_ptr)
+    }
+
+    /// Remaining free space after the current position.
+    pub fn remain(&self) -> u16 {
+        self.tail_ptr.wrapping_sub(self.ptr)
+    }
+}
+
+/// An asynchronous W5500 TCP socket trait.
+///
+/// This provides the asynchronous equivalents of [`crate::Tcp::tcp_reader`]
+/// and [`crate::Tcp::tcp_writer`], the socket must already be connected
+/// with the blocking [`crate::Tcp`] trait or the
+/// [`w5500_ll::aio::Registers`] trait.
+///
+/// The interrupt pin requirements are the same as for the [`Udp`] trait.
+/// The socket interrupts used by these methods must be unmasked, with
+/// [`Registers::set_sn_imr`] and [`Registers::set_simr`].
+pub trait Tcp: Registers {
+    /// Create a TCP reader.
+    ///
+    /// This waits on the interrupt pin `irq` until data has been received,
+    /// and returns a [`TcpReader`] for the buffered data.
+    ///
+    /// The RECV interrupt must be unmasked for the socket.
+    ///
+    /// If the connection is closed without buffered data the returned reader
+    /// is empty, [`TcpReader::read`] returns `0`.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a TCP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W, P>(w5500: &mut W, irq: &mut P) -> Result<(), w5500_hl::Error<W::Error>>
+    /// # where
+    /// #     W: w5500_hl::ll::aio::Registers,
+    /// #     P: eha0a::digital::Wait<Error = core::convert::Infallible>,
+    /// # {
+    /// use w5500_hl::{
+    ///     aio::{Tcp, TcpReader},
+    ///     ll::{Sn::Sn0, SocketInterruptMask},
+    /// };
+    ///
+    /// w5500
+    ///     .set_sn_imr(Sn0, SocketInterruptMask::ALL_MASKED.unmask_recv())
+    ///     .await?;
+    /// w5500.set_simr(Sn0.bitmask()).await?;
+    ///
+    /// // ... connect the socket
+    ///
+    /// let mut reader: TcpReader<_> = w5500.tcp_reader(irq, Sn0).await?;
+    /// let mut buf = [0; 2];
+    /// reader.read_exact(&mut buf).await?;
+    /// reader.done().await?;
+    /// # Ok(()) }
+    /// ```
+    async fn tcp_reader<P>(&mut self, irq: &mut P, sn: Sn) -> Result<TcpReader<Self>, Self::Error>
+    where
+        Self: Sized,
+        P: Wait<Error = Infallible>,
+    {
+        debug_assert!(!matches!(
+            self.sn_sr(sn).await?,
+            Ok(SocketStatus::Udp) | Ok(SocketStatus::Init) | Ok(SocketStatus::Macraw)
+        ));
+
+        let sn_rx_rsr: u16 = wait_for_data(self, irq, sn).await?;
+        let sn_rx_rd: u16 = self.sn_rx_rd(sn).await?;
+
+        Ok(TcpReader {
+            w5500: self,
+            sn,
+            head_ptr: sn_rx_rd,
+            tail_ptr: sn_rx_rd.wrapping_add(sn_rx_rsr),
+            ptr: sn_rx_rd,
+        })
+    }
+
+    /// Create a TCP writer.
+    ///
+    /// This returns a [`TcpWriter`] for the free space of the socket TX
+    /// buffer, [`TcpWriter::send`] waits for the data to be sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f<W, P>(w5500: &mut W, irq: &mut P) -> Result<(), w5500_hl::Error<W::Error>>
+    /// # where
+    /// #     W: w5500_hl::ll::aio::Registers,
+    /// #     P: eha0a::digital::Wait<Error = core::convert::Infallible>,
+    /// # {
+    /// use w5500_hl::{
+    ///     aio::{Tcp, TcpWriter},
+    ///     ll::{Sn::Sn0, SocketInterruptMask},
+    /// };
+    ///
+    /// w5500
+    ///     .set_sn_imr(
+    ///         Sn0,
+    ///         SocketInterruptMask::ALL_MASKED
+    ///             .unmask_sendok()
+    ///             .unmask_timeout(),
+    ///     )
+    ///     .await?;
+    /// w5500.set_simr(Sn0.bitmask()).await?;
+    ///
+    /// // ... connect the socket
+    ///
+    /// let mut writer: TcpWriter<_> = w5500.tcp_writer(Sn0).await?;
+    /// writer.write_all(b"hello").await?;
+    /// writer.send(irq).await?;
+    /// # Ok(()) }
+    /// ```
+    async fn tcp_writer(&mut self, sn: Sn) -> Result<TcpWriter<Self>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let fsr: u16 = sn_tx_fsr_stable(self, sn).await?;
+        let wr: u16 = self.sn_tx_wr(sn).await?;
+
+        Ok(TcpWriter {
+            w5500: self,
+            sn,
+            head_ptr: wr,
+            tail_ptr: wr.wrapping_add(fsr),
+            ptr: wr,
+        })
+    }
+}
+
+/// Implement the async TCP trait for any structure that implements
+/// [`w5500_ll::aio::Registers`].
+impl<T> Tcp for T where T: Registers {}
@@ -0,0 +1,187 @@
+use crate::{Error, Tcp, Udp};
+use atomic_waker::AtomicWaker;
+use core::{future::poll_fn, task::Poll};
+use w5500_ll::{net::SocketAddrV4, Registers, Sn, SocketInterrupt, SocketStatus, SOCKETS};
+
+/// Socket wakers for the W5500 interrupt pin.
+///
+/// This holds one waker for each socket, the futures returned by the methods
+/// of this structure register their waker and then poll the socket
+/// registers, returning [`Poll::Pending`] until the operation completes.
+///
+/// Call [`on_interrupt`](Self::on_interrupt) from the interrupt handler of
+/// the pin connected to the W5500 INTn pin, typically a falling edge EXTI
+/// interrupt.
+/// The socket interrupts must be unmasked with [`Registers::set_sn_imr`] and
+/// [`Registers::set_simr`], otherwise the futures are never woken.
+///
+/// Unlike the [`Udp`](super::Udp) and [`Tcp`](super::Tcp) traits in this
+/// module the futures are built on the blocking [`Registers`] trait, and do
+/// not require an [`embedded_hal_async::digital::Wait`] pin.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn f<W: w5500_hl::ll::Registers>(w5500: &mut W) -> Result<(), w5500_hl::Error<W::Error>> {
+/// use w5500_hl::{
+///     aio::InterruptContext,
+///     ll::{Registers, Sn::Sn0, SocketInterruptMask},
+///     Udp,
+/// };
+///
+/// static IRQ: InterruptContext = InterruptContext::new();
+///
+/// w5500.udp_bind(Sn0, 8080)?;
+/// w5500.set_sn_imr(Sn0, SocketInterruptMask::ALL_MASKED.unmask_recv())?;
+/// w5500.set_simr(Sn0.bitmask())?;
+///
+/// // in the EXTI interrupt handler:
+/// // IRQ.on_interrupt(&mut w5500)?;
+///
+/// let mut buf = [0; 10];
+/// let (number_of_bytes, src_addr) = IRQ.udp_recv_from(w5500, Sn0, &mut buf).await?;
+/// # Ok(()) }
+/// ```
+///
+/// [`embedded_hal_async::digital::Wait`]: https://docs.rs/embedded-hal-async/0.2.0-alpha.0/embedded_hal_async/digital/trait.Wait.html
+#[derive(Debug)]
+pub struct InterruptContext {
+    wakers: [AtomicWaker; SOCKETS.len()],
+}
+
+impl Default for InterruptContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InterruptContext {
+    /// Creates a new interrupt context, with no registered wakers.
+    pub const fn new() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const WAKER: AtomicWaker = AtomicWaker::new();
+        Self {
+            wakers: [WAKER; SOCKETS.len()],
+        }
+    }
+
+    /// Reads the socket interrupt register (SIR), and wakes the futures of
+    /// the sockets with pending interrupts.
+    ///
+    /// The socket interrupts are not cleared, the woken futures clear the
+    /// interrupts that they service.
+    ///
+    /// If the W5500 cannot be accessed from the interrupt handler use
+    /// [`wake`](Self::wake) instead.
+    pub fn on_interrupt<W: Registers>(&self, w5500: &mut W) -> Result<(), W::Error> {
+        let sir: u8 = w5500.sir()?;
+        SOCKETS
+            .iter()
+            .filter(|sn| sir & sn.bitmask() != 0)
+            .for_each(|sn| self.wake(*sn));
+        Ok(())
+    }
+
+    /// Wakes the future of a socket, without reading the W5500.
+    ///
+    /// The woken future polls the socket registers, waking a socket without
+    /// a pending interrupt is harmless.
+    pub fn wake(&self, sn: Sn) {
+        self.wakers[usize::from(sn)].wake()
+    }
+
+    /// Receives a single datagram message on the socket.
+    /// On success, returns the number of bytes read and the origin.
+    ///
+    /// This is the same as [`Udp::udp_recv_from`], but the future is pending
+    /// instead of returning [`Error::WouldBlock`].
+    ///
+    /// The RECV interrupt must be unmasked for the socket.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    pub async fn udp_recv_from<W: Registers>(
+        &self,
+        w5500: &mut W,
+        sn: Sn,
+        buf: &mut [u8],
+    ) -> Result<(u16, SocketAddrV4), Error<W::Error>> {
+        poll_fn(|cx| {
+            self.wakers[usize::from(sn)].register(cx.waker());
+            // clear the interrupt before checking the buffer, a datagram that
+            // arrives after the check will raise the interrupt again
+            w5500.set_sn_ir(sn, SocketInterrupt::RECV_MASK)?;
+            match w5500.udp_recv_from(sn, buf) {
+                Err(Error::WouldBlock) => Poll::Pending,
+                result => Poll::Ready(result),
+            }
+        })
+        .await
+    }
+
+    /// Read data from the remote host.
+    /// On success, returns the number of bytes read.
+    ///
+    /// This is the same as [`Tcp::tcp_read`], but the future is pending
+    /// while no data has been received and the connection is established.
+    /// Returns `0` when the connection has been closed by the remote host.
+    ///
+    /// The RECV interrupt, and the DISCON interrupt to detect a closed
+    /// connection, must be unmasked for the socket.
+    pub async fn tcp_read<W: Registers>(
+        &self,
+        w5500: &mut W,
+        sn: Sn,
+        buf: &mut [u8],
+    ) -> Result<u16, W::Error> {
+        poll_fn(|cx| {
+            self.wakers[usize::from(sn)].register(cx.waker());
+            // clear the interrupts before checking the buffer, data that
+            // arrives after the check will raise the interrupt again
+            w5500.set_sn_ir(
+                sn,
+                SocketInterrupt::RECV_MASK | SocketInterrupt::DISCON_MASK,
+            )?;
+            let rx_bytes: u16 = w5500.tcp_read(sn, buf)?;
+            if rx_bytes == 0 && w5500.sn_sr(sn)? == Ok(SocketStatus::Established) {
+                Poll::Pending
+            } else {
+                Poll::Ready(Ok(rx_bytes))
+            }
+        })
+        .await
+    }
+
+    /// Waits for the previous send on the socket to complete.
+    ///
+    /// This is the same as [`Udp::udp_poll_send`], but the future is pending
+    /// instead of returning [`Error::WouldBlock`].
+    /// This also works for TCP sockets.
+    ///
+    /// The SENDOK and TIMEOUT interrupts must be unmasked for the socket.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::SendTimeout`]
+    pub async fn send_complete<W: Registers>(
+        &self,
+        w5500: &mut W,
+        sn: Sn,
+    ) -> Result<(), Error<W::Error>> {
+        poll_fn(|cx| {
+            self.wakers[usize::from(sn)].register(cx.waker());
+            match w5500.udp_poll_send(sn) {
+                Err(Error::WouldBlock) => Poll::Pending,
+                result => Poll::Ready(result),
+            }
+        })
+        .await
+    }
+}
@@ -0,0 +1,144 @@
+use core::time::Duration;
+use w5500_ll::{
+    net::{Eui48Addr, Ipv4Addr},
+    BufferSize, Mode, SOCKETS,
+};
+
+/// Configuration for [`Common::bring_up`](crate::Common::bring_up).
+///
+/// # Example
+///
+/// ```
+/// use w5500_hl::{
+///     ll::BufferSize,
+///     net::{Eui48Addr, Ipv4Addr},
+///     BringUpConfig,
+/// };
+///
+/// let mut config = BringUpConfig::new(
+///     Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC),
+///     Ipv4Addr::new(192, 168, 0, 50),
+///     Ipv4Addr::new(192, 168, 0, 1),
+///     Ipv4Addr::new(255, 255, 255, 0),
+/// );
+/// // give all the memory to the first socket
+/// config.rx_buf_sizes = [BufferSize::KB0; 8];
+/// config.rx_buf_sizes[0] = BufferSize::KB16;
+/// // retry every 100ms, up to 3 times
+/// config.retry_time = Some(1000);
+/// config.retry_count = Some(3);
+/// // enable interrupts for the first socket
+/// config.socket_interrupt_mask = Some(0x01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BringUpConfig {
+    /// Source hardware (MAC) address.
+    pub mac: Eui48Addr,
+    /// Source IP address.
+    pub ip: Ipv4Addr,
+    /// Gateway IP address.
+    pub gateway: Ipv4Addr,
+    /// Subnet mask.
+    pub subnet_mask: Ipv4Addr,
+    /// RX buffer size of each socket, indexed by socket number.
+    ///
+    /// The sum of all sizes must not exceed 16 KiB.
+    pub rx_buf_sizes: [BufferSize; SOCKETS.len()],
+    /// TX buffer size of each socket, indexed by socket number.
+    ///
+    /// The sum of all sizes must not exceed 16 KiB.
+    pub tx_buf_sizes: [BufferSize; SOCKETS.len()],
+    /// Mode register value, see [`Registers::set_mr`].
+    ///
+    /// Set to `None` to keep the reset value.
+    ///
+    /// [`Registers::set_mr`]: w5500_ll::Registers::set_mr
+    pub mode: Option<Mode>,
+    /// Retry time, in units of 100µs, see [`Registers::set_rtr`].
+    ///
+    /// Set to `None` to keep the reset value of 200ms.
+    ///
+    /// [`Registers::set_rtr`]: w5500_ll::Registers::set_rtr
+    pub retry_time: Option<u16>,
+    /// Retry count, see [`Registers::set_rcr`].
+    ///
+    /// Set to `None` to keep the reset value of 8.
+    ///
+    /// [`Registers::set_rcr`]: w5500_ll::Registers::set_rcr
+    pub retry_count: Option<u8>,
+    /// Socket interrupt mask, see [`Registers::set_simr`].
+    ///
+    /// Set to `None` to keep the reset value, with all socket interrupts
+    /// disabled.
+    ///
+    /// [`Registers::set_simr`]: w5500_ll::Registers::set_simr
+    pub socket_interrupt_mask: Option<u8>,
+    /// Maximum time to wait for the Ethernet link to come up.
+    ///
+    /// Set to `None` to skip waiting for the link.
+    pub link_timeout: Option<Duration>,
+}
+
+impl BringUpConfig {
+    /// Creates a new bring-up configuration.
+    ///
+    /// The socket buffer sizes default to 2 KiB for every socket (the W5500
+    /// reset value), the mode, retry, and interrupt registers keep their reset
+    /// values, and the link timeout defaults to 5 seconds.
+    pub const fn new(
+        mac: Eui48Addr,
+        ip: Ipv4Addr,
+        gateway: Ipv4Addr,
+        subnet_mask: Ipv4Addr,
+    ) -> Self {
+        Self {
+            mac,
+            ip,
+            gateway,
+            subnet_mask,
+            rx_buf_sizes: [BufferSize::KB2; SOCKETS.len()],
+            tx_buf_sizes: [BufferSize::KB2; SOCKETS.len()],
+            mode: None,
+            retry_time: None,
+            retry_count: None,
+            socket_interrupt_mask: None,
+            link_timeout: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+/// Returns `true` if the sum of the buffer sizes fits in the 16 KiB of
+/// buffer memory of the W5500.
+pub(crate) fn buffer_sizes_fit(sizes: &[BufferSize; SOCKETS.len()]) -> bool {
+    sizes.iter().map(BufferSize::size_in_bytes).sum::<usize>() <= 16 * 1024
+}
+
+/// Errors from [`Common::bring_up`](crate::Common::bring_up).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BringUpError<E> {
+    /// The sum of the RX or TX buffer sizes exceeds 16 KiB.
+    ///
+    /// This is checked before any registers are written.
+    BufferOverallocation,
+    /// The software reset bit did not clear after the software reset.
+    ResetTimeout,
+    /// The version register did not contain the expected value,
+    /// [`w5500_ll::VERSION`].
+    ///
+    /// This usually indicates a SPI wiring problem, or that the device is not
+    /// a W5500.
+    Version(u8),
+    /// The Ethernet link did not come up before the link timeout.
+    LinkTimeout,
+    /// Errors from the [`Registers`](w5500_ll::Registers) trait
+    /// implementation.
+    Other(E),
+}
+
+impl<E> From<E> for BringUpError<E> {
+    fn from(error: E) -> BringUpError<E> {
+        BringUpError::Other(error)
+    }
+}
@@ -0,0 +1,95 @@
+use w5500_ll::Registers;
+
+/// W5500 wrapper that limits the length of each SPI transfer.
+///
+/// Every [`Registers::read`] and [`Registers::write`] longer than the chunk
+/// size is split into multiple transfers of at most the chunk size, with the
+/// address incremented for each transfer.
+/// This is useful for DMA controllers that cannot do long transfers.
+///
+/// All methods of the [`Registers`] trait are implemented with `read` and
+/// `write`, when wrapped every transfer of the high level methods is split,
+/// including the transfers of the streaming readers and writers.
+///
+/// The socket buffer addresses wrap around, a transfer that wraps around
+/// the end of the socket buffer is split the same as any other transfer.
+///
+/// # Example
+///
+/// ```no_run
+/// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{io::Write, ll::Sn, Chunked, Udp};
+///
+/// // limit DMA transfers to 4 KiB
+/// let mut w5500 = Chunked::new(w5500, 4096);
+/// w5500.udp_bind(Sn::Sn0, 8080)?;
+///
+/// let mut writer = w5500.udp_writer(Sn::Sn0)?;
+/// writer.write_all(&[0; 10 * 1024])?;
+/// writer.send()?;
+/// # Ok::<(), w5500_hl::Error<_>>(())
+/// ```
+#[derive(Debug)]
+pub struct Chunked<W> {
+    w5500: W,
+    chunk_size: usize,
+}
+
+impl<W: Registers> Chunked<W> {
+    /// Wraps a W5500 device, limiting transfers to `chunk_size` bytes.
+    ///
+    /// The chunk size does not include the 3 byte address and control phase.
+    ///
+    /// # Panics
+    ///
+    /// * `chunk_size` is zero.
+    pub const fn new(w5500: W, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must not be zero");
+        Self { w5500, chunk_size }
+    }
+
+    /// Returns the maximum length of each transfer.
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Get a mutable reference to the wrapped W5500 device.
+    ///
+    /// Transfers made with the wrapped device are not split.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w5500
+    }
+
+    /// Returns the wrapped W5500 device.
+    pub fn free(self) -> W {
+        self.w5500
+    }
+}
+
+impl<W: Registers> Registers for Chunked<W> {
+    type Error = W::Error;
+
+    fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        if data.len() <= self.chunk_size {
+            return self.w5500.read(address, block, data);
+        }
+        let mut address: u16 = address;
+        for chunk in data.chunks_mut(self.chunk_size) {
+            self.w5500.read(address, block, chunk)?;
+            address = address.wrapping_add(chunk.len() as u16);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+        if data.len() <= self.chunk_size {
+            return self.w5500.write(address, block, data);
+        }
+        let mut address: u16 = address;
+        for chunk in data.chunks(self.chunk_size) {
+            self.w5500.write(address, block, chunk)?;
+            address = address.wrapping_add(chunk.len() as u16);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    io::{Read, Write},
+    Error, TcpReader, TcpWriter, UdpReader, UdpWriter,
+};
+use embedded_io::{ErrorKind, ErrorType};
+use w5500_ll::Registers;
+
+impl<E: core::fmt::Debug> embedded_io::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::OutOfMemory => ErrorKind::OutOfMemory,
+            Error::SendTimeout | Error::Timeout => ErrorKind::TimedOut,
+            Error::ConnectionRefused => ErrorKind::ConnectionRefused,
+            Error::NotListening | Error::NotConnected | Error::InvalidState { .. } => {
+                ErrorKind::NotConnected
+            }
+            Error::BufferOverallocation | Error::StaleHandle => ErrorKind::InvalidInput,
+            Error::Unsupported => ErrorKind::Unsupported,
+            Error::NotFound => ErrorKind::NotFound,
+            Error::PortInUse(_) => ErrorKind::AddrInUse,
+            Error::UnexpectedEof | Error::WouldBlock | Error::Other(_) => ErrorKind::Other,
+        }
+    }
+}
+
+macro_rules! impl_reader {
+    ($ty:ident) => {
+        impl<'w, W5500: Registers> ErrorType for $ty<'w, W5500>
+        where
+            W5500::Error: core::fmt::Debug,
+        {
+            type Error = Error<W5500::Error>;
+        }
+
+        /// Reads from the data that was buffered when the reader was created.
+        ///
+        /// `Ok(0)` is returned once all buffered data has been read.
+        impl<'w, W5500: Registers> embedded_io::Read for $ty<'w, W5500>
+        where
+            W5500::Error: core::fmt::Debug,
+        {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                Ok(Read::read(self, buf)?.into())
+            }
+        }
+    };
+}
+
+macro_rules! impl_writer {
+    ($ty:ident) => {
+        impl<'w, W5500: Registers> ErrorType for $ty<'w, W5500>
+        where
+            W5500::Error: core::fmt::Debug,
+        {
+            type Error = Error<W5500::Error>;
+        }
+
+        /// Writes to the free space that was available in the socket buffer
+        /// when the writer was created.
+        ///
+        /// `Ok(0)` is returned once the writer is full.
+        /// Flushing does nothing, the data is only sent once the native
+        /// `send` method of the writer is called.
+        impl<'w, W5500: Registers> embedded_io::Write for $ty<'w, W5500>
+        where
+            W5500::Error: core::fmt::Debug,
+        {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                Ok(Write::write(self, buf)?.into())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_reader!(TcpReader);
+impl_reader!(UdpReader);
+impl_writer!(TcpWriter);
+impl_writer!(UdpWriter);
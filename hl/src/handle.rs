@@ -0,0 +1,178 @@
+use core::cell::{Cell, RefCell, RefMut};
+use w5500_ll::{Registers, Sn, SocketCommand, SOCKETS};
+
+/// Tracks ownership of the W5500 sockets.
+///
+/// This wraps the W5500 device, and hands out a [`SocketHandle`] for each
+/// claimed socket.
+/// A socket can only be claimed once, and it is closed and released when the
+/// handle is dropped.
+///
+/// This is an opt-in alternative to passing bare [`Sn`] values around, the
+/// device is stored in a [`RefCell`] so that multiple handles can share it.
+///
+/// # Example
+///
+/// ```no_run
+/// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{ll::Sn, SocketClaims, SocketHandle, Udp};
+///
+/// let sockets = SocketClaims::new(w5500);
+///
+/// let dns: SocketHandle<_> = sockets.claim_socket(Sn::Sn1).unwrap();
+/// dns.with(|w5500, sn| w5500.udp_bind(sn, 5353))?;
+///
+/// // the socket is already claimed
+/// assert!(sockets.claim_socket(Sn::Sn1).is_none());
+///
+/// // closes and releases the socket
+/// drop(dns);
+/// assert!(sockets.claim_socket(Sn::Sn1).is_some());
+/// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+/// ```
+#[derive(Debug)]
+pub struct SocketClaims<W> {
+    w5500: RefCell<W>,
+    claimed: Cell<u8>,
+    /// Bitmask of sockets with a handle that was dropped while the device
+    /// was borrowed, these are closed and released on the next borrow.
+    close_pending: Cell<u8>,
+}
+
+/// Closes the sockets in the `sockets` bitmask, ignoring errors.
+fn close_sockets<W: Registers>(w5500: &mut W, sockets: u8) {
+    SOCKETS
+        .iter()
+        .filter(|sn| sockets & sn.bitmask() != 0)
+        .for_each(|sn| {
+            w5500.set_sn_cr(*sn, SocketCommand::Close).ok();
+        });
+}
+
+impl<W: Registers> SocketClaims<W> {
+    /// Creates a new socket ownership tracker, with all sockets unclaimed.
+    pub const fn new(w5500: W) -> Self {
+        Self {
+            w5500: RefCell::new(w5500),
+            claimed: Cell::new(0),
+            close_pending: Cell::new(0),
+        }
+    }
+
+    /// Mutably borrows the device, closing and releasing any sockets with a
+    /// pending close first.
+    fn borrow_mut(&self) -> RefMut<'_, W> {
+        let mut w5500: RefMut<'_, W> = self.w5500.borrow_mut();
+        let pending: u8 = self.close_pending.take();
+        if pending != 0 {
+            close_sockets(&mut *w5500, pending);
+            self.claimed.set(self.claimed.get() & !pending);
+        }
+        w5500
+    }
+
+    /// Claims a socket.
+    ///
+    /// Returns `None` if the socket is already claimed.
+    ///
+    /// This does not modify the socket registers, the socket is closed when
+    /// the returned handle is dropped.
+    pub fn claim_socket(&self, sn: Sn) -> Option<SocketHandle<'_, W>> {
+        if self.close_pending.get() & sn.bitmask() != 0 && self.w5500.try_borrow_mut().is_ok() {
+            drop(self.borrow_mut());
+        }
+        if self.is_claimed(sn) {
+            None
+        } else {
+            self.claimed.set(self.claimed.get() | sn.bitmask());
+            Some(SocketHandle { claims: self, sn })
+        }
+    }
+
+    /// Returns `true` if the socket is claimed.
+    ///
+    /// A socket with a handle that was dropped while the device was borrowed
+    /// remains claimed until the device is borrowed again.
+    pub fn is_claimed(&self, sn: Sn) -> bool {
+        self.claimed.get() & sn.bitmask() != 0
+    }
+
+    /// Mutably borrows the W5500 device, for operations that are not
+    /// specific to a socket.
+    ///
+    /// # Panics
+    ///
+    /// * The device is already borrowed.
+    pub fn w5500(&self) -> RefMut<'_, W> {
+        self.borrow_mut()
+    }
+
+    /// Returns the W5500 device.
+    ///
+    /// This can only be called once all handles have been dropped.
+    pub fn into_inner(self) -> W {
+        let mut w5500: W = self.w5500.into_inner();
+        close_sockets(&mut w5500, self.close_pending.get());
+        w5500
+    }
+}
+
+/// Ownership of a single W5500 socket.
+///
+/// This is returned by [`SocketClaims::claim_socket`].
+///
+/// When dropped the socket is closed, and released to be claimed again.
+/// Errors from closing the socket are ignored.
+///
+/// If the device is borrowed while the handle is dropped, for example with
+/// [`SocketClaims::w5500`], the socket is closed and released the next time
+/// the device is borrowed.
+#[derive(Debug)]
+pub struct SocketHandle<'a, W: Registers> {
+    claims: &'a SocketClaims<W>,
+    sn: Sn,
+}
+
+// SocketClaims holds the device in a RefCell, which has no defmt::Format
+// implementation, only the socket number is formatted.
+#[cfg(feature = "defmt")]
+impl<'a, W: Registers> defmt::Format for SocketHandle<'a, W> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "SocketHandle({})", self.sn);
+    }
+}
+
+impl<'a, W: Registers> SocketHandle<'a, W> {
+    /// Returns the socket number.
+    pub fn sn(&self) -> Sn {
+        self.sn
+    }
+
+    /// Calls `f` with the W5500 device and the socket number.
+    ///
+    /// # Panics
+    ///
+    /// * The device is already borrowed, for example by calling `with` on
+    ///   another handle inside of `f`.
+    ///
+    /// # Example
+    ///
+    /// See [`SocketClaims`].
+    pub fn with<R>(&self, f: impl FnOnce(&mut W, Sn) -> R) -> R {
+        f(&mut self.claims.borrow_mut(), self.sn)
+    }
+}
+
+impl<'a, W: Registers> Drop for SocketHandle<'a, W> {
+    fn drop(&mut self) {
+        let claims: &SocketClaims<W> = self.claims;
+        claims
+            .close_pending
+            .set(claims.close_pending.get() | self.sn.bitmask());
+        // defer the close if the device is borrowed, panicking in drop
+        // would abort when already unwinding
+        if claims.w5500.try_borrow_mut().is_ok() {
+            drop(claims.borrow_mut());
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use w5500_ll::{Sn, SocketInterrupt, SOCKETS};
+
+/// Pending interrupts for all sockets.
+///
+/// This is returned by [`Common::poll_interrupts`].
+///
+/// [`Common::poll_interrupts`]: crate::Common::poll_interrupts
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SocketInterrupts {
+    pub(crate) sir: u8,
+    pub(crate) sn_ir: [SocketInterrupt; 8],
+}
+
+impl SocketInterrupts {
+    /// No pending socket interrupts.
+    pub(crate) const NONE: Self = Self {
+        sir: 0,
+        sn_ir: [SocketInterrupt::DEFAULT; 8],
+    };
+
+    /// Returns `true` if no sockets have pending interrupts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{Common, SocketInterrupts};
+    ///
+    /// let interrupts: SocketInterrupts = w5500.poll_interrupts()?;
+    /// if interrupts.is_empty() {
+    ///     // spurious interrupt
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.sir == 0
+    }
+
+    /// Get the pending interrupts for a single socket.
+    ///
+    /// Returns `None` if the socket has no pending interrupts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::{Common, SocketInterrupts};
+    ///
+    /// let interrupts: SocketInterrupts = w5500.poll_interrupts()?;
+    /// if let Some(sn_ir) = interrupts.get(Sn0) {
+    ///     if sn_ir.recv_raised() {
+    ///         // handle received data
+    ///     }
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    pub fn get(&self, sn: Sn) -> Option<SocketInterrupt> {
+        if self.sir & sn.bitmask() != 0 {
+            Some(self.sn_ir[usize::from(sn)])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the sockets with pending interrupts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{Common, SocketInterrupts};
+    ///
+    /// let interrupts: SocketInterrupts = w5500.poll_interrupts()?;
+    /// for (sn, sn_ir) in interrupts.iter() {
+    ///     if sn_ir.discon_raised() {
+    ///         // handle disconnect
+    ///     }
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Sn, SocketInterrupt)> + '_ {
+        SOCKETS
+            .iter()
+            .filter_map(|sn| self.get(*sn).map(|sn_ir| (*sn, sn_ir)))
+    }
+}
+
+impl Default for SocketInterrupts {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
@@ -1,6 +1,7 @@
 //! Socket buffer IO traits.
 
 use crate::Error;
+use core::mem::MaybeUninit;
 
 /// Enumeration of all possible methods to seek the W5500 socket buffers.
 ///
@@ -25,6 +26,35 @@ fn wrapping_add_signed(ptr: u16, offset: i16) -> u16 {
     ptr.wrapping_add(offset as u16)
 }
 
+/// Length of the stack buffer used to copy socket buffer data in chunks.
+pub(crate) const CHUNK_LEN: u16 = 64;
+
+/// Initializes `buf` with zeros, returning the initialized slice.
+///
+/// The [`Registers`](w5500_ll::Registers) methods only accept initialized
+/// buffers, because the SPI implementations may read the buffer while filling
+/// it.
+pub(crate) fn zero_uninit(buf: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    buf.iter_mut().for_each(|byte| {
+        byte.write(0);
+    });
+    // SAFETY: every byte of the buffer was initialized above
+    #[allow(unsafe_code)]
+    unsafe {
+        core::slice::from_raw_parts_mut(buf.as_mut_ptr().cast::<u8>(), buf.len())
+    }
+}
+
+/// Returns whichever of `a` and `b` is further from `head` in the socket
+/// buffer, accounting for pointer wraparound.
+pub(crate) fn furthest_ptr(head: u16, a: u16, b: u16) -> u16 {
+    if b.wrapping_sub(head) > a.wrapping_sub(head) {
+        b
+    } else {
+        a
+    }
+}
+
 impl SeekFrom {
     /// Calculate the next value of `ptr` for the given seek method.
     #[doc(hidden)]
@@ -69,15 +99,28 @@ impl SeekFrom {
 pub trait Seek {
     /// Seek to an offset, in bytes, within the socket buffer.
     ///
-    /// Seeking beyond the limits will result [`Error::UnexpectedEof`].
+    /// Seeking beyond the limits will result [`Error::UnexpectedEof`], and
+    /// the position will not be changed.
     ///
     /// # Limits
     ///
-    /// * [`UdpWriter`](crate::UdpWriter) is limited by socket free size.
+    /// * [`UdpWriter`](crate::UdpWriter) is limited by the furthest position
+    ///   written.
     /// * [`UdpReader`](crate::UdpReader) is limited by the received size or
     ///   the UDP datagram length, whichever is less.
-    /// * [`TcpWriter`](crate::TcpWriter) is limited by socket free size.
+    /// * [`TcpWriter`](crate::TcpWriter) is limited by the furthest position
+    ///   written.
     /// * [`TcpReader`](crate::TcpReader) is limited by the received size.
+    ///
+    /// # Writers
+    ///
+    /// Writers send everything up to the furthest position written, not only
+    /// up to the current position, and [`SeekFrom::End`] is relative to the
+    /// furthest position written.
+    /// This allows seeking backwards to patch a header, such as a length
+    /// field, once the rest of the data has been written, without seeking
+    /// forward again before sending.
+    /// Seeking never extends the data that will be sent.
     fn seek<E>(&mut self, pos: SeekFrom) -> Result<(), Error<E>>;
 
     /// Rewind to the beginning of the stream.
@@ -98,14 +141,69 @@ pub trait Seek {
     fn stream_position(&self) -> u16;
 
     /// Remaining bytes in the socket buffer from the current seek position.
+    ///
+    /// For writers this is the free space available for writing, writes never
+    /// exceed the free space in the socket buffer.
     fn remain(&self) -> u16;
 }
 
 /// Socket reader trait.
 pub trait Read<E> {
-    /// Read data from the UDP socket, and return the number of bytes read.
+    /// Read data from the socket buffer, and return the number of bytes read.
+    ///
+    /// This reads the lesser of [`Seek::remain`] and `buf.len()` bytes in a
+    /// single transfer, advancing the current position by the same amount.
+    /// Unlike [`read_exact`](Self::read_exact) a short `buf` is not an error,
+    /// there is no need to call this in a loop to drain the remaining data
+    /// into a large enough buffer.
     fn read(&mut self, buf: &mut [u8]) -> Result<u16, E>;
 
+    /// Read data from the socket into an uninitialized buffer, and return the
+    /// number of bytes read.
+    ///
+    /// The first `n` bytes of `buf` are initialized when `Ok(n)` is returned,
+    /// the rest of `buf` is left untouched.
+    ///
+    /// This avoids initializing a large buffer before reading into it.
+    ///
+    /// [`TcpReader`](crate::TcpReader) and [`UdpReader`](crate::UdpReader)
+    /// read directly into `buf` with a single transfer, zeroing only the bytes
+    /// that are read before the transfer, because the SPI implementations may
+    /// read `buf` while filling it.
+    /// The default implementation reads in chunks of 64 bytes through a buffer
+    /// on the stack.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use core::mem::MaybeUninit;
+    /// use w5500_hl::{io::Read, ll::Sn::Sn0, Udp, UdpReader};
+    ///
+    /// let mut buf: [MaybeUninit<u8>; 2048] = [MaybeUninit::uninit(); 2048];
+    /// let mut reader: UdpReader<_> = w5500.udp_reader(Sn0)?;
+    /// let n: u16 = reader.read_uninit(&mut buf)?;
+    /// reader.done()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<u16, E> {
+        let mut chunk: [u8; CHUNK_LEN as usize] = [0; CHUNK_LEN as usize];
+        let mut total: u16 = 0;
+        for dst in buf.chunks_mut(CHUNK_LEN.into()) {
+            let n: u16 = self.read(&mut chunk[..dst.len()])?;
+            dst.iter_mut()
+                .zip(chunk[..usize::from(n)].iter())
+                .for_each(|(dst, src)| {
+                    dst.write(*src);
+                });
+            total = total.saturating_add(n);
+            if usize::from(n) < dst.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
     /// Read the exact number of bytes required to fill `buf`.
     ///
     /// This function reads as many bytes as necessary to completely fill the
@@ -119,6 +217,39 @@ pub trait Read<E> {
     /// * [`Error::UnexpectedEof`]
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error<E>>;
 
+    /// Read exactly `N` bytes into an array.
+    ///
+    /// This is the same as [`read_exact`](Self::read_exact) with a buffer on
+    /// the stack, which is useful for fixed size protocol headers.
+    /// The array can be parsed with crates such as `zerocopy` or `bytemuck`.
+    ///
+    /// Nothing is read when [`Error::UnexpectedEof`] is returned.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::UnexpectedEof`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{io::Read, ll::Sn::Sn0, Udp, UdpReader};
+    ///
+    /// let mut reader: UdpReader<_> = w5500.udp_reader(Sn0)?;
+    /// let header: [u8; 12] = reader.read_array()?;
+    /// let msg_id: u16 = u16::from_be_bytes([header[0], header[1]]);
+    /// reader.done()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error<E>> {
+        let mut buf: [u8; N] = [0; N];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
     /// Mark the data as read, removing the data from the queue.
     ///
     /// For a TCP reader this removes all data up to the current pointer
@@ -131,11 +262,16 @@ pub trait Read<E> {
 /// Socket writer trait.
 pub trait Write<E> {
     /// Write data to the socket buffer, and return the number of bytes written.
+    ///
+    /// The write is truncated to the free space in the socket buffer,
+    /// see [`Seek::remain`].
     fn write(&mut self, buf: &[u8]) -> Result<u16, E>;
 
     /// Writes all the data, returning [`Error::OutOfMemory`] if the size of
     /// `buf` exceeds the free memory available in the socket buffer.
     ///
+    /// Nothing is written when [`Error::OutOfMemory`] is returned.
+    ///
     /// # Errors
     ///
     /// This method can only return:
@@ -144,18 +280,144 @@ pub trait Write<E> {
     /// * [`Error::OutOfMemory`]
     fn write_all(&mut self, buf: &[u8]) -> Result<(), Error<E>>;
 
+    /// Writes all the bytes of an array.
+    ///
+    /// This is the same as [`write_all`](Self::write_all), and exists for
+    /// symmetry with [`Read::read_array`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{io::Write, ll::Sn::Sn0, Udp, UdpWriter};
+    ///
+    /// let mut writer: UdpWriter<_> = w5500.udp_writer(Sn0)?;
+    /// writer.write_array([0x01, 0x02])?;
+    /// writer.write_u16_be(0x0304)?;
+    /// writer.write_u32_le(0x0807_0605)?;
+    /// writer.send()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn write_array<const N: usize>(&mut self, buf: [u8; N]) -> Result<(), Error<E>> {
+        self.write_all(&buf)
+    }
+
+    /// Writes a `u16` in big-endian byte order.
+    ///
+    /// This is the same as [`write_all`](Self::write_all) with
+    /// [`u16::to_be_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    fn write_u16_be(&mut self, n: u16) -> Result<(), Error<E>> {
+        self.write_all(&n.to_be_bytes())
+    }
+
+    /// Writes a `u16` in little-endian byte order.
+    ///
+    /// This is the same as [`write_all`](Self::write_all) with
+    /// [`u16::to_le_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    fn write_u16_le(&mut self, n: u16) -> Result<(), Error<E>> {
+        self.write_all(&n.to_le_bytes())
+    }
+
+    /// Writes a `u32` in big-endian byte order.
+    ///
+    /// This is the same as [`write_all`](Self::write_all) with
+    /// [`u32::to_be_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    fn write_u32_be(&mut self, n: u32) -> Result<(), Error<E>> {
+        self.write_all(&n.to_be_bytes())
+    }
+
+    /// Writes a `u32` in little-endian byte order.
+    ///
+    /// This is the same as [`write_all`](Self::write_all) with
+    /// [`u32::to_le_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    fn write_u32_le(&mut self, n: u32) -> Result<(), Error<E>> {
+        self.write_all(&n.to_le_bytes())
+    }
+
     /// Send all data previously written with [`write`] and [`write_all`].
     ///
     /// For UDP sockets the destination is set by the last call to
     /// [`Registers::set_sn_dest`], [`Udp::udp_send_to`], or
     /// [`UdpWriter::udp_send_to`].
     ///
+    /// Data after the current position is also sent when the writer has
+    /// previously been further into the buffer, see [`Seek::seek`].
+    ///
     /// [`Registers::set_sn_dest`]: w5500_ll::Registers::set_sn_dest
     /// [`Udp::udp_send_to`]: crate::Udp::udp_send_to
     /// [`UdpWriter::udp_send_to`]: crate::UdpWriter::udp_send_to
     /// [`write_all`]: Self::write_all
     /// [`write`]: Self::write
     fn send(self) -> Result<(), E>;
+
+    /// Discard all data previously written with [`write`] and [`write_all`]
+    /// without sending it.
+    ///
+    /// Data is only committed to the socket buffer by [`send`], the TX write
+    /// pointer register is not updated and no send command is issued.
+    /// Dropping the writer has the same effect, this method exists to make
+    /// the intent explicit.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{io::Write, ll::Sn::Sn0, Udp, UdpWriter};
+    ///
+    /// let mut writer: UdpWriter<_> = w5500.udp_writer(Sn0)?;
+    /// writer.write_all(&[0x01, 0x02, 0x03])?;
+    /// let checksum_ok: bool = false;
+    /// if checksum_ok {
+    ///     writer.send()?;
+    /// } else {
+    ///     writer.abort();
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`send`]: Self::send
+    /// [`write_all`]: Self::write_all
+    /// [`write`]: Self::write
+    fn abort(self)
+    where
+        Self: Sized,
+    {
+    }
 }
 
 #[cfg(test)]
@@ -17,14 +17,42 @@
 //! the [`Registers`], [`Tcp`], and [`Udp`] traits to provide separate socket
 //! structures utilizing whatever Mutex is available for your platform / RTOS.
 //!
+//! All methods are built on the `read` and `write` methods of [`Registers`],
+//! and work with both the variable data length mode (VDM) and the fixed data
+//! length mode (FDM) implementations in [`w5500-ll`].
+//! The FDM implementations split every transfer into frames of 4, 2, and 1
+//! bytes, the methods in this crate never split a 16-bit register across
+//! frames.
+//! In FDM the chip select pin is tied low and the SPI bus cannot be shared,
+//! use VDM with the [`Chunked`] wrapper when chip select can only be held for
+//! short transfers.
+//!
 //! # Feature Flags
 //!
 //! All features are disabled by default.
 //!
+//! * `async`: **Nightly only.** Enables the [`aio`] module which contains
+//!   asynchronous traits built on [`w5500-ll`]'s `aio::Registers` trait.
 //! * `defmt`: Passthrough to [`w5500-ll`].
 //! * `eh0`: Passthrough to [`w5500-ll`].
 //! * `eh1`: Passthrough to [`w5500-ll`].
+//! * `embedded-io`: Implement the [`embedded-io`] traits for the socket readers
+//!   and writers.
+//! * `embedded-nal`: Enable [`NalDevice`], an [`embedded-nal`] network stack.
+//! * `log-spi`: Enable [`LogSpi`], a wrapper that logs every register read and
+//!   write with [`defmt`]. This enables the `defmt` feature.
 //! * `std`: Passthrough to [`w5500-ll`].
+//! * `stats`: Enable [`Common::spin_stats`] to count busy-wait loop iterations,
+//!   and [`Udp::udp_overflow_events`] to count RX buffer overflows.
+//! * `strict`: Check the socket status of UDP methods that return [`Error`],
+//!   returning [`Error::InvalidState`] in release builds instead of relying on
+//!   debug assertions, and check that a destination is configured for
+//!   [`Udp::udp_send_all`], returning [`Error::NotConnected`].
+//!   This costs additional SPI reads per call.
+//!   The UDP methods that return the bus error have `_checked` variants that
+//!   always check the socket status.
+//! * `test-util`: Enable the [`testutil`] module, with an in-memory model of
+//!   the W5500 for host tests. This enables the `std` feature.
 //!
 //! # Examples
 //!
@@ -89,52 +117,323 @@
 //! # Ok::<(), embedded_hal::spi::ErrorKind>(())
 //! ```
 //!
+//! [`aio`]: https://docs.rs/w5500-hl/latest/w5500_hl/aio/index.html
+//! [`Chunked`]: https://docs.rs/w5500-hl/latest/w5500_hl/struct.Chunked.html
+//! [`Common::spin_stats`]: https://docs.rs/w5500-hl/latest/w5500_hl/trait.Common.html#method.spin_stats
+//! [`embedded-io`]: https://crates.io/crates/embedded-io
+//! [`embedded-nal`]: https://crates.io/crates/embedded-nal
+//! [`defmt`]: https://crates.io/crates/defmt
+//! [`Error`]: https://docs.rs/w5500-hl/latest/w5500_hl/enum.Error.html
+//! [`Error::InvalidState`]: https://docs.rs/w5500-hl/latest/w5500_hl/enum.Error.html#variant.InvalidState
+//! [`Error::NotConnected`]: https://docs.rs/w5500-hl/latest/w5500_hl/enum.Error.html#variant.NotConnected
+//! [`LogSpi`]: https://docs.rs/w5500-hl/latest/w5500_hl/struct.LogSpi.html
+//! [`NalDevice`]: https://docs.rs/w5500-hl/latest/w5500_hl/struct.NalDevice.html
 //! [`Registers`]: https://docs.rs/w5500-ll/latest/w5500_ll/trait.Registers.html
 //! [`std::net`]: https://doc.rust-lang.org/std/net/index.html
 //! [`Tcp`]: https://docs.rs/w5500-hl/latest/w5500_hl/trait.Tcp.html
+//! [`testutil`]: https://docs.rs/w5500-hl/latest/w5500_hl/testutil/index.html
 //! [`Udp`]: https://docs.rs/w5500-hl/latest/w5500_hl/trait.Udp.html
+//! [`Udp::udp_overflow_events`]: https://docs.rs/w5500-hl/latest/w5500_hl/trait.Udp.html#method.udp_overflow_events
+//! [`Udp::udp_send_all`]: https://docs.rs/w5500-hl/latest/w5500_hl/trait.Udp.html#method.udp_send_all
 //! [`w5500-ll`]: https://crates.io/crates/w5500-ll
 //! [Wiznet W5500]: https://www.wiznet.io/product-item/w5500/
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+#![cfg_attr(
+    feature = "async",
+    feature(async_fn_in_trait),
+    allow(incomplete_features), // async_fn_in_trait
+)]
 
+#[cfg(feature = "async")]
+pub mod aio;
+mod bring_up;
+mod chunked;
+#[cfg(feature = "embedded-io")]
+mod eio;
+mod handle;
 mod hostname;
+mod interrupts;
 pub mod io;
+#[cfg(feature = "log-spi")]
+mod log_spi;
+mod macraw;
+#[cfg(feature = "embedded-nal")]
+mod nal;
+mod net_config;
+mod phy;
+mod socket_info;
+mod socket_stats;
+#[cfg(feature = "stats")]
+mod stats;
 mod tcp;
+#[cfg(feature = "test-util")]
+pub mod testutil;
 mod udp;
 
+use bring_up::buffer_sizes_fit;
+pub use bring_up::{BringUpConfig, BringUpError};
+pub use chunked::Chunked;
+pub use handle::{SocketClaims, SocketHandle};
 pub use hostname::{Hostname, TryFromStrError};
+pub use interrupts::SocketInterrupts;
 pub use ll::net;
-use ll::{Registers, Sn, SocketCommand, SocketStatus, SOCKETS};
+use ll::{
+    BufferSize, Interrupt, LinkStatus, Mode, Reg, Registers, Sn, SnReg, SocketCommand,
+    SocketInterrupt, SocketInterruptMask, SocketStatus, SOCKETS,
+};
+#[cfg(feature = "log-spi")]
+pub use log_spi::LogSpi;
+pub use macraw::MacRaw;
+#[cfg(feature = "embedded-nal")]
+pub use nal::{NalDevice, NalTcpSocket, NalUdpSocket};
+pub use net_config::NetConfig;
+pub use phy::Phy;
+pub use socket_info::SocketInfo;
+pub use socket_stats::{SocketStats, Stats};
+#[cfg(feature = "stats")]
+pub use stats::SpinStats;
 pub use tcp::{Tcp, TcpReader, TcpWriter};
-pub use udp::{Udp, UdpHeader, UdpReader, UdpWriter};
+pub use udp::{Datagram, SendOutcome, Udp, UdpDatagrams, UdpHeader, UdpReader, UdpWriter};
 pub use w5500_ll as ll;
 
-use net::{Ipv4Addr, SocketAddrV4};
+use core::time::Duration;
+use net::{Eui48Addr, Ipv4Addr, SocketAddrV4};
 
-fn port_is_unique<T: ?Sized, E>(w5500: &mut T, socket: Sn, port: u16) -> Result<bool, E>
+/// Returns the first socket, other than `exclude`, that is not closed and is
+/// bound to `port`.
+fn port_owner<T, E>(w5500: &mut T, exclude: Option<Sn>, port: u16) -> Result<Option<Sn>, E>
 where
-    T: Registers<Error = E>,
+    T: ?Sized + Registers<Error = E>,
 {
     const CLOSED_STATUS: [Result<SocketStatus, u8>; 3] = [
         Ok(SocketStatus::Closed),
         Ok(SocketStatus::CloseWait),
         Ok(SocketStatus::Closing),
     ];
-    for socket in SOCKETS.iter().filter(|s| s != &&socket) {
+    for socket in SOCKETS.iter().filter(|s| Some(**s) != exclude) {
         if w5500.sn_port(*socket)? == port {
             let status = w5500.sn_sr(*socket)?;
             if !CLOSED_STATUS.iter().any(|x| x == &status) {
-                return Ok(false);
+                return Ok(Some(*socket));
             }
         }
     }
+    Ok(None)
+}
+
+fn port_is_unique<T: ?Sized, E>(w5500: &mut T, socket: Sn, port: u16) -> Result<bool, E>
+where
+    T: Registers<Error = E>,
+{
+    Ok(port_owner(w5500, Some(socket), port)?.is_none())
+}
+
+/// Returns the first port, starting at `port`, that is not in use by any
+/// other socket.
+///
+/// Port 0 is skipped, and the search wraps around to port 1.
+fn unique_port<T, E>(w5500: &mut T, socket: Sn, port: u16) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let mut port: u16 = port.max(1);
+    // at most 7 other sockets can be using a port, this will terminate
+    while !port_is_unique(w5500, socket, port)? {
+        port = port.checked_add(1).unwrap_or(1);
+    }
+    Ok(port)
+}
+
+/// Returns the first port in the dynamic (ephemeral) port range,
+/// 49152 to 65535, that is not in use by any other socket.
+fn ephemeral_port<T, E>(w5500: &mut T, socket: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    // at most 7 other sockets can be using a port, this will terminate
+    for port in 49152..=u16::MAX {
+        if port_is_unique(w5500, socket, port)? {
+            return Ok(port);
+        }
+    }
+    unreachable!()
+}
+
+/// Writes the RX and TX buffer sizes of all sockets, without validation.
+fn write_buffer_sizes<T, E>(
+    w5500: &mut T,
+    rx: &[BufferSize; SOCKETS.len()],
+    tx: &[BufferSize; SOCKETS.len()],
+) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    // shrink all sockets first to stay within the 16 KiB total
+    for sn in SOCKETS.iter() {
+        w5500.set_sn_rxbuf_size(*sn, BufferSize::KB0)?;
+        w5500.set_sn_txbuf_size(*sn, BufferSize::KB0)?;
+    }
+    for sn in SOCKETS.iter() {
+        w5500.set_sn_rxbuf_size(*sn, rx[usize::from(*sn)])?;
+        w5500.set_sn_txbuf_size(*sn, tx[usize::from(*sn)])?;
+    }
+    Ok(())
+}
+
+/// Mask of all socket interrupts, writing this to `SN_IR` clears them.
+const ALL_SN_IR: u8 = SocketInterrupt::CON_MASK
+    | SocketInterrupt::DISCON_MASK
+    | SocketInterrupt::RECV_MASK
+    | SocketInterrupt::TIMEOUT_MASK
+    | SocketInterrupt::SENDOK_MASK;
+
+/// Busy-wait until the socket status is equal to `status`.
+fn wait_for_status<T, E>(w5500: &mut T, sn: Sn, status: SocketStatus) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    while w5500.sn_sr(sn)? != Ok(status) {
+        #[cfg(feature = "stats")]
+        if status == SocketStatus::Closed {
+            stats::close_spin()
+        } else {
+            stats::open_spin()
+        }
+    }
+    Ok(())
+}
+
+/// Polls the socket status until the socket is closed, at most 1000 times.
+///
+/// Returns [`Error::Timeout`] if the socket does not close.
+fn wait_for_closed<T, E>(w5500: &mut T, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    const POLLS: u32 = 1000;
+    let mut polls: u32 = 0;
+    while w5500.sn_sr(sn)? != Ok(SocketStatus::Closed) {
+        polls += 1;
+        if polls >= POLLS {
+            return Err(Error::Timeout);
+        }
+        #[cfg(feature = "stats")]
+        stats::close_spin();
+    }
+    Ok(())
+}
+
+/// Performs a software reset, polling the mode register at most 100 times
+/// for the reset to complete.
+///
+/// Returns `false` if the reset did not complete.
+fn reset<T, E, D>(w5500: &mut T, mut delay_ms: D) -> Result<bool, E>
+where
+    T: ?Sized + Registers<Error = E>,
+    D: FnMut(u32),
+{
+    const RESET_POLLS: u32 = 100;
+
+    w5500.set_mr(Mode::DEFAULT.rst())?;
+    let mut polls: u32 = 0;
+    while u8::from(w5500.mr()?) & Mode::RST_MASK != 0 {
+        polls += 1;
+        if polls >= RESET_POLLS {
+            return Ok(false);
+        }
+        delay_ms(1);
+    }
     Ok(true)
 }
 
+/// Maximum number of reads of a 16-bit size register to get a stable value.
+const STABLE_READ_ATTEMPTS: u8 = 8;
+
+/// Reads a 16-bit size register until two consecutive reads agree.
+///
+/// The W5500 datasheet recommends this for SN_RX_RSR and SN_TX_FSR, which
+/// can be read in the middle of an update of the two bytes.
+/// After [`STABLE_READ_ATTEMPTS`] reads the last value is returned.
+fn stable_read<T, E>(
+    w5500: &mut T,
+    mut read: impl FnMut(&mut T) -> Result<u16, E>,
+) -> Result<u16, E>
+where
+    T: ?Sized,
+{
+    let mut prev: u16 = read(w5500)?;
+    for _ in 1..STABLE_READ_ATTEMPTS {
+        let next: u16 = read(w5500)?;
+        if next == prev {
+            break;
+        }
+        prev = next;
+    }
+    Ok(prev)
+}
+
+/// Reads SN_RX_RSR until two consecutive reads agree.
+fn sn_rx_rsr_stable<T, E>(w5500: &mut T, sn: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    stable_read(w5500, |w5500| w5500.sn_rx_rsr(sn))
+}
+
+/// Returns `true` if the socket has received data.
+///
+/// For UDP sockets this is only `true` once a complete datagram is in the RX
+/// buffer.
+fn recv_data_ready<T, E>(w5500: &mut T, sn: Sn) -> Result<bool, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let rsr: u16 = sn_rx_rsr_stable(w5500, sn)?;
+    if rsr == 0 {
+        return Ok(false);
+    }
+    if w5500.sn_sr(sn)? != Ok(SocketStatus::Udp) {
+        return Ok(true);
+    }
+    let payload_len: u16 = match rsr.checked_sub(UdpHeader::LEN) {
+        Some(len) => len,
+        None => return Ok(false),
+    };
+    // the payload length is the last field of the header
+    let ptr: u16 = w5500.sn_rx_rd(sn)?;
+    let mut len: [u8; 2] = [0; 2];
+    w5500.sn_rx_buf(sn, ptr.wrapping_add(UdpHeader::LEN - 2), &mut len)?;
+    Ok(payload_len >= u16::from_be_bytes(len))
+}
+
+/// Reads SN_TX_FSR until two consecutive reads agree.
+fn sn_tx_fsr_stable<T, E>(w5500: &mut T, sn: Sn) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    stable_read(w5500, |w5500| w5500.sn_tx_fsr(sn))
+}
+
+/// Percentage of a `size` byte socket buffer that is in use.
+fn utilization(used: u16, size: Result<BufferSize, u8>) -> u8 {
+    match size.map(|size| size.size_in_bytes()) {
+        // a buffer without any memory is always full
+        Ok(0) | Err(_) => 100,
+        Ok(size) => {
+            let percent: usize = usize::from(used).saturating_mul(100) / size;
+            percent.min(100) as u8
+        }
+    }
+}
+
 /// Higher level W5500 errors.
+///
+/// This enum is non-exhaustive, new variants may be added without a breaking
+/// change.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Error<E> {
     /// Unexpected "end of file".
     ///
@@ -152,6 +451,62 @@ pub enum Error<E> {
     ///
     /// [`nb`]: (https://docs.rs/nb/latest/nb/index.html)
     WouldBlock,
+    /// A send operation timed out.
+    ///
+    /// Returned when the W5500 raised a timeout interrupt instead of a
+    /// send complete interrupt, for example when the destination did not
+    /// respond to ARP requests.
+    SendTimeout,
+    /// An operation did not complete in the time, or number of attempts,
+    /// allowed for it.
+    Timeout,
+    /// The remote host refused the connection.
+    ///
+    /// Returned when the remote host responded to a connection request with
+    /// a reset (RST).
+    ConnectionRefused,
+    /// The socket is not listening for, or connected to, a remote host.
+    NotListening,
+    /// The socket does not have a destination.
+    ///
+    /// Returned by [`Udp::udp_send_checked`] and
+    /// [`Udp::udp_send_if_free_checked`] when no destination has been
+    /// configured, and by [`Udp::udp_send_all`] with the `strict` feature.
+    NotConnected,
+    /// The operation is not supported by the W5500.
+    ///
+    /// Returned by the [`embedded-nal`] network stack when given an IPv6
+    /// address, the W5500 only supports IPv4.
+    ///
+    /// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+    Unsupported,
+    /// The sum of the socket buffer sizes exceeds the 16 KiB of buffer
+    /// memory.
+    BufferOverallocation,
+    /// The device is not a W5500, the version register did not contain the
+    /// expected value.
+    NotFound,
+    /// The local port is in use by another socket.
+    ///
+    /// Contains the socket that is bound to the port.
+    PortInUse(Sn),
+    /// The socket is not in the state required by the operation.
+    ///
+    /// This is only returned when the `strict` feature is enabled, without it
+    /// the socket state is only checked with debug assertions.
+    InvalidState {
+        /// Socket status required by the operation.
+        expected: SocketStatus,
+        /// Socket status read from the socket status register.
+        actual: Result<SocketStatus, u8>,
+    },
+    /// The socket handle is no longer valid.
+    ///
+    /// Returned by the [`embedded-nal`] network stack when a handle is used
+    /// after the W5500 socket it was created for was closed.
+    ///
+    /// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+    StaleHandle,
     /// Errors from the [`Registers`] trait implementation.
     Other(E),
 }
@@ -189,6 +544,65 @@ macro_rules! block {
     };
 }
 
+/// Calls the non-blocking W5500 function `f` until it no longer returns
+/// [`Error::WouldBlock`], or until `timeout` has elapsed.
+///
+/// This is a bounded version of the [`block!`] macro.
+///
+/// `now` is a monotonic clock source, in milliseconds.
+/// The clock is allowed to wrap around, `timeout` must be less
+/// than `u32::MAX`.
+///
+/// `f` is always called at least once, even when `timeout` is zero.
+///
+/// # Errors
+///
+/// * [`Error::Timeout`] if `f` returned [`Error::WouldBlock`] until `timeout`
+///   elapsed.
+/// * Any other error returned by `f`.
+///
+/// # Example
+///
+/// ```
+/// use w5500_hl::{block_timeout, Error};
+///
+/// // fake clock that advances 10 ms on every call
+/// let mut ms: u32 = 0;
+/// let now = || {
+///     ms += 10;
+///     ms
+/// };
+///
+/// let mut attempts: u32 = 0;
+/// let result: Result<(), Error<()>> = block_timeout(
+///     || {
+///         attempts += 1;
+///         Err(Error::WouldBlock)
+///     },
+///     now,
+///     100,
+/// );
+/// assert_eq!(result, Err(Error::Timeout));
+/// assert_eq!(attempts, 10);
+/// ```
+pub fn block_timeout<T, E>(
+    mut f: impl FnMut() -> Result<T, Error<E>>,
+    mut now: impl FnMut() -> u32,
+    timeout: u32,
+) -> Result<T, Error<E>> {
+    let start: u32 = now();
+    loop {
+        match f() {
+            Err(Error::WouldBlock) => {
+                if now().wrapping_sub(start) >= timeout {
+                    return Err(Error::Timeout);
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Methods common to all W5500 socket types.
 pub trait Common: Registers {
     /// Returns the socket address.
@@ -210,157 +624,1213 @@ pub trait Common: Registers {
         Ok(SocketAddrV4::new(ip, port))
     }
 
-    /// Close a socket.
+    /// Returns the source hardware (MAC) address.
     ///
-    /// This will not poll for completion, the socket may not be closed after
-    /// this method has returned.
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{net::Eui48Addr, Common};
+    ///
+    /// let mac: Eui48Addr = w5500.mac()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn mac(&mut self) -> Result<Eui48Addr, Self::Error> {
+        self.shar()
+    }
+
+    /// Sets the source hardware (MAC) address.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The multicast bit, the least significant bit of the first
+    ///   octet, must not be set.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
-    /// use w5500_hl::ll::{Registers, Sn::Sn0};
-    /// use w5500_hl::Common;
+    /// use w5500_hl::{net::Eui48Addr, Common};
     ///
-    /// w5500.close(Sn0)?;
+    /// // locally administered address
+    /// w5500.set_mac(&Eui48Addr::new(0x02, 0x00, 0x00, 0x12, 0x34, 0x56))?;
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
-    fn close(&mut self, sn: Sn) -> Result<(), Self::Error> {
-        self.set_sn_cr(sn, SocketCommand::Close)
+    fn set_mac(&mut self, mac: &Eui48Addr) -> Result<(), Self::Error> {
+        debug_assert_eq!(mac.octets[0] & 0x01, 0, "MAC address is multicast");
+        self.set_shar(mac)
     }
 
-    /// Returns `true` if the socket state is [Closed].
+    /// Returns the IPv4 network configuration.
     ///
-    /// **Note:** This does not include states that indicate the socket is about
-    /// to close, such as [Closing].
+    /// The gateway, subnet mask, and source IP address registers are read with
+    /// a single burst read.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
-    /// use w5500_hl::ll::{Registers, Sn::Sn0};
-    /// use w5500_hl::{Common, Udp};
+    /// use w5500_hl::{Common, NetConfig};
     ///
-    /// w5500.close(Sn0)?;
-    /// assert!(w5500.is_state_closed(Sn0)?);
-    /// w5500.udp_bind(Sn0, 8080)?;
-    /// assert!(!w5500.is_state_closed(Sn0)?);
+    /// let config: NetConfig = w5500.net_config()?;
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
+    fn net_config(&mut self) -> Result<NetConfig, Self::Error> {
+        // GAR, SUBR, SHAR, and SIPR are contiguous
+        let mut buf: [u8; 18] = [0; 18];
+        self.read(Reg::GAR0.addr(), ll::COMMON_BLOCK_OFFSET, &mut buf)?;
+        Ok(NetConfig {
+            gateway: Ipv4Addr::new(buf[0], buf[1], buf[2], buf[3]),
+            subnet: Ipv4Addr::new(buf[4], buf[5], buf[6], buf[7]),
+            ip: Ipv4Addr::new(buf[14], buf[15], buf[16], buf[17]),
+        })
+    }
+
+    /// Sets the IPv4 network configuration.
     ///
-    /// [Closed]: w5500_ll::SocketStatus::Closed
-    /// [Closing]: w5500_ll::SocketStatus::Closing
-    #[allow(clippy::wrong_self_convention)]
-    fn is_state_closed(&mut self, sn: Sn) -> Result<bool, Self::Error> {
-        Ok(self.sn_sr(sn)? == Ok(SocketStatus::Closed))
+    /// The gateway and subnet mask are written with a single burst write,
+    /// followed by the source IP address.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{net::Ipv4Addr, Common, NetConfig};
+    ///
+    /// w5500.set_net_config(&NetConfig {
+    ///     ip: Ipv4Addr::new(192, 168, 0, 50),
+    ///     subnet: Ipv4Addr::new(255, 255, 255, 0),
+    ///     gateway: Ipv4Addr::new(192, 168, 0, 1),
+    /// })?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_net_config(&mut self, config: &NetConfig) -> Result<(), Self::Error> {
+        let mut buf: [u8; 8] = [0; 8];
+        buf[..4].copy_from_slice(&config.gateway.octets);
+        buf[4..].copy_from_slice(&config.subnet.octets);
+        self.write(Reg::GAR0.addr(), ll::COMMON_BLOCK_OFFSET, &buf)?;
+        self.set_sipr(&config.ip)
     }
 
-    /// Returns `true` if the socket state is any valid TCP state as described
-    /// in [RFC 793].
+    /// Returns a snapshot of the socket registers.
     ///
-    /// Valid TCP states include:
+    /// The registers are read with two burst reads, this is intended for
+    /// debugging, such as logging the socket state in an error handler.
     ///
-    /// * [Closed]
-    /// * [Listen]
-    /// * [SynSent]
-    /// * [SynRecv]
-    /// * [Established]
-    /// * [FinWait]
-    /// * [Closing]
-    /// * [CloseWait]
-    /// * [TimeWait]
-    /// * [LastAck]
+    /// # Example
     ///
-    /// **Note:** This **does not** include the W5500 [Init] state.
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::{Common, SocketInfo};
+    ///
+    /// let info: SocketInfo = w5500.socket_info(Sn0)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn socket_info(&mut self, sn: Sn) -> Result<SocketInfo, Self::Error> {
+        let mut head: [u8; SocketInfo::HEAD_LEN] = [0; SocketInfo::HEAD_LEN];
+        self.read(SnReg::MR.addr(), sn.block(), &mut head)?;
+        let mut ptrs: [u8; SocketInfo::PTRS_LEN] = [0; SocketInfo::PTRS_LEN];
+        self.read(SnReg::TX_FSR0.addr(), sn.block(), &mut ptrs)?;
+        Ok(SocketInfo::deser(sn, &head, &ptrs))
+    }
+
+    /// Returns the socket bound to the local `port`, if any.
+    ///
+    /// Closed sockets, and sockets that are closing, are not considered to be
+    /// using their port.
+    ///
+    /// Binding a socket to a port in use by another socket steals the port,
+    /// use this to check that a port is free before binding.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
-    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::ll::Sn::Sn0;
     /// use w5500_hl::{Common, Udp};
     ///
-    /// w5500.close(Sn0)?;
-    /// assert!(w5500.is_state_tcp(Sn0)?);
-    /// w5500.udp_bind(Sn0, 8080)?;
-    /// assert!(!w5500.is_state_tcp(Sn0)?);
+    /// if w5500.local_port_in_use(8080)?.is_none() {
+    ///     w5500.udp_bind(Sn0, 8080)?;
+    /// }
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
-    ///
-    /// [RFC 793]: https://tools.ietf.org/html/rfc793
-    /// [Init]: w5500_ll::SocketStatus::Init
-    /// [Closed]: w5500_ll::SocketStatus::Closed
-    /// [Listen]: w5500_ll::SocketStatus::Listen
-    /// [SynSent]: w5500_ll::SocketStatus::SynSent
-    /// [SynRecv]: w5500_ll::SocketStatus::SynRecv
-    /// [Established]: w5500_ll::SocketStatus::Established
-    /// [FinWait]: w5500_ll::SocketStatus::FinWait
-    /// [Closing]: w5500_ll::SocketStatus::Closing
-    /// [CloseWait]: w5500_ll::SocketStatus::CloseWait
-    /// [TimeWait]: w5500_ll::SocketStatus::TimeWait
-    /// [LastAck]: w5500_ll::SocketStatus::LastAck
-    #[allow(clippy::wrong_self_convention)]
-    fn is_state_tcp(&mut self, sn: Sn) -> Result<bool, Self::Error> {
-        // Hopefully the compiler will optimize this to check that the state is
-        // not MACRAW, UDP, or INIT.
-        // Leaving it as-is since the code is more readable this way.
-        Ok(matches!(
-            self.sn_sr(sn)?,
-            Ok(SocketStatus::Closed)
-                | Ok(SocketStatus::Listen)
-                | Ok(SocketStatus::SynSent)
-                | Ok(SocketStatus::SynRecv)
-                | Ok(SocketStatus::Established)
-                | Ok(SocketStatus::FinWait)
-                | Ok(SocketStatus::Closing)
-                | Ok(SocketStatus::CloseWait)
-                | Ok(SocketStatus::TimeWait)
-                | Ok(SocketStatus::LastAck)
-        ))
+    fn local_port_in_use(&mut self, port: u16) -> Result<Option<Sn>, Self::Error> {
+        port_owner(self, None, port)
     }
 
-    /// Returns `true` if the socket state is [Udp].
+    /// Close a socket.
+    ///
+    /// This will not poll for completion, the socket may not be closed after
+    /// this method has returned.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
     /// use w5500_hl::ll::{Registers, Sn::Sn0};
-    /// use w5500_hl::{Common, Udp};
+    /// use w5500_hl::Common;
     ///
     /// w5500.close(Sn0)?;
-    /// assert!(!w5500.is_state_udp(Sn0)?);
-    /// w5500.udp_bind(Sn0, 8080)?;
-    /// assert!(w5500.is_state_udp(Sn0)?);
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
-    ///
-    /// [Udp]: w5500_ll::SocketStatus::Udp
-    #[allow(clippy::wrong_self_convention)]
-    fn is_state_udp(&mut self, sn: Sn) -> Result<bool, Self::Error> {
-        Ok(self.sn_sr(sn)? == Ok(SocketStatus::Udp))
-    }
-}
-
-/// Implement the common socket trait for any structure that implements [`w5500_ll::Registers`].
-impl<T> Common for T where T: Registers {}
-
-#[cfg(test)]
-mod tests {
-    use core::convert::Infallible;
-
-    use super::*;
-
-    struct MockRegisters {
-        pub socket_ports: [u16; SOCKETS.len()],
-        pub socket_status: [SocketStatus; SOCKETS.len()],
+    fn close(&mut self, sn: Sn) -> Result<(), Self::Error> {
+        self.set_sn_cr(sn, SocketCommand::Close)
     }
 
-    impl Registers for MockRegisters {
-        type Error = Infallible;
-
-        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
-            unimplemented!()
+    /// Closes all sockets and clears all socket interrupts.
+    ///
+    /// This closes all sockets with [`close_all`](Common::close_all), then
+    /// clears the interrupts of every socket, establishing a known state after
+    /// power on or a reset.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::Timeout`] if a socket did not close.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.init_sockets()?;
+    /// assert!(w5500.is_state_closed(Sn0)?);
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn init_sockets(&mut self) -> Result<(), Error<Self::Error>> {
+        self.close_all()?;
+        for sn in SOCKETS.iter() {
+            self.set_sn_ir(*sn, ALL_SN_IR)?;
+        }
+        Ok(())
+    }
+
+    /// Closes all sockets, and waits for every socket to reach the [Closed]
+    /// state.
+    ///
+    /// Sockets that are already closed are unaffected.
+    /// Unlike [`init_sockets`](Common::init_sockets) this does not clear the
+    /// socket interrupts, and the status of each socket is polled at most
+    /// 1000 times, returning [`Error::Timeout`] if a socket does not close.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::Timeout`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.close_all()?;
+    /// assert!(w5500.is_state_closed(Sn0)?);
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [Closed]: w5500_ll::SocketStatus::Closed
+    fn close_all(&mut self) -> Result<(), Error<Self::Error>> {
+        for sn in SOCKETS.iter() {
+            self.set_sn_cr(*sn, SocketCommand::Close)?;
+        }
+        for sn in SOCKETS.iter() {
+            wait_for_closed(self, *sn)?;
+        }
+        Ok(())
+    }
+
+    /// Performs a software reset, and waits for the reset to complete.
+    ///
+    /// This sets the reset bit in the [`Registers::mr`] register, then polls
+    /// the register until the W5500 clears the bit, calling `delay_ms(1)`
+    /// between polls.
+    /// All registers are set to their reset values, including the network
+    /// configuration and the socket buffer sizes.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::Timeout`] if the reset did not complete within 100 polls.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn delay_ms(_ms: u32) {}
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.software_reset(delay_ms)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn software_reset<D>(&mut self, mut delay_ms: D) -> Result<(), Error<Self::Error>>
+    where
+        D: FnMut(u32),
+    {
+        if reset(self, &mut delay_ms)? {
+            Ok(())
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
+    /// Returns `true` if the version register contains the expected value,
+    /// [`w5500_ll::VERSION`].
+    ///
+    /// This is useful to check the SPI bus before using the device, an
+    /// unexpected value usually indicates a SPI wiring problem, or that the
+    /// device is not a W5500.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// assert!(w5500.is_version_valid()?, "W5500 not found");
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn is_version_valid(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.version()? == ll::VERSION)
+    }
+
+    /// Checks that a W5500 is present on the SPI bus.
+    ///
+    /// This is a single read of the version register, and it is the
+    /// recommended first call after power-up and SPI initialization to fail
+    /// fast on a SPI wiring problem.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::NotFound`] if the version register does not contain
+    ///   [`w5500_ll::VERSION`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{Common, Error};
+    ///
+    /// match w5500.probe() {
+    ///     Ok(()) => (),
+    ///     Err(Error::NotFound) => panic!("W5500 not found, check the SPI wiring"),
+    ///     Err(e) => panic!("SPI error: {e:?}"),
+    /// }
+    /// ```
+    fn probe(&mut self) -> Result<(), Error<Self::Error>> {
+        if self.is_version_valid()? {
+            Ok(())
+        } else {
+            Err(Error::NotFound)
+        }
+    }
+
+    /// Performs the W5500 startup sequence.
+    ///
+    /// This performs the following sequence, returning an error from the
+    /// step that failed:
+    ///
+    /// 1. Check the socket buffer sizes in `config`.
+    /// 2. Software reset, and wait for the reset to complete.
+    /// 3. Check the version register.
+    /// 4. Set the MAC address, IP address, gateway, and subnet mask.
+    /// 5. Set the socket buffer sizes.
+    /// 6. Set the mode, retry time, retry count, and socket interrupt mask,
+    ///    for each of these that is not `None` in `config`.
+    /// 7. Wait for the Ethernet link to come up, unless
+    ///    [`BringUpConfig::link_timeout`] is `None`.
+    ///
+    /// `delay_ms` is called to wait for the given number of milliseconds
+    /// between polls of the W5500 registers.
+    ///
+    /// This does not perform a hardware reset with the reset pin, use the
+    /// `reset` function of the [`w5500_ll`] `eh0` or `eh1` modules before
+    /// calling this method if needed.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`BringUpError::BufferOverallocation`]
+    /// * [`BringUpError::ResetTimeout`]
+    /// * [`BringUpError::Version`]
+    /// * [`BringUpError::LinkTimeout`]
+    /// * [`BringUpError::Other`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn delay_ms(_ms: u32) {}
+    /// use w5500_hl::{
+    ///     net::{Eui48Addr, Ipv4Addr},
+    ///     BringUpConfig, Common,
+    /// };
+    ///
+    /// const CONFIG: BringUpConfig = BringUpConfig::new(
+    ///     Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC),
+    ///     Ipv4Addr::new(192, 168, 0, 50),
+    ///     Ipv4Addr::new(192, 168, 0, 1),
+    ///     Ipv4Addr::new(255, 255, 255, 0),
+    /// );
+    ///
+    /// w5500.bring_up(&CONFIG, delay_ms)?;
+    /// # Ok::<(), w5500_hl::BringUpError<_>>(())
+    /// ```
+    fn bring_up<D>(
+        &mut self,
+        config: &BringUpConfig,
+        mut delay_ms: D,
+    ) -> Result<(), BringUpError<Self::Error>>
+    where
+        D: FnMut(u32),
+    {
+        const LINK_POLL_MS: u32 = 10;
+
+        if !buffer_sizes_fit(&config.rx_buf_sizes) || !buffer_sizes_fit(&config.tx_buf_sizes) {
+            return Err(BringUpError::BufferOverallocation);
+        }
+
+        if !reset(self, &mut delay_ms)? {
+            return Err(BringUpError::ResetTimeout);
+        }
+
+        let version: u8 = self.version()?;
+        if version != ll::VERSION {
+            return Err(BringUpError::Version(version));
+        }
+
+        self.set_shar(&config.mac)?;
+        self.set_sipr(&config.ip)?;
+        self.set_gar(&config.gateway)?;
+        self.set_subr(&config.subnet_mask)?;
+
+        write_buffer_sizes(self, &config.rx_buf_sizes, &config.tx_buf_sizes)?;
+
+        if let Some(mode) = config.mode {
+            self.set_mr(mode)?;
+        }
+        if let Some(rtr) = config.retry_time {
+            self.set_rtr(rtr)?;
+        }
+        if let Some(rcr) = config.retry_count {
+            self.set_rcr(rcr)?;
+        }
+        if let Some(simr) = config.socket_interrupt_mask {
+            self.set_simr(simr)?;
+        }
+
+        if let Some(timeout) = config.link_timeout {
+            let timeout_ms: u32 = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+            let mut waited_ms: u32 = 0;
+            while self.phycfgr()?.lnk() != LinkStatus::Up {
+                if waited_ms >= timeout_ms {
+                    return Err(BringUpError::LinkTimeout);
+                }
+                delay_ms(LINK_POLL_MS);
+                waited_ms = waited_ms.saturating_add(LINK_POLL_MS);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the socket status.
+    ///
+    /// This is the same as [`Registers::sn_sr`], provided on this trait so
+    /// that state machines can branch on the socket status without the
+    /// [`Registers`] trait in scope.
+    ///
+    /// The inner result is `Err` with the raw value when the socket status
+    /// register does not contain a known status, this can occur while the
+    /// W5500 is changing state, read the status again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Sn::Sn0, SocketStatus},
+    ///     Common,
+    /// };
+    ///
+    /// match w5500.socket_status(Sn0)? {
+    ///     Ok(SocketStatus::SynSent) => {
+    ///         // still connecting
+    ///     }
+    ///     Ok(SocketStatus::CloseWait) => {
+    ///         // the remote host closed the connection
+    ///     }
+    ///     Ok(_) => (),
+    ///     Err(_) => {
+    ///         // transitional value, read again
+    ///     }
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn socket_status(&mut self, sn: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+        self.sn_sr(sn)
+    }
+
+    /// Returns `true` if the socket state is [Closed].
+    ///
+    /// **Note:** This does not include states that indicate the socket is about
+    /// to close, such as [Closing].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::{Common, Udp};
+    ///
+    /// w5500.close(Sn0)?;
+    /// assert!(w5500.is_state_closed(Sn0)?);
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// assert!(!w5500.is_state_closed(Sn0)?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [Closed]: w5500_ll::SocketStatus::Closed
+    /// [Closing]: w5500_ll::SocketStatus::Closing
+    #[allow(clippy::wrong_self_convention)]
+    fn is_state_closed(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        Ok(self.sn_sr(sn)? == Ok(SocketStatus::Closed))
+    }
+
+    /// Returns `true` if the socket state is any valid TCP state as described
+    /// in [RFC 793].
+    ///
+    /// Valid TCP states include:
+    ///
+    /// * [Closed]
+    /// * [Listen]
+    /// * [SynSent]
+    /// * [SynRecv]
+    /// * [Established]
+    /// * [FinWait]
+    /// * [Closing]
+    /// * [CloseWait]
+    /// * [TimeWait]
+    /// * [LastAck]
+    ///
+    /// **Note:** This **does not** include the W5500 [Init] state.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::{Common, Udp};
+    ///
+    /// w5500.close(Sn0)?;
+    /// assert!(w5500.is_state_tcp(Sn0)?);
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// assert!(!w5500.is_state_tcp(Sn0)?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [RFC 793]: https://tools.ietf.org/html/rfc793
+    /// [Init]: w5500_ll::SocketStatus::Init
+    /// [Closed]: w5500_ll::SocketStatus::Closed
+    /// [Listen]: w5500_ll::SocketStatus::Listen
+    /// [SynSent]: w5500_ll::SocketStatus::SynSent
+    /// [SynRecv]: w5500_ll::SocketStatus::SynRecv
+    /// [Established]: w5500_ll::SocketStatus::Established
+    /// [FinWait]: w5500_ll::SocketStatus::FinWait
+    /// [Closing]: w5500_ll::SocketStatus::Closing
+    /// [CloseWait]: w5500_ll::SocketStatus::CloseWait
+    /// [TimeWait]: w5500_ll::SocketStatus::TimeWait
+    /// [LastAck]: w5500_ll::SocketStatus::LastAck
+    #[allow(clippy::wrong_self_convention)]
+    fn is_state_tcp(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        // Hopefully the compiler will optimize this to check that the state is
+        // not MACRAW, UDP, or INIT.
+        // Leaving it as-is since the code is more readable this way.
+        Ok(matches!(
+            self.sn_sr(sn)?,
+            Ok(SocketStatus::Closed)
+                | Ok(SocketStatus::Listen)
+                | Ok(SocketStatus::SynSent)
+                | Ok(SocketStatus::SynRecv)
+                | Ok(SocketStatus::Established)
+                | Ok(SocketStatus::FinWait)
+                | Ok(SocketStatus::Closing)
+                | Ok(SocketStatus::CloseWait)
+                | Ok(SocketStatus::TimeWait)
+                | Ok(SocketStatus::LastAck)
+        ))
+    }
+
+    /// Returns `true` if the socket state is [Udp].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::{Common, Udp};
+    ///
+    /// w5500.close(Sn0)?;
+    /// assert!(!w5500.is_state_udp(Sn0)?);
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// assert!(w5500.is_state_udp(Sn0)?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [Udp]: w5500_ll::SocketStatus::Udp
+    #[allow(clippy::wrong_self_convention)]
+    fn is_state_udp(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        Ok(self.sn_sr(sn)? == Ok(SocketStatus::Udp))
+    }
+
+    /// Reads and clears the common interrupt register.
+    ///
+    /// Only the interrupts that were raised at the time of the read are
+    /// cleared, interrupts raised after the read will remain pending.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Interrupt, Registers};
+    /// use w5500_hl::Common;
+    ///
+    /// let ir: Interrupt = w5500.take_common_interrupts()?;
+    /// if ir.conflict() {
+    ///     // handle IP conflict
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn take_common_interrupts(&mut self) -> Result<Interrupt, Self::Error> {
+        let ir: Interrupt = self.ir()?;
+        if u8::from(ir) != 0 {
+            self.set_ir(ir)?;
+        }
+        Ok(ir)
+    }
+
+    /// Reads and clears the socket interrupt register.
+    ///
+    /// Only the interrupts that were raised at the time of the read are
+    /// cleared, interrupts raised after the read will remain pending.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0, SocketInterrupt};
+    /// use w5500_hl::Common;
+    ///
+    /// let sn_ir: SocketInterrupt = w5500.take_socket_interrupts(Sn0)?;
+    /// if sn_ir.recv_raised() {
+    ///     // handle received data
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn take_socket_interrupts(&mut self, sn: Sn) -> Result<SocketInterrupt, Self::Error> {
+        let sn_ir: SocketInterrupt = self.sn_ir(sn)?;
+        if sn_ir.any_raised() {
+            self.set_sn_ir(sn, sn_ir)?;
+        }
+        Ok(sn_ir)
+    }
+
+    /// Enables interrupts on the INT pin for a single socket.
+    ///
+    /// This writes `mask` to the socket interrupt mask register (SN_IMR),
+    /// and enables the socket in the socket interrupt mask register (SIMR).
+    /// Interrupt settings of other sockets are not modified.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Sn::Sn0, SocketInterruptMask};
+    /// use w5500_hl::Common;
+    ///
+    /// // only interrupt on received data
+    /// const MASK: SocketInterruptMask = SocketInterruptMask::ALL_MASKED.unmask_recv();
+    /// w5500.enable_socket_interrupts(Sn0, MASK)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn enable_socket_interrupts(
+        &mut self,
+        sn: Sn,
+        mask: SocketInterruptMask,
+    ) -> Result<(), Self::Error> {
+        self.set_sn_imr(sn, mask)?;
+        let simr: u8 = self.simr()?;
+        self.set_simr(simr | sn.bitmask())
+    }
+
+    /// Sets the sockets that can raise interrupts on the INT pin.
+    ///
+    /// This writes the socket interrupt mask register (SIMR), sockets not
+    /// in `sockets` are disabled.
+    /// Use [`Common::enable_socket_interrupts`] to select the interrupts of
+    /// each socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn;
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.set_interrupt_mask(&[Sn::Sn1, Sn::Sn3])?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_interrupt_mask(&mut self, sockets: &[Sn]) -> Result<(), Self::Error> {
+        let simr: u8 = sockets.iter().fold(0, |simr, sn| simr | sn.bitmask());
+        self.set_simr(simr)
+    }
+
+    /// Reads the pending interrupts of all sockets.
+    ///
+    /// This reads the socket interrupt register (SIR) once, then reads the
+    /// socket interrupt register (SN_IR) of each socket with a pending
+    /// interrupt.
+    ///
+    /// The interrupts are not cleared, use [`Common::clear_interrupts`] after
+    /// handling them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{Common, SocketInterrupts};
+    ///
+    /// let interrupts: SocketInterrupts = w5500.poll_interrupts()?;
+    /// for (sn, sn_ir) in interrupts.iter() {
+    ///     // handle socket interrupts
+    /// }
+    /// w5500.clear_interrupts(&interrupts)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn poll_interrupts(&mut self) -> Result<SocketInterrupts, Self::Error> {
+        let mut interrupts: SocketInterrupts = SocketInterrupts::NONE;
+        interrupts.sir = self.sir()?;
+        for sn in SOCKETS.iter() {
+            if interrupts.sir & sn.bitmask() != 0 {
+                interrupts.sn_ir[usize::from(*sn)] = self.sn_ir(*sn)?;
+            }
+        }
+        Ok(interrupts)
+    }
+
+    /// Clears the socket interrupts returned by [`Common::poll_interrupts`].
+    ///
+    /// Only the interrupts that were pending at the time of the poll are
+    /// cleared, interrupts raised after the poll will remain pending.
+    ///
+    /// # Example
+    ///
+    /// See [`Common::poll_interrupts`].
+    fn clear_interrupts(&mut self, interrupts: &SocketInterrupts) -> Result<(), Self::Error> {
+        for (sn, sn_ir) in interrupts.iter() {
+            if sn_ir.any_raised() {
+                self.set_sn_ir(sn, sn_ir)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the lowest numbered socket with received data, or `None` if no
+    /// socket has received data.
+    ///
+    /// This reads the socket interrupt (SIR) and socket interrupt mask (SIMR)
+    /// registers in a single transfer, then reads the received size register
+    /// (SN_RX_RSR) of each candidate socket in order, stopping at the first
+    /// socket with received data.
+    ///
+    /// * Sockets with interrupts enabled in SIMR, see
+    ///   [`Common::set_interrupt_mask`], are only candidates while an
+    ///   interrupt is pending in SIR.
+    ///   Received data is not detected after the RECV interrupt is cleared.
+    /// * All other sockets are always candidates.
+    ///
+    /// UDP sockets are only returned once a complete datagram, the 8 byte
+    /// W5500 UDP header and the payload, is in the RX buffer.
+    ///
+    /// The received data must be read from the returned socket, otherwise the
+    /// same socket is returned again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Common, Tcp, Udp};
+    ///
+    /// const DNS_SOCKET: Sn = Sn::Sn1;
+    /// const HTTP_SOCKET: Sn = Sn::Sn2;
+    ///
+    /// let mut buf: [u8; 256] = [0; 256];
+    /// while let Some(sn) = w5500.recv_ready()? {
+    ///     match sn {
+    ///         DNS_SOCKET => {
+    ///             let (len, origin) = w5500.udp_recv_from(sn, &mut buf)?;
+    ///             // handle the datagram
+    ///         }
+    ///         HTTP_SOCKET => {
+    ///             let len: u16 = w5500.tcp_read(sn, &mut buf)?;
+    ///             // handle the data
+    ///         }
+    ///         // no other sockets are open
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn recv_ready(&mut self) -> Result<Option<Sn>, Self::Error> {
+        // SIR and SIMR are contiguous
+        let mut buf: [u8; 2] = [0; 2];
+        self.read(Reg::SIR.addr(), ll::COMMON_BLOCK_OFFSET, &mut buf)?;
+        let [sir, simr] = buf;
+        let candidates: u8 = sir | !simr;
+        for sn in SOCKETS.iter() {
+            if candidates & sn.bitmask() != 0 && recv_data_ready(self, *sn)? {
+                return Ok(Some(*sn));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Allocates the entire 16 KiB of RX buffer memory to a single socket.
+    ///
+    /// The RX buffers of all other sockets are set to 0 KiB, the TX buffers
+    /// are not modified.
+    /// On success, returns the RX buffer size of `sn` in bytes, as read back
+    /// from the socket RX buffer size register.
+    /// A register value that is not a valid buffer size is returned as 0.
+    ///
+    /// This is useful for applications that receive large datagrams on a
+    /// single socket.
+    ///
+    /// The buffer sizes should only be changed while the sockets are closed,
+    /// the other sockets will be unable to receive any data.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// let size: u16 = w5500.maximize_socket_rx(Sn0)?;
+    /// assert_eq!(size, 16 * 1024);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn maximize_socket_rx(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        // shrink the other sockets first to stay within the 16 KiB total
+        for other in SOCKETS.iter().filter(|other| **other != sn) {
+            self.set_sn_rxbuf_size(*other, BufferSize::KB0)?;
+        }
+        self.set_sn_rxbuf_size(sn, BufferSize::KB16)?;
+        let size: usize = self
+            .sn_rxbuf_size(sn)?
+            .map_or(0, |size| size.size_in_bytes());
+        Ok(size as u16)
+    }
+
+    /// Sets the RX and TX buffer sizes of all sockets.
+    ///
+    /// `rx` and `tx` are indexed by socket number.
+    /// The sum of the RX sizes, and the sum of the TX sizes, must each not
+    /// exceed the 16 KiB of buffer memory, otherwise
+    /// [`Error::BufferOverallocation`] is returned before any registers are
+    /// written.
+    ///
+    /// The buffer sizes should only be changed while the sockets are closed.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::BufferOverallocation`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::BufferSize;
+    /// use w5500_hl::Common;
+    ///
+    /// // two sockets with 8 KiB buffers
+    /// let mut sizes: [BufferSize; 8] = [BufferSize::KB0; 8];
+    /// sizes[0] = BufferSize::KB8;
+    /// sizes[1] = BufferSize::KB8;
+    /// w5500.set_socket_buffer_sizes(&sizes, &sizes)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn set_socket_buffer_sizes(
+        &mut self,
+        rx: &[BufferSize; SOCKETS.len()],
+        tx: &[BufferSize; SOCKETS.len()],
+    ) -> Result<(), Error<Self::Error>> {
+        if !buffer_sizes_fit(rx) || !buffer_sizes_fit(tx) {
+            return Err(Error::BufferOverallocation);
+        }
+        write_buffer_sizes(self, rx, tx)?;
+        Ok(())
+    }
+
+    /// Restores the reset value of the socket buffer sizes, 2 KiB of RX and
+    /// TX buffer for every socket.
+    ///
+    /// This must only be done while the sockets are closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.maximize_socket_rx(Sn0)?;
+    /// // ...
+    /// w5500.close_all()?;
+    /// w5500.reset_socket_buffers()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn reset_socket_buffers(&mut self) -> Result<(), Self::Error> {
+        const DEFAULT: [BufferSize; SOCKETS.len()] = [BufferSize::KB2; SOCKETS.len()];
+        write_buffer_sizes(self, &DEFAULT, &DEFAULT)
+    }
+
+    /// Returns the percentage of the socket TX buffer that is in use, from 0
+    /// to 100.
+    ///
+    /// This is computed from the TX free size and the TX buffer size, and is
+    /// useful as a backpressure signal for rate adaptive senders.
+    ///
+    /// A socket with a 0 KiB TX buffer is always 100% utilized.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// if w5500.tx_utilization(Sn0)? > 75 {
+    ///     // slow down
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn tx_utilization(&mut self, sn: Sn) -> Result<u8, Self::Error> {
+        let size: Result<BufferSize, u8> = self.sn_txbuf_size(sn)?;
+        let free: u16 = sn_tx_fsr_stable(self, sn)?;
+        let total: u16 = size.map_or(0, |size| size.size_in_bytes() as u16);
+        Ok(utilization(total.saturating_sub(free), size))
+    }
+
+    /// Returns the percentage of the socket RX buffer that is in use, from 0
+    /// to 100.
+    ///
+    /// This is computed from the RX received size and the RX buffer size.
+    ///
+    /// A socket with a 0 KiB RX buffer is always 100% utilized.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::Sn::Sn0;
+    /// use w5500_hl::Common;
+    ///
+    /// let percent: u8 = w5500.rx_utilization(Sn0)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn rx_utilization(&mut self, sn: Sn) -> Result<u8, Self::Error> {
+        let size: Result<BufferSize, u8> = self.sn_rxbuf_size(sn)?;
+        let used: u16 = sn_rx_rsr_stable(self, sn)?;
+        Ok(utilization(used, size))
+    }
+
+    /// Sets the retry time.
+    ///
+    /// This is the time the W5500 waits for a response before retransmitting
+    /// a packet, and applies to ARP and TCP retransmissions on all sockets.
+    /// See [`Registers::rtr`] for more information.
+    ///
+    /// The duration is rounded to the nearest multiple of 100 µs, and
+    /// clamped to the range supported by the W5500 of 100 µs to 6.5535
+    /// seconds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use core::time::Duration;
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.set_retry_time(Duration::from_millis(500))?;
+    /// assert_eq!(w5500.retry_time()?, Duration::from_millis(500));
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_retry_time(&mut self, retry_time: Duration) -> Result<(), Self::Error> {
+        const TICK_MICROS: u128 = 100;
+        let ticks: u128 = (retry_time.as_micros() + TICK_MICROS / 2) / TICK_MICROS;
+        let rtr: u16 = u16::try_from(ticks.max(1)).unwrap_or(u16::MAX);
+        self.set_rtr(rtr)
+    }
+
+    /// Returns the retry time.
+    ///
+    /// See [`Common::set_retry_time`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use core::time::Duration;
+    /// use w5500_hl::Common;
+    ///
+    /// let retry_time: Duration = w5500.retry_time()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn retry_time(&mut self) -> Result<Duration, Self::Error> {
+        let rtr: u16 = self.rtr()?;
+        Ok(Duration::from_micros(u64::from(rtr) * 100))
+    }
+
+    /// Sets the retry count.
+    ///
+    /// This is the number of retransmissions before a timeout, and applies to
+    /// ARP and TCP retransmissions on all sockets.
+    /// See [`Registers::rcr`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.set_retry_count(16)?;
+    /// assert_eq!(w5500.retry_count()?, 16);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_retry_count(&mut self, count: u8) -> Result<(), Self::Error> {
+        self.set_rcr(count)
+    }
+
+    /// Returns the retry count.
+    ///
+    /// See [`Common::set_retry_count`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// let retry_count: u8 = w5500.retry_count()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn retry_count(&mut self) -> Result<u8, Self::Error> {
+        self.rcr()
+    }
+
+    /// Enables or disables ping block.
+    ///
+    /// When ping block is enabled the W5500 does not respond to ICMP echo
+    /// (ping) requests.
+    ///
+    /// This is a read-modify-write of the [`Registers::mr`] register, the
+    /// other mode bits are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.set_ping_block(true)?;
+    /// assert!(w5500.ping_block()?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_ping_block(&mut self, block: bool) -> Result<(), Self::Error> {
+        let mode: Mode = self.mr()?;
+        let mode: Mode = if block {
+            mode.enable_pb()
+        } else {
+            mode.disable_pb()
+        };
+        self.set_mr(mode)
+    }
+
+    /// Returns `true` if ping block is enabled.
+    ///
+    /// See [`Common::set_ping_block`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// let ping_block: bool = w5500.ping_block()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn ping_block(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.mr()?.pb_enabled())
+    }
+
+    /// Enables or disables force ARP.
+    ///
+    /// When force ARP is enabled the W5500 sends an ARP request before every
+    /// transmission, re-resolving the destination hardware address.
+    /// This applies to all sockets, the W5500 does not have a per-socket
+    /// force ARP option.
+    ///
+    /// This is a read-modify-write of the [`Registers::mr`] register, the
+    /// other mode bits are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// w5500.set_force_arp(true)?;
+    /// assert!(w5500.force_arp()?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_force_arp(&mut self, force_arp: bool) -> Result<(), Self::Error> {
+        let mode: Mode = self.mr()?;
+        let mode: Mode = if force_arp {
+            mode.enable_farp()
+        } else {
+            mode.disable_farp()
+        };
+        self.set_mr(mode)
+    }
+
+    /// Returns `true` if force ARP is enabled.
+    ///
+    /// See [`Common::set_force_arp`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Common;
+    ///
+    /// let force_arp: bool = w5500.force_arp()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn force_arp(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.mr()?.farp_enabled())
+    }
+
+    /// Restores the socket TX write pointer to a previously saved value,
+    /// discarding any data staged after that point.
+    ///
+    /// This must be called before the send command is issued, data that has
+    /// already been sent cannot be recalled.
+    ///
+    /// **Note:** [`TcpWriter`] and [`UdpWriter`] only update the TX write
+    /// pointer when sending, dropping a writer without sending already
+    /// discards the data written to it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::{Common, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let saved_wr: u16 = w5500.sn_tx_wr(Sn0)?;
+    /// // ... stage data with the low level register methods
+    /// w5500.abort_tx(Sn0, saved_wr)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn abort_tx(&mut self, sn: Sn, saved_wr: u16) -> Result<(), Self::Error> {
+        self.set_sn_tx_wr(sn, saved_wr)
+    }
+
+    /// Returns the busy-wait loop statistics.
+    ///
+    /// The statistics are global, they are shared between all W5500
+    /// instances.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::{Common, SpinStats, Udp};
+    ///
+    /// w5500.reset_spin_stats();
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let stats: SpinStats = w5500.spin_stats();
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    fn spin_stats(&self) -> SpinStats {
+        stats::get()
+    }
+
+    /// Resets the busy-wait loop statistics to zero.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{Common, SpinStats};
+    ///
+    /// w5500.reset_spin_stats();
+    /// assert_eq!(w5500.spin_stats(), SpinStats::default());
+    /// ```
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    fn reset_spin_stats(&self) {
+        stats::reset()
+    }
+}
+
+/// Implement the common socket trait for any structure that implements [`w5500_ll::Registers`].
+impl<T> Common for T where T: Registers {}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+
+    struct MockRegisters {
+        pub socket_ports: [u16; SOCKETS.len()],
+        pub socket_status: [SocketStatus; SOCKETS.len()],
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
         }
 
         fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
@@ -397,4 +1867,81 @@ mod tests {
         // other socket on same port
         assert!(!port_is_unique(&mut mock, Sn::Sn1, 0).unwrap());
     }
+
+    #[test]
+    fn test_block_timeout() {
+        // clock wraps around during the timeout
+        let mut ms: u32 = u32::MAX - 25;
+        let mut now = || {
+            ms = ms.wrapping_add(10);
+            ms
+        };
+        let mut attempts: u8 = 0;
+        assert_eq!(
+            block_timeout::<(), Infallible>(
+                || {
+                    attempts += 1;
+                    Err(Error::WouldBlock)
+                },
+                &mut now,
+                50,
+            ),
+            Err(Error::Timeout)
+        );
+        assert_eq!(attempts, 5);
+
+        let mut attempts: u8 = 0;
+        assert_eq!(
+            block_timeout::<u8, Infallible>(
+                || {
+                    attempts += 1;
+                    if attempts == 3 {
+                        Ok(attempts)
+                    } else {
+                        Err(Error::WouldBlock)
+                    }
+                },
+                &mut now,
+                50,
+            ),
+            Ok(3)
+        );
+
+        assert_eq!(
+            block_timeout::<(), Infallible>(|| Err(Error::SendTimeout), &mut now, 50),
+            Err(Error::SendTimeout)
+        );
+
+        // called once with a zero timeout
+        let mut attempts: u8 = 0;
+        assert_eq!(
+            block_timeout::<(), Infallible>(
+                || {
+                    attempts += 1;
+                    Err(Error::WouldBlock)
+                },
+                &mut now,
+                0,
+            ),
+            Err(Error::Timeout)
+        );
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_local_port_in_use() {
+        let mut mock = MockRegisters {
+            socket_ports: [0; SOCKETS.len()],
+            socket_status: [SocketStatus::Closed; SOCKETS.len()],
+        };
+        assert_eq!(mock.local_port_in_use(0), Ok(None));
+
+        mock.socket_ports[3] = 8080;
+        mock.socket_status[3] = SocketStatus::CloseWait;
+        assert_eq!(mock.local_port_in_use(8080), Ok(None));
+
+        mock.socket_status[3] = SocketStatus::Udp;
+        assert_eq!(mock.local_port_in_use(8080), Ok(Some(Sn::Sn3)));
+        assert_eq!(mock.local_port_in_use(8081), Ok(None));
+    }
 }
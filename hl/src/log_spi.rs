@@ -0,0 +1,97 @@
+use w5500_ll::Registers;
+
+/// Maximum number of data bytes included in each log line.
+const MAX_LOG_DATA: usize = 8;
+
+/// W5500 wrapper that logs every register read and write.
+///
+/// Each [`Registers::read`] and [`Registers::write`] is logged at the trace
+/// level with [`defmt`], after the transfer completes, on a single line:
+///
+/// ```text
+/// R 0x39 0x0 len=1 [4]
+/// W 0x814 0x80 len=12 [C0, A8, 0, 32, 1F, 90, 0, 4] ..
+/// ```
+///
+/// The fields are the address, block select bits, transfer length, and up to
+/// 8 bytes of the data, followed by `..` when the data was truncated.
+///
+/// All methods of the [`Registers`] trait are implemented with `read` and
+/// `write`, when wrapped every transfer of the high level methods is logged.
+/// Use the `DEFMT_LOG` environment variable to remove the logs at compile
+/// time.
+///
+/// # Example
+///
+/// ```no_run
+/// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{ll::Sn, LogSpi, Udp};
+///
+/// let mut w5500 = LogSpi::new(w5500);
+/// w5500.udp_bind(Sn::Sn0, 8080)?;
+/// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+/// ```
+///
+/// [`defmt`]: https://docs.rs/defmt
+#[derive(Debug)]
+pub struct LogSpi<W> {
+    w5500: W,
+}
+
+impl<W: Registers> LogSpi<W> {
+    /// Wraps a W5500 device.
+    pub const fn new(w5500: W) -> Self {
+        Self { w5500 }
+    }
+
+    /// Get a mutable reference to the wrapped W5500 device.
+    ///
+    /// Transfers made with the wrapped device are not logged.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w5500
+    }
+
+    /// Returns the wrapped W5500 device.
+    pub fn free(self) -> W {
+        self.w5500
+    }
+}
+
+fn log_transfer(op: char, addr: u16, block: u8, data: &[u8]) {
+    let shown: &[u8] = &data[..data.len().min(MAX_LOG_DATA)];
+    if shown.len() == data.len() {
+        defmt::trace!(
+            "{=char} {=u16:#X} {=u8:#X} len={=usize} {=[u8]:X}",
+            op,
+            addr,
+            block,
+            data.len(),
+            shown
+        );
+    } else {
+        defmt::trace!(
+            "{=char} {=u16:#X} {=u8:#X} len={=usize} {=[u8]:X} ..",
+            op,
+            addr,
+            block,
+            data.len(),
+            shown
+        );
+    }
+}
+
+impl<W: Registers> Registers for LogSpi<W> {
+    type Error = W::Error;
+
+    fn read(&mut self, addr: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.w5500.read(addr, block, data)?;
+        log_transfer('R', addr, block, data);
+        Ok(())
+    }
+
+    fn write(&mut self, addr: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.w5500.write(addr, block, data)?;
+        log_transfer('W', addr, block, data);
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+use crate::{sn_rx_rsr_stable, sn_tx_fsr_stable, wait_for_status, Error};
+use core::cmp::min;
+use w5500_ll::{
+    net::{Eui48Addr, Ipv4Addr},
+    Protocol, Registers, Sn, SocketCommand, SocketMode, SocketStatus,
+};
+
+/// The only socket that supports the MACRAW protocol.
+const MACRAW_SOCKET: Sn = Sn::Sn0;
+
+/// Length of the W5500 MACRAW header.
+///
+/// For a MACRAW socket the W5500 prepends a 2 byte big endian length to each
+/// received frame, the length includes the header itself.
+const HEADER_LEN: u16 = 2;
+
+/// A W5500 MACRAW socket trait.
+///
+/// MACRAW sockets send and receive raw Ethernet frames, this is useful for
+/// implementing layer 2 protocols, or for using the W5500 with a software
+/// TCP/IP stack.
+///
+/// Only [`Sn0`] supports the MACRAW protocol, all methods in this trait
+/// operate on [`Sn0`].
+///
+/// [`Sn0`]: w5500_ll::Sn::Sn0
+pub trait MacRaw: Registers {
+    /// Opens socket 0 with the MACRAW protocol.
+    ///
+    /// The protocol field of `mode` is ignored, the MACRAW specific options
+    /// of the [`SocketMode`] are applied:
+    ///
+    /// * [`enable_mfen`]: only receive frames addressed to the W5500, and
+    ///   broadcast / multicast frames.
+    ///   When disabled all frames are received (promiscuous mode).
+    /// * [`enable_bcastb`]: block broadcast frames.
+    /// * [`enable_mmb`]: block multicast frames.
+    /// * [`enable_mip6b`]: block IPv6 frames.
+    ///
+    /// This will close the socket, which will reset the RX and TX buffers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::SocketMode, MacRaw};
+    ///
+    /// w5500.macraw_open(SocketMode::DEFAULT.enable_mfen())?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`enable_mfen`]: SocketMode::enable_mfen
+    /// [`enable_bcastb`]: SocketMode::enable_bcastb
+    /// [`enable_mmb`]: SocketMode::enable_mmb
+    /// [`enable_mip6b`]: SocketMode::enable_mip6b
+    fn macraw_open(&mut self, mode: SocketMode) -> Result<(), Self::Error> {
+        self.set_sn_cr(MACRAW_SOCKET, SocketCommand::Close)?;
+        // This will not hang, the socket status will always change to closed
+        // after a close command.
+        // (unless you do somthing silly like holding the W5500 in reset)
+        wait_for_status(self, MACRAW_SOCKET, SocketStatus::Closed)?;
+        self.set_sn_mr(MACRAW_SOCKET, mode.set_protocol(Protocol::Macraw))?;
+        self.set_sn_cr(MACRAW_SOCKET, SocketCommand::Open)?;
+        // This will not hang, the socket status will always change to Macraw
+        // after a open command with SN_MR set to MACRAW.
+        // (unless you do somthing silly like holding the W5500 in reset)
+        wait_for_status(self, MACRAW_SOCKET, SocketStatus::Macraw)
+    }
+
+    /// Enables or disables promiscuous reception on the MACRAW socket.
+    ///
+    /// When promiscuous reception is enabled the MAC filter ([`enable_mfen`])
+    /// is disabled, and all frames on the network are received.
+    /// When disabled only frames addressed to the W5500, and broadcast /
+    /// multicast frames are received.
+    ///
+    /// The W5500 only applies the socket mode when the socket is opened, this
+    /// method reopens socket 0 with the updated mode, which will reset the RX
+    /// and TX buffers.
+    /// The other MACRAW options of the current socket mode are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::SocketMode, MacRaw};
+    ///
+    /// w5500.macraw_open(SocketMode::DEFAULT.enable_mfen())?;
+    /// // sniff all frames on the network
+    /// w5500.macraw_set_promiscuous(true)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`enable_mfen`]: SocketMode::enable_mfen
+    fn macraw_set_promiscuous(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        let mode: SocketMode = self.sn_mr(MACRAW_SOCKET)?;
+        let mode: SocketMode = if enabled {
+            mode.disable_mfen()
+        } else {
+            mode.enable_mfen()
+        };
+        self.macraw_open(mode)
+    }
+
+    /// Receives a single Ethernet frame.
+    /// On success, returns the number of bytes read.
+    ///
+    /// If a frame is too long to fit in the supplied buffer, excess bytes
+    /// will be discarded.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a MACRAW socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{block, ll::SocketMode, MacRaw};
+    ///
+    /// w5500.macraw_open(SocketMode::DEFAULT)?;
+    /// let mut frame: [u8; 1514] = [0; 1514];
+    /// let rx_bytes: u16 = block!(w5500.macraw_recv(&mut frame))?;
+    /// let frame: &[u8] = &frame[..rx_bytes.into()];
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn macraw_recv(&mut self, buf: &mut [u8]) -> Result<u16, Error<Self::Error>> {
+        let rsr: u16 = match sn_rx_rsr_stable(self, MACRAW_SOCKET)?.checked_sub(HEADER_LEN) {
+            Some(rsr) => rsr,
+            // nothing to recieve
+            None => return Err(Error::WouldBlock),
+        };
+
+        debug_assert_eq!(self.sn_sr(MACRAW_SOCKET)?, Ok(SocketStatus::Macraw));
+
+        let mut ptr: u16 = self.sn_rx_rd(MACRAW_SOCKET)?;
+        let mut header: [u8; 2] = [0; 2];
+        self.sn_rx_buf(MACRAW_SOCKET, ptr, &mut header)?;
+        ptr = ptr.wrapping_add(HEADER_LEN);
+        let frame_len: u16 = u16::from_be_bytes(header).saturating_sub(HEADER_LEN);
+
+        // not all data as indicated by the header has been buffered
+        if rsr < frame_len {
+            return Err(Error::WouldBlock);
+        }
+
+        let read_size: u16 = min(frame_len, buf.len().try_into().unwrap_or(u16::MAX));
+        if read_size != 0 {
+            self.sn_rx_buf(MACRAW_SOCKET, ptr, &mut buf[..read_size.into()])?;
+        }
+        ptr = ptr.wrapping_add(frame_len);
+        self.set_sn_rx_rd(MACRAW_SOCKET, ptr)?;
+        self.set_sn_cr(MACRAW_SOCKET, SocketCommand::Recv)?;
+        Ok(read_size)
+    }
+
+    /// Sends a single Ethernet frame.
+    ///
+    /// The frame must include the Ethernet header, the W5500 appends the
+    /// frame check sequence.
+    ///
+    /// Data is only written, and the send command only issued, when the entire
+    /// frame fits in the free space of the W5500 transmit buffer.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// [`Error::OutOfMemory`] is returned when the frame is larger than the
+    /// socket transmit buffer, and can never be sent.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a MACRAW socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{block, ll::SocketMode, MacRaw};
+    ///
+    /// w5500.macraw_open(SocketMode::DEFAULT)?;
+    /// let frame: [u8; 60] = [0; 60];
+    /// block!(w5500.macraw_send(&frame))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn macraw_send(&mut self, frame: &[u8]) -> Result<(), Error<Self::Error>> {
+        debug_assert_eq!(self.sn_sr(MACRAW_SOCKET)?, Ok(SocketStatus::Macraw));
+
+        let data_len: u16 = u16::try_from(frame.len()).map_err(|_| Error::OutOfMemory)?;
+        let free_size: u16 = sn_tx_fsr_stable(self, MACRAW_SOCKET)?;
+        if data_len > free_size {
+            if let Ok(size) = self.sn_txbuf_size(MACRAW_SOCKET)? {
+                if frame.len() > size.size_in_bytes() {
+                    return Err(Error::OutOfMemory);
+                }
+            }
+            return Err(Error::WouldBlock);
+        }
+
+        if data_len != 0 {
+            let ptr: u16 = self.sn_tx_wr(MACRAW_SOCKET)?;
+            self.set_sn_tx_buf(MACRAW_SOCKET, ptr, frame)?;
+            self.set_sn_tx_wr(MACRAW_SOCKET, ptr.wrapping_add(data_len))?;
+            self.set_sn_cr(MACRAW_SOCKET, SocketCommand::Send)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a gratuitous ARP announcement for the W5500 IP address.
+    ///
+    /// The W5500 does not provide a command to send ARP announcements, the
+    /// ARP request is constructed by this method and sent as a raw Ethernet
+    /// frame with [`macraw_send`].
+    /// The frame is a broadcast ARP request with the sender and target
+    /// protocol addresses both set to the source IP address register (SIPR),
+    /// and the sender hardware address set to the source hardware address
+    /// register (SHAR).
+    ///
+    /// This is useful for refreshing the MAC address tables of switches, and
+    /// for announcing a new IP address to other hosts on the network.
+    ///
+    /// Socket 0 must be opened as a MACRAW socket with [`macraw_open`], the
+    /// socket can be closed afterwards to be used for other protocols.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a MACRAW socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{block, ll::SocketMode, MacRaw};
+    ///
+    /// w5500.macraw_open(SocketMode::DEFAULT)?;
+    /// block!(w5500.gratuitous_arp())?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`macraw_open`]: MacRaw::macraw_open
+    /// [`macraw_send`]: MacRaw::macraw_send
+    fn gratuitous_arp(&mut self) -> Result<(), Error<Self::Error>> {
+        // 14 byte Ethernet header, 28 byte ARP packet, padded to the minimum
+        // Ethernet frame length without the frame check sequence
+        const FRAME_LEN: usize = 60;
+        const ETHERTYPE_ARP: [u8; 2] = [0x08, 0x06];
+        // hardware type Ethernet, protocol type IPv4, 6 byte hardware
+        // address, 4 byte protocol address, request operation
+        const ARP_REQUEST: [u8; 8] = [0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x01];
+
+        let mac: Eui48Addr = self.shar()?;
+        let ip: Ipv4Addr = self.sipr()?;
+
+        let mut frame: [u8; FRAME_LEN] = [0; FRAME_LEN];
+        frame[0..6].copy_from_slice(&[0xFF; 6]);
+        frame[6..12].copy_from_slice(&mac.octets);
+        frame[12..14].copy_from_slice(&ETHERTYPE_ARP);
+        frame[14..22].copy_from_slice(&ARP_REQUEST);
+        frame[22..28].copy_from_slice(&mac.octets);
+        frame[28..32].copy_from_slice(&ip.octets);
+        // target hardware address is ignored for requests, left as zero
+        frame[38..42].copy_from_slice(&ip.octets);
+
+        self.macraw_send(&frame)
+    }
+}
+
+/// Implement the MACRAW trait for any structure that implements [`w5500_ll::Registers`].
+impl<T> MacRaw for T where T: Registers {}
@@ -0,0 +1,491 @@
+use crate::{unique_port, Error, Tcp, Udp};
+use embedded_nal::{
+    nb, TcpClientStack, TcpError, TcpErrorKind, TcpFullStack, UdpClientStack, UdpFullStack,
+};
+use w5500_ll::{
+    net::{Ipv4Addr, SocketAddrV4},
+    Registers, Sn, SocketCommand, SocketStatus, SOCKETS,
+};
+
+/// First port of the IANA dynamic port range, used for the local port of
+/// sockets that are connected without being bound.
+const EPHEMERAL_PORT_START: u16 = 49152;
+
+/// Converts an [`embedded-nal`] address, returning [`Error::Unsupported`] for
+/// IPv6 addresses.
+///
+/// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+fn from_nal<E>(addr: embedded_nal::SocketAddr) -> Result<SocketAddrV4, Error<E>> {
+    match addr {
+        embedded_nal::SocketAddr::V4(addr) => Ok(SocketAddrV4::new(
+            Ipv4Addr::from(addr.ip().octets()),
+            addr.port(),
+        )),
+        embedded_nal::SocketAddr::V6(_) => Err(Error::Unsupported),
+    }
+}
+
+/// Returns [`Error::NotListening`] if the socket is not a bound UDP socket.
+fn udp_bound<W: Registers>(w5500: &mut W, sn: Sn) -> Result<(), Error<W::Error>> {
+    if w5500.sn_sr(sn)? == Ok(SocketStatus::Udp) {
+        Ok(())
+    } else {
+        Err(Error::NotListening)
+    }
+}
+
+fn to_nal(addr: SocketAddrV4) -> embedded_nal::SocketAddr {
+    embedded_nal::SocketAddr::V4(embedded_nal::SocketAddrV4::new(
+        embedded_nal::Ipv4Addr::from(addr.ip().octets),
+        addr.port(),
+    ))
+}
+
+fn to_nb<E>(e: Error<E>) -> nb::Error<Error<E>> {
+    match e {
+        Error::WouldBlock => nb::Error::WouldBlock,
+        e => nb::Error::Other(e),
+    }
+}
+
+/// W5500 [`embedded-nal`] network stack.
+///
+/// This wraps a W5500 device and allocates sockets from a set of W5500
+/// sockets as they are requested by the [`embedded-nal`] traits.
+///
+/// UDP and TCP sockets are allocated from the same set of W5500 sockets.
+///
+/// The W5500 does not support IPv6, all methods that accept an address
+/// return [`Error::Unsupported`] if given an IPv6 address.
+///
+/// # Example
+///
+/// ```no_run
+/// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use embedded_nal::UdpClientStack;
+/// use w5500_hl::{ll::Sn, NalDevice};
+///
+/// // Sn0 is reserved for DHCP
+/// let mut stack = NalDevice::with_sockets(w5500, &[Sn::Sn1, Sn::Sn2, Sn::Sn3]);
+/// let socket = stack.socket()?;
+/// # Ok::<(), w5500_hl::Error<_>>(())
+/// ```
+///
+/// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NalDevice<W> {
+    w5500: W,
+    /// Bitmap of sockets available for allocation.
+    available: u8,
+    /// Bitmap of sockets currently allocated.
+    allocated: u8,
+    /// Generation of each socket, incremented when a socket is released.
+    generation: [u8; 8],
+}
+
+impl<W> NalDevice<W> {
+    /// Create a new network stack that allocates from all W5500 sockets.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::NalDevice;
+    ///
+    /// let stack = NalDevice::new(w5500);
+    /// ```
+    pub fn new(w5500: W) -> Self {
+        Self::with_sockets(w5500, &SOCKETS)
+    }
+
+    /// Create a new network stack that only allocates from the given sockets.
+    ///
+    /// Sockets not in `sockets` are left free for use outside of the network
+    /// stack.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, NalDevice};
+    ///
+    /// let stack = NalDevice::with_sockets(w5500, &[Sn::Sn6, Sn::Sn7]);
+    /// ```
+    pub fn with_sockets(w5500: W, sockets: &[Sn]) -> Self {
+        Self {
+            w5500,
+            available: sockets.iter().fold(0, |acc, sn| acc | sn.bitmask()),
+            allocated: 0,
+            generation: [0; 8],
+        }
+    }
+
+    /// Returns a mutable reference to the W5500 device.
+    ///
+    /// This can be used to operate on sockets that are not allocated by the
+    /// network stack.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, NalDevice, Udp};
+    ///
+    /// let mut stack = NalDevice::with_sockets(w5500, &[Sn::Sn1, Sn::Sn2]);
+    /// stack.get_mut().udp_bind(Sn::Sn0, 68)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w5500
+    }
+
+    /// Free the W5500 device from the network stack.
+    ///
+    /// Sockets allocated by the network stack are not closed.
+    pub fn free(self) -> W {
+        self.w5500
+    }
+
+    fn allocate(&mut self) -> Option<Sn> {
+        let sn: Sn = SOCKETS
+            .iter()
+            .copied()
+            .find(|sn| (self.available & !self.allocated) & sn.bitmask() != 0)?;
+        self.allocated |= sn.bitmask();
+        Some(sn)
+    }
+
+    fn release(&mut self, sn: Sn) {
+        self.allocated &= !sn.bitmask();
+        let generation: &mut u8 = &mut self.generation[usize::from(sn)];
+        *generation = generation.wrapping_add(1);
+    }
+
+    fn tcp_socket(&self, sn: Sn, port: Option<u16>) -> NalTcpSocket {
+        NalTcpSocket {
+            sn,
+            generation: self.generation[usize::from(sn)],
+            port,
+            connecting: false,
+        }
+    }
+
+    /// Returns [`Error::StaleHandle`] if the socket was closed after the
+    /// handle was created.
+    fn check<E>(&self, socket: &NalTcpSocket) -> Result<(), Error<E>> {
+        if self.generation[usize::from(socket.sn)] == socket.generation {
+            Ok(())
+        } else {
+            Err(Error::StaleHandle)
+        }
+    }
+}
+
+/// [`embedded-nal`] UDP socket handle.
+///
+/// Created with [`UdpClientStack::socket`].
+///
+/// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NalUdpSocket {
+    sn: Sn,
+    remote: Option<SocketAddrV4>,
+}
+
+impl NalUdpSocket {
+    /// W5500 socket number used by this handle.
+    pub fn sn(&self) -> Sn {
+        self.sn
+    }
+}
+
+impl<W: Registers> UdpClientStack for NalDevice<W>
+where
+    W::Error: core::fmt::Debug,
+{
+    type UdpSocket = NalUdpSocket;
+    type Error = Error<W::Error>;
+
+    /// Allocate a socket.
+    ///
+    /// Returns [`Error::OutOfMemory`] if all sockets are allocated.
+    fn socket(&mut self) -> Result<Self::UdpSocket, Self::Error> {
+        let sn: Sn = self.allocate().ok_or(Error::OutOfMemory)?;
+        Ok(NalUdpSocket { sn, remote: None })
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        remote: embedded_nal::SocketAddr,
+    ) -> Result<(), Self::Error> {
+        let remote: SocketAddrV4 = from_nal(remote)?;
+        self.w5500
+            .udp_bind_or_next(socket.sn, EPHEMERAL_PORT_START)?;
+        self.w5500.set_sn_dest(socket.sn, &remote)?;
+        socket.remote = Some(remote);
+        Ok(())
+    }
+
+    /// Send a datagram to the remote host.
+    ///
+    /// Returns [`Error::NotConnected`] if the socket was not connected with
+    /// [`UdpClientStack::connect`].
+    fn send(&mut self, socket: &mut Self::UdpSocket, buffer: &[u8]) -> nb::Result<(), Self::Error> {
+        let remote: SocketAddrV4 = socket.remote.ok_or(nb::Error::Other(Error::NotConnected))?;
+        udp_bound(&mut self.w5500, socket.sn)?;
+        // the destination may have been changed by send_to
+        self.w5500
+            .set_sn_dest(socket.sn, &remote)
+            .map_err(Error::Other)?;
+        self.w5500.udp_send_all(socket.sn, buffer).map_err(to_nb)
+    }
+
+    /// Receive a datagram.
+    ///
+    /// Returns [`Error::NotListening`] if the socket was not connected with
+    /// [`UdpClientStack::connect`] or bound with [`UdpFullStack::bind`].
+    fn receive(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<(usize, embedded_nal::SocketAddr), Self::Error> {
+        udp_bound(&mut self.w5500, socket.sn)?;
+        let (len, origin) = self.w5500.udp_recv_from(socket.sn, buffer).map_err(to_nb)?;
+        Ok((len.into(), to_nal(origin)))
+    }
+
+    fn close(&mut self, socket: Self::UdpSocket) -> Result<(), Self::Error> {
+        self.w5500.set_sn_cr(socket.sn, SocketCommand::Close)?;
+        self.release(socket.sn);
+        Ok(())
+    }
+}
+
+impl<W: Registers> UdpFullStack for NalDevice<W>
+where
+    W::Error: core::fmt::Debug,
+{
+    fn bind(&mut self, socket: &mut Self::UdpSocket, local_port: u16) -> Result<(), Self::Error> {
+        self.w5500.udp_bind(socket.sn, local_port)?;
+        socket.remote = None;
+        Ok(())
+    }
+
+    /// Send a datagram to `remote`.
+    ///
+    /// Returns [`Error::NotListening`] if the socket was not connected with
+    /// [`UdpClientStack::connect`] or bound with [`UdpFullStack::bind`].
+    fn send_to(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        remote: embedded_nal::SocketAddr,
+        buffer: &[u8],
+    ) -> nb::Result<(), Self::Error> {
+        let remote: SocketAddrV4 = from_nal(remote)?;
+        udp_bound(&mut self.w5500, socket.sn)?;
+        self.w5500
+            .set_sn_dest(socket.sn, &remote)
+            .map_err(Error::Other)?;
+        self.w5500.udp_send_all(socket.sn, buffer).map_err(to_nb)
+    }
+}
+
+/// [`embedded-nal`] TCP socket handle.
+///
+/// Created with [`TcpClientStack::socket`] or [`TcpFullStack::accept`].
+///
+/// The handle records the generation of the W5500 socket it was created
+/// for, a handle used after the W5500 socket was closed returns
+/// [`Error::StaleHandle`] instead of operating on a newly allocated socket.
+///
+/// [`embedded-nal`]: https://crates.io/crates/embedded-nal
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NalTcpSocket {
+    sn: Sn,
+    generation: u8,
+    port: Option<u16>,
+    connecting: bool,
+}
+
+impl NalTcpSocket {
+    /// W5500 socket number used by this handle.
+    pub fn sn(&self) -> Sn {
+        self.sn
+    }
+}
+
+impl<E: core::fmt::Debug> TcpError for Error<E> {
+    fn kind(&self) -> TcpErrorKind {
+        match self {
+            Error::NotListening | Error::NotConnected => TcpErrorKind::PipeClosed,
+            _ => TcpErrorKind::Other,
+        }
+    }
+}
+
+impl<W: Registers> TcpClientStack for NalDevice<W>
+where
+    W::Error: core::fmt::Debug,
+{
+    type TcpSocket = NalTcpSocket;
+    type Error = Error<W::Error>;
+
+    /// Allocate a socket.
+    ///
+    /// Returns [`Error::OutOfMemory`] if all sockets are allocated.
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        let sn: Sn = self.allocate().ok_or(Error::OutOfMemory)?;
+        Ok(self.tcp_socket(sn, None))
+    }
+
+    /// Connect to a remote host.
+    ///
+    /// The first call starts the connection, following calls return
+    /// [`nb::Error::WouldBlock`] until the connection is established.
+    /// [`Error::ConnectionRefused`] is returned if the connection was refused,
+    /// and [`Error::Timeout`] if the connection timed out, see
+    /// [`Tcp::tcp_poll_connect`].
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: embedded_nal::SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.check(socket)?;
+        if !socket.connecting {
+            let remote: SocketAddrV4 = from_nal(remote)?;
+            let port: u16 = unique_port(&mut self.w5500, socket.sn, EPHEMERAL_PORT_START)
+                .map_err(Error::Other)?;
+            self.w5500
+                .tcp_connect(socket.sn, port, &remote)
+                .map_err(Error::Other)?;
+            socket.connecting = true;
+            return Err(nb::Error::WouldBlock);
+        }
+
+        match self.w5500.tcp_poll_connect(socket.sn) {
+            Err(Error::WouldBlock) => Err(nb::Error::WouldBlock),
+            result => {
+                socket.connecting = false;
+                result.map_err(nb::Error::Other)
+            }
+        }
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        self.check(socket)?;
+        Ok(matches!(
+            self.w5500.sn_sr(socket.sn)?,
+            Ok(SocketStatus::Established) | Ok(SocketStatus::CloseWait)
+        ))
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.check(socket)?;
+        match self.w5500.sn_sr(socket.sn).map_err(Error::Other)? {
+            Ok(SocketStatus::Established) | Ok(SocketStatus::CloseWait) => (),
+            _ => return Err(nb::Error::Other(Error::NotConnected)),
+        }
+        let tx_bytes: u16 = self
+            .w5500
+            .tcp_write(socket.sn, buffer)
+            .map_err(Error::Other)?;
+        if tx_bytes == 0 && !buffer.is_empty() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            Ok(tx_bytes.into())
+        }
+    }
+
+    /// Receive data from the remote host.
+    ///
+    /// Returns `Ok(0)` once the remote host has closed the connection and all
+    /// data has been received.
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.check(socket)?;
+        let closing: bool = match self.w5500.sn_sr(socket.sn).map_err(Error::Other)? {
+            Ok(SocketStatus::Established) => false,
+            Ok(SocketStatus::CloseWait) => true,
+            _ => return Err(nb::Error::Other(Error::NotConnected)),
+        };
+        let rx_bytes: u16 = self
+            .w5500
+            .tcp_read(socket.sn, buffer)
+            .map_err(Error::Other)?;
+        if rx_bytes == 0 && !buffer.is_empty() && !closing {
+            Err(nb::Error::WouldBlock)
+        } else {
+            Ok(rx_bytes.into())
+        }
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.check(&socket)?;
+        self.w5500.set_sn_cr(socket.sn, SocketCommand::Close)?;
+        self.release(socket.sn);
+        Ok(())
+    }
+}
+
+impl<W: Registers> TcpFullStack for NalDevice<W>
+where
+    W::Error: core::fmt::Debug,
+{
+    fn bind(&mut self, socket: &mut Self::TcpSocket, local_port: u16) -> Result<(), Self::Error> {
+        self.check(socket)?;
+        socket.port = Some(local_port);
+        Ok(())
+    }
+
+    /// Start listening for connections.
+    ///
+    /// Returns [`Error::NotListening`] if the socket was not bound to a local
+    /// port with [`TcpFullStack::bind`].
+    fn listen(&mut self, socket: &mut Self::TcpSocket) -> Result<(), Self::Error> {
+        self.check(socket)?;
+        let port: u16 = socket.port.ok_or(Error::NotListening)?;
+        self.w5500.tcp_listen(socket.sn, port)?;
+        Ok(())
+    }
+
+    /// Accept a connection.
+    ///
+    /// A W5500 socket can only hold a single connection, when a connection is
+    /// accepted the listener moves to a newly allocated W5500 socket, and the
+    /// returned handle takes over the W5500 socket holding the connection.
+    ///
+    /// Returns [`Error::OutOfMemory`] if there is no socket available for the
+    /// listener, the connection remains pending and can be accepted after
+    /// another socket is closed.
+    fn accept(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+    ) -> nb::Result<(Self::TcpSocket, embedded_nal::SocketAddr), Self::Error> {
+        self.check(socket)?;
+        let port: u16 = socket.port.ok_or(nb::Error::Other(Error::NotListening))?;
+        let peer: SocketAddrV4 = self
+            .w5500
+            .tcp_try_accept(socket.sn)?
+            .ok_or(nb::Error::WouldBlock)?;
+        let listener: Sn = self
+            .allocate()
+            .ok_or(nb::Error::Other(Error::OutOfMemory))?;
+        self.w5500
+            .tcp_listen(listener, port)
+            .map_err(Error::Other)?;
+        let connection: NalTcpSocket = self.tcp_socket(socket.sn, Some(port));
+        *socket = self.tcp_socket(listener, Some(port));
+        Ok((connection, to_nal(peer)))
+    }
+}
@@ -0,0 +1,31 @@
+use w5500_ll::net::Ipv4Addr;
+
+/// IPv4 network configuration.
+///
+/// This is read and written with [`Common::net_config`] and
+/// [`Common::set_net_config`].
+///
+/// # Example
+///
+/// ```
+/// use w5500_hl::{net::Ipv4Addr, NetConfig};
+///
+/// const STATIC: NetConfig = NetConfig {
+///     ip: Ipv4Addr::new(192, 168, 0, 50),
+///     subnet: Ipv4Addr::new(255, 255, 255, 0),
+///     gateway: Ipv4Addr::new(192, 168, 0, 1),
+/// };
+/// ```
+///
+/// [`Common::net_config`]: crate::Common::net_config
+/// [`Common::set_net_config`]: crate::Common::set_net_config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NetConfig {
+    /// Source IP address, SIPR.
+    pub ip: Ipv4Addr,
+    /// Subnet mask, SUBR.
+    pub subnet: Ipv4Addr,
+    /// Gateway IP address, GAR.
+    pub gateway: Ipv4Addr,
+}
@@ -0,0 +1,96 @@
+use w5500_ll::{DuplexStatus, LinkStatus, OperationMode, PhyCfg, Registers, SpeedStatus};
+
+/// A W5500 PHY trait.
+///
+/// This provides helpers for the PHY configuration register (PHYCFGR).
+pub trait Phy: Registers {
+    /// Returns `true` if the Ethernet link is up.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Phy;
+    ///
+    /// while !w5500.link_up()? {}
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn link_up(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.phycfgr()?.lnk() == LinkStatus::Up)
+    }
+
+    /// Returns the negotiated speed and duplex of the Ethernet link.
+    ///
+    /// The speed and duplex are only valid when the link is up, see
+    /// [`Phy::link_up`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{DuplexStatus, SpeedStatus};
+    /// use w5500_hl::Phy;
+    ///
+    /// if w5500.link_up()? {
+    ///     let (speed, duplex): (SpeedStatus, DuplexStatus) = w5500.speed_duplex()?;
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn speed_duplex(&mut self) -> Result<(SpeedStatus, DuplexStatus), Self::Error> {
+        let phy_cfg: PhyCfg = self.phycfgr()?;
+        Ok((phy_cfg.spd(), phy_cfg.dpx()))
+    }
+
+    /// Resets the PHY.
+    ///
+    /// This clears the PHY reset bit, then sets it again to bring the PHY out
+    /// of reset.
+    /// The other fields of the PHY configuration register are preserved.
+    ///
+    /// The link will be down until the PHY completes auto-negotiation.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::Phy;
+    ///
+    /// w5500.phy_reset()?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn phy_reset(&mut self) -> Result<(), Self::Error> {
+        let phy_cfg: PhyCfg = self.phycfgr()?;
+        self.set_phycfgr(phy_cfg.rst())?;
+        self.set_phycfgr(PhyCfg::from(u8::from(phy_cfg) | PhyCfg::RST_MASK))
+    }
+
+    /// Sets the PHY operation mode from software.
+    ///
+    /// This overrides the operation mode selected by the PMODE pins.
+    ///
+    /// The new operation mode is written with the PHY reset bit cleared, then
+    /// the PHY reset bit is set again to latch the new configuration.
+    ///
+    /// The link will be down until the PHY re-establishes the link with the
+    /// new operation mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::OperationMode;
+    /// use w5500_hl::Phy;
+    ///
+    /// w5500.set_phy_mode(OperationMode::FullDuplex100bt)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn set_phy_mode(&mut self, mode: OperationMode) -> Result<(), Self::Error> {
+        // set_opmdc also sets the OPMD bit to enable software configuration
+        let phy_cfg: PhyCfg = PhyCfg::DEFAULT.set_opmdc(mode);
+        self.set_phycfgr(phy_cfg.rst())?;
+        self.set_phycfgr(phy_cfg)
+    }
+}
+
+/// Implement the PHY trait for any structure that implements [`w5500_ll::Registers`].
+impl<T> Phy for T where T: Registers {}
@@ -0,0 +1,97 @@
+use w5500_ll::{
+    net::{Eui48Addr, Ipv4Addr, SocketAddrV4},
+    Sn, SocketInterrupt, SocketMode, SocketStatus,
+};
+
+/// Snapshot of the registers of a single socket.
+///
+/// This is returned by [`Common::socket_info`], and it is intended for
+/// debugging.
+///
+/// The [`defmt::Format`] implementation prints the snapshot on a single line,
+/// with the mode and interrupt registers as raw values.
+///
+/// [`Common::socket_info`]: crate::Common::socket_info
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SocketInfo {
+    /// Socket number.
+    pub sn: Sn,
+    /// Socket mode, SN_MR.
+    pub mode: SocketMode,
+    /// Socket interrupts, SN_IR.
+    pub interrupt: SocketInterrupt,
+    /// Socket status, SN_SR.
+    pub status: Result<SocketStatus, u8>,
+    /// Source port, SN_PORT.
+    pub port: u16,
+    /// Destination hardware address, SN_DHAR.
+    pub dhar: Eui48Addr,
+    /// Destination IP address and port, SN_DIPR and SN_DPORT.
+    pub dest: SocketAddrV4,
+    /// TX free size, SN_TX_FSR.
+    pub tx_fsr: u16,
+    /// TX read pointer, SN_TX_RD.
+    pub tx_rd: u16,
+    /// TX write pointer, SN_TX_WR.
+    pub tx_wr: u16,
+    /// RX received size, SN_RX_RSR.
+    pub rx_rsr: u16,
+    /// RX read pointer, SN_RX_RD.
+    pub rx_rd: u16,
+    /// RX write pointer, SN_RX_WR.
+    pub rx_wr: u16,
+}
+
+impl SocketInfo {
+    /// Length of the burst read from SN_MR to SN_DPORT.
+    pub(crate) const HEAD_LEN: usize = 0x12;
+    /// Length of the burst read from SN_TX_FSR to SN_RX_WR.
+    pub(crate) const PTRS_LEN: usize = 12;
+
+    pub(crate) fn deser(sn: Sn, head: &[u8; Self::HEAD_LEN], ptrs: &[u8; Self::PTRS_LEN]) -> Self {
+        let be = |idx: usize| u16::from_be_bytes([ptrs[idx], ptrs[idx + 1]]);
+        Self {
+            sn,
+            mode: SocketMode::from(head[0x00]),
+            interrupt: SocketInterrupt::from(head[0x02]),
+            status: SocketStatus::try_from(head[0x03]),
+            port: u16::from_be_bytes([head[0x04], head[0x05]]),
+            dhar: Eui48Addr::new(
+                head[0x06], head[0x07], head[0x08], head[0x09], head[0x0A], head[0x0B],
+            ),
+            dest: SocketAddrV4::new(
+                Ipv4Addr::new(head[0x0C], head[0x0D], head[0x0E], head[0x0F]),
+                u16::from_be_bytes([head[0x10], head[0x11]]),
+            ),
+            tx_fsr: be(0),
+            tx_rd: be(2),
+            tx_wr: be(4),
+            rx_rsr: be(6),
+            rx_rd: be(8),
+            rx_wr: be(10),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SocketInfo {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "{} {} mr={=u8:#X} ir={=u8:#X} port={} dest={} dhar={} tx(fsr={} rd={=u16:#X} wr={=u16:#X}) rx(rsr={} rd={=u16:#X} wr={=u16:#X})",
+            self.sn,
+            self.status,
+            u8::from(self.mode),
+            u8::from(self.interrupt),
+            self.port,
+            self.dest,
+            self.dhar,
+            self.tx_fsr,
+            self.tx_rd,
+            self.tx_wr,
+            self.rx_rsr,
+            self.rx_rd,
+            self.rx_wr,
+        );
+    }
+}
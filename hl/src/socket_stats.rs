@@ -0,0 +1,221 @@
+use w5500_ll::{
+    Registers, RxPtrs, Sn, SocketCommand, SocketInterrupt, SocketPtrs, TxPtrs, SOCKETS,
+};
+
+/// Socket traffic counters.
+///
+/// Returned by [`Stats::stats`].
+///
+/// All counters wrap around on overflow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SocketStats {
+    /// Bytes removed from the socket RX buffer.
+    ///
+    /// For UDP sockets this includes the 8 byte W5500 UDP header of each
+    /// datagram.
+    pub rx_bytes: u32,
+    /// Bytes sent from the socket TX buffer.
+    ///
+    /// Bytes written to the TX buffer are counted when the send command is
+    /// issued, data discarded before sending, such as with
+    /// [`Common::abort_tx`](crate::Common::abort_tx), is not counted.
+    pub tx_bytes: u32,
+    /// Number of [`SocketCommand::Send`] and [`SocketCommand::SendMac`]
+    /// commands.
+    pub sends: u32,
+    /// Number of send timeouts.
+    ///
+    /// This counts timeout interrupts that were read, and then cleared, such
+    /// as by [`Udp::udp_poll_send`](crate::Udp::udp_poll_send) and
+    /// [`Udp::udp_send_to_blocking`](crate::Udp::udp_send_to_blocking).
+    pub send_timeouts: u32,
+}
+
+/// W5500 wrapper that counts the traffic of each socket.
+///
+/// The W5500 does not have any traffic counters, this wrapper counts
+/// traffic by observing the socket buffer pointer and command registers.
+/// Every method of the [`Udp`](crate::Udp), [`Tcp`](crate::Tcp), and
+/// [`Common`](crate::Common) traits is counted when called on the wrapper.
+///
+/// The counters are only kept by the wrapper, there is no overhead when the
+/// wrapper is not used.
+///
+/// # Example
+///
+/// ```no_run
+/// # let w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{
+///     ll::Sn,
+///     net::{Ipv4Addr, SocketAddrV4},
+///     SocketStats, Stats, Udp,
+/// };
+///
+/// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+///
+/// let mut w5500 = Stats::new(w5500);
+/// w5500.udp_bind(Sn::Sn0, 8080)?;
+/// w5500.udp_send_to(Sn::Sn0, b"hello", &DEST)?;
+///
+/// let stats: SocketStats = w5500.stats(Sn::Sn0);
+/// assert_eq!(stats.tx_bytes, 5);
+/// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+/// ```
+#[derive(Debug)]
+pub struct Stats<W> {
+    w5500: W,
+    stats: [SocketStats; SOCKETS.len()],
+    /// Last known value of the RX read pointer of each socket.
+    rx_rd: [u16; SOCKETS.len()],
+    /// Last known value of the TX write pointer of each socket.
+    tx_wr: [u16; SOCKETS.len()],
+    /// Bytes written to the TX buffer of each socket since the last send
+    /// command.
+    tx_pending: [u16; SOCKETS.len()],
+    /// Bitmask of sockets with a timeout interrupt that has been read, but not
+    /// cleared.
+    timeout_raised: u8,
+}
+
+impl<W: Registers> Stats<W> {
+    /// Wraps a W5500 device, with all counters set to zero.
+    pub const fn new(w5500: W) -> Self {
+        Self {
+            w5500,
+            stats: [SocketStats {
+                rx_bytes: 0,
+                tx_bytes: 0,
+                sends: 0,
+                send_timeouts: 0,
+            }; SOCKETS.len()],
+            rx_rd: [0; SOCKETS.len()],
+            tx_wr: [0; SOCKETS.len()],
+            tx_pending: [0; SOCKETS.len()],
+            timeout_raised: 0,
+        }
+    }
+
+    /// Returns the counters for a socket.
+    pub fn stats(&self, sn: Sn) -> SocketStats {
+        self.stats[usize::from(sn)]
+    }
+
+    /// Sets the counters for a socket to zero.
+    pub fn reset_stats(&mut self, sn: Sn) {
+        self.stats[usize::from(sn)] = SocketStats::default()
+    }
+
+    /// Get a mutable reference to the wrapped W5500 device.
+    ///
+    /// Operations performed with the wrapped device are not counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w5500
+    }
+
+    /// Returns the wrapped W5500 device.
+    pub fn free(self) -> W {
+        self.w5500
+    }
+
+    fn socket_stats(&mut self, sn: Sn) -> &mut SocketStats {
+        &mut self.stats[usize::from(sn)]
+    }
+}
+
+impl<W: Registers> Registers for Stats<W> {
+    type Error = W::Error;
+
+    fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.w5500.read(address, block, data)
+    }
+
+    fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.w5500.write(address, block, data)
+    }
+
+    fn set_sn_cr(&mut self, sn: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+        self.w5500.set_sn_cr(sn, cmd)?;
+        match cmd {
+            SocketCommand::Send | SocketCommand::SendMac => {
+                let pending: u16 = core::mem::take(&mut self.tx_pending[usize::from(sn)]);
+                let stats: &mut SocketStats = self.socket_stats(sn);
+                stats.sends = stats.sends.wrapping_add(1);
+                stats.tx_bytes = stats.tx_bytes.wrapping_add(pending.into());
+            }
+            SocketCommand::Open | SocketCommand::Close => self.tx_pending[usize::from(sn)] = 0,
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn sn_ir(&mut self, sn: Sn) -> Result<SocketInterrupt, Self::Error> {
+        let sn_ir: SocketInterrupt = self.w5500.sn_ir(sn)?;
+        if sn_ir.timeout_raised() {
+            self.timeout_raised |= sn.bitmask();
+        }
+        Ok(sn_ir)
+    }
+
+    fn set_sn_ir<T: Into<u8>>(&mut self, sn: Sn, sn_ir: T) -> Result<(), Self::Error> {
+        let sn_ir: u8 = sn_ir.into();
+        self.w5500.set_sn_ir(sn, sn_ir)?;
+        if sn_ir & SocketInterrupt::TIMEOUT_MASK != 0 && self.timeout_raised & sn.bitmask() != 0 {
+            self.timeout_raised &= !sn.bitmask();
+            let stats: &mut SocketStats = self.socket_stats(sn);
+            stats.send_timeouts = stats.send_timeouts.wrapping_add(1);
+        }
+        Ok(())
+    }
+
+    fn sn_rx_rd(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        let ptr: u16 = self.w5500.sn_rx_rd(sn)?;
+        self.rx_rd[usize::from(sn)] = ptr;
+        Ok(ptr)
+    }
+
+    fn sn_rx_ptrs(&mut self, sn: Sn) -> Result<RxPtrs, Self::Error> {
+        let ptrs: RxPtrs = self.w5500.sn_rx_ptrs(sn)?;
+        self.rx_rd[usize::from(sn)] = ptrs.rd;
+        Ok(ptrs)
+    }
+
+    fn set_sn_rx_rd(&mut self, sn: Sn, ptr: u16) -> Result<(), Self::Error> {
+        self.w5500.set_sn_rx_rd(sn, ptr)?;
+        let len: u16 = ptr.wrapping_sub(self.rx_rd[usize::from(sn)]);
+        self.rx_rd[usize::from(sn)] = ptr;
+        let stats: &mut SocketStats = self.socket_stats(sn);
+        stats.rx_bytes = stats.rx_bytes.wrapping_add(len.into());
+        Ok(())
+    }
+
+    fn sn_tx_wr(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        let ptr: u16 = self.w5500.sn_tx_wr(sn)?;
+        self.tx_wr[usize::from(sn)] = ptr;
+        Ok(ptr)
+    }
+
+    fn sn_tx_ptrs(&mut self, sn: Sn) -> Result<TxPtrs, Self::Error> {
+        let ptrs: TxPtrs = self.w5500.sn_tx_ptrs(sn)?;
+        self.tx_wr[usize::from(sn)] = ptrs.wr;
+        Ok(ptrs)
+    }
+
+    fn sn_ptrs(&mut self, sn: Sn) -> Result<SocketPtrs, Self::Error> {
+        let ptrs: SocketPtrs = self.w5500.sn_ptrs(sn)?;
+        self.rx_rd[usize::from(sn)] = ptrs.rx.rd;
+        self.tx_wr[usize::from(sn)] = ptrs.tx.wr;
+        Ok(ptrs)
+    }
+
+    fn set_sn_tx_wr(&mut self, sn: Sn, ptr: u16) -> Result<(), Self::Error> {
+        self.w5500.set_sn_tx_wr(sn, ptr)?;
+        let idx: usize = usize::from(sn);
+        // moving the pointer backwards discards pending data, the wrapping
+        // arithmetic subtracts it again
+        let len: u16 = ptr.wrapping_sub(self.tx_wr[idx]);
+        self.tx_wr[idx] = ptr;
+        self.tx_pending[idx] = self.tx_pending[idx].wrapping_add(len);
+        Ok(())
+    }
+}
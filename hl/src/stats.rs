@@ -0,0 +1,69 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use w5500_ll::{Sn, SOCKETS};
+
+static CLOSE_SPINS: AtomicU32 = AtomicU32::new(0);
+static OPEN_SPINS: AtomicU32 = AtomicU32::new(0);
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU32 = AtomicU32::new(0);
+static RX_OVERFLOWS: [AtomicU32; SOCKETS.len()] = [ZERO; SOCKETS.len()];
+
+/// Busy-wait loop statistics.
+///
+/// Counts the number of times the socket status register was polled while
+/// waiting for a socket to change state.
+///
+/// High spin counts indicate a lot of time is spent polling, and that an
+/// interrupt driven design may be a better fit.
+///
+/// These statistics are global, they are shared between all W5500 instances.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpinStats {
+    /// Spin iterations waiting for a socket to close after a
+    /// [`SocketCommand::Close`](crate::ll::SocketCommand::Close).
+    pub close: u32,
+    /// Spin iterations waiting for a socket to open after a
+    /// [`SocketCommand::Open`](crate::ll::SocketCommand::Open).
+    pub open: u32,
+}
+
+// Load and store instead of fetch_add to support targets without atomic
+// read-modify-write instructions, such as the Cortex-M0.
+// Counts may be lost if the counters are updated from multiple contexts.
+fn increment(counter: &AtomicU32) {
+    let value: u32 = counter.load(Ordering::Relaxed);
+    counter.store(value.wrapping_add(1), Ordering::Relaxed);
+}
+
+pub(crate) fn close_spin() {
+    increment(&CLOSE_SPINS)
+}
+
+pub(crate) fn open_spin() {
+    increment(&OPEN_SPINS)
+}
+
+pub(crate) fn get() -> SpinStats {
+    SpinStats {
+        close: CLOSE_SPINS.load(Ordering::Relaxed),
+        open: OPEN_SPINS.load(Ordering::Relaxed),
+    }
+}
+
+pub(crate) fn reset() {
+    CLOSE_SPINS.store(0, Ordering::Relaxed);
+    OPEN_SPINS.store(0, Ordering::Relaxed);
+}
+
+pub(crate) fn rx_overflow(sn: Sn) {
+    increment(&RX_OVERFLOWS[usize::from(sn)])
+}
+
+pub(crate) fn rx_overflows(sn: Sn) -> u32 {
+    RX_OVERFLOWS[usize::from(sn)].load(Ordering::Relaxed)
+}
+
+pub(crate) fn reset_rx_overflows(sn: Sn) {
+    RX_OVERFLOWS[usize::from(sn)].store(0, Ordering::Relaxed);
+}
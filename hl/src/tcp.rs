@@ -1,10 +1,12 @@
 use crate::{
-    io::{Read, Seek, SeekFrom, Write},
-    port_is_unique, Error,
+    io::{furthest_ptr, zero_uninit, Read, Seek, SeekFrom, Write},
+    port_is_unique, sn_rx_rsr_stable, sn_tx_fsr_stable, wait_for_closed, wait_for_status, Error,
+    ALL_SN_IR,
 };
-use core::cmp::min;
+use core::{cmp::min, mem::MaybeUninit, time::Duration};
 use w5500_ll::{
-    net::SocketAddrV4, Protocol, Registers, Sn, SocketCommand, SocketMode, SocketStatus, TxPtrs,
+    net::SocketAddrV4, Protocol, Registers, Sn, SocketCommand, SocketInterrupt, SocketMode,
+    SocketStatus,
 };
 
 /// Streaming reader for a TCP socket buffer.
@@ -103,6 +105,16 @@ impl<'a, W5500: Registers> Read<W5500::Error> for TcpReader<'a, W5500> {
         }
     }
 
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<u16, W5500::Error> {
+        let read_size: u16 = min(self.remain(), buf.len().try_into().unwrap_or(u16::MAX));
+        if read_size != 0 {
+            let buf: &mut [u8] = zero_uninit(&mut buf[..usize::from(read_size)]);
+            self.w5500.sn_rx_buf(self.sn, self.ptr, buf)?;
+            self.ptr = self.ptr.wrapping_add(read_size);
+        }
+        Ok(read_size)
+    }
+
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error<W5500::Error>> {
         let buf_len: u16 = buf.len().try_into().unwrap_or(u16::MAX);
         let read_size: u16 = min(self.remain(), buf_len);
@@ -164,11 +176,13 @@ pub struct TcpWriter<'w, W5500> {
     pub(crate) head_ptr: u16,
     pub(crate) tail_ptr: u16,
     pub(crate) ptr: u16,
+    /// Furthest position written, the end of the data that will be sent.
+    pub(crate) end_ptr: u16,
 }
 
 impl<'w, W5500> Seek for TcpWriter<'w, W5500> {
     fn seek<E>(&mut self, pos: SeekFrom) -> Result<(), Error<E>> {
-        self.ptr = pos.new_ptr(self.ptr, self.head_ptr, self.tail_ptr)?;
+        self.ptr = pos.new_ptr(self.ptr, self.head_ptr, self.end_ptr)?;
         Ok(())
     }
 
@@ -196,6 +210,7 @@ impl<'w, W5500: Registers> Write<W5500::Error> for TcpWriter<'w, W5500> {
             self.w5500
                 .set_sn_tx_buf(self.sn, self.ptr, &buf[..usize::from(write_size)])?;
             self.ptr = self.ptr.wrapping_add(write_size);
+            self.end_ptr = furthest_ptr(self.head_ptr, self.end_ptr, self.ptr);
 
             Ok(write_size)
         } else {
@@ -211,17 +226,100 @@ impl<'w, W5500: Registers> Write<W5500::Error> for TcpWriter<'w, W5500> {
         } else {
             self.w5500.set_sn_tx_buf(self.sn, self.ptr, buf)?;
             self.ptr = self.ptr.wrapping_add(write_size);
+            self.end_ptr = furthest_ptr(self.head_ptr, self.end_ptr, self.ptr);
             Ok(())
         }
     }
 
     fn send(self) -> Result<(), W5500::Error> {
-        self.w5500.set_sn_tx_wr(self.sn, self.ptr)?;
+        self.w5500.set_sn_tx_wr(self.sn, self.end_ptr)?;
         self.w5500.set_sn_cr(self.sn, SocketCommand::Send)?;
         Ok(())
     }
 }
 
+impl<'w, W5500: Registers> TcpWriter<'w, W5500> {
+    /// Send all data previously written with [`write`] and [`write_all`],
+    /// and start a new segment at the current position.
+    ///
+    /// This is identical to [`send`], except the writer is not consumed.
+    ///
+    /// Pointer bookkeeping after sending:
+    ///
+    /// * The start of the writer and the current position move to the end of
+    ///   the sent data, the sent data can no longer be reached with [`seek`]
+    ///   or [`rewind`].
+    /// * The free space is not reclaimed, [`remain`] is not increased.
+    ///   Create a new writer to use the space freed by the W5500 as the data
+    ///   is transmitted.
+    ///
+    /// This does not wait for the transmission to complete.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{io::Write, ll::Sn::Sn0, Tcp, TcpWriter};
+    ///
+    /// let mut writer: TcpWriter<_> = w5500.tcp_writer(Sn0)?;
+    /// writer.write_all(&[0x01, 0x02])?;
+    /// writer.send_segment()?;
+    /// writer.write_all(&[0x03, 0x04])?;
+    /// writer.send()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`send`]: Write::send
+    /// [`write_all`]: Write::write_all
+    /// [`write`]: Write::write
+    /// [`seek`]: Seek::seek
+    /// [`rewind`]: Seek::rewind
+    /// [`remain`]: Seek::remain
+    pub fn send_segment(&mut self) -> Result<(), W5500::Error> {
+        let end: u16 = self.end_ptr;
+        self.w5500.set_sn_tx_wr(self.sn, end)?;
+        self.w5500.set_sn_cr(self.sn, SocketCommand::Send)?;
+        self.head_ptr = end;
+        self.end_ptr = end;
+        self.ptr = end;
+        Ok(())
+    }
+}
+
+/// Write formatted text into the socket buffer.
+///
+/// Formatting fails with [`core::fmt::Error`] when the text does not fit in
+/// the remaining space of the socket buffer, or if a bus error occurs;
+/// the underlying error is not available.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use core::fmt::Write as _;
+/// use w5500_hl::{io::Write, ll::Sn::Sn0, Tcp, TcpWriter};
+///
+/// let mut writer: TcpWriter<_> = w5500.tcp_writer(Sn0)?;
+/// write!(writer, "GET /{} HTTP/1.0\r\n\r\n", "index.html").unwrap();
+/// writer.send()?;
+/// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+/// ```
+impl<'w, W5500: Registers> core::fmt::Write for TcpWriter<'w, W5500> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write_all(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+/// TCP socket mode, retaining the no delayed ACK option of `mode`.
+fn tcp_mode(mode: SocketMode) -> SocketMode {
+    let tcp: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Tcp);
+    if mode.nd_enabled() {
+        tcp.enable_nd()
+    } else {
+        tcp
+    }
+}
+
 /// A W5500 TCP trait.
 pub trait Tcp: Registers {
     /// Starts the 3-way TCP handshake with the remote host.
@@ -300,21 +398,176 @@ pub trait Tcp: Registers {
         // This will not hang, the socket status will always change to closed
         // after a close command.
         // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Closed) {}
+        wait_for_status(self, sn, SocketStatus::Closed)?;
 
-        const MODE: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Tcp);
-        self.set_sn_mr(sn, MODE)?;
+        let mode: SocketMode = tcp_mode(self.sn_mr(sn)?);
+        self.set_sn_mr(sn, mode)?;
         self.set_sn_port(sn, port)?;
         self.set_sn_cr(sn, SocketCommand::Open)?;
         self.set_sn_dest(sn, addr)?;
         // This will not hang, the socket status will always change to Init
         // after a open command with SN_MR set to TCP.
         // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Init) {}
+        wait_for_status(self, sn, SocketStatus::Init)?;
 
         self.set_sn_cr(sn, SocketCommand::Connect)
     }
 
+    /// Polls the socket status for completion of the TCP handshake started
+    /// with [`tcp_connect`].
+    ///
+    /// This is an alternative to waiting for the [`con`] interrupt.
+    ///
+    /// Returns `Ok(())` once the socket status is [`Established`], or
+    /// [`CloseWait`] if the remote host already sent a FIN after connecting.
+    ///
+    /// The W5500 closes the socket when the handshake fails, once the
+    /// socket is [`Closed`] the [`timeout`] interrupt distinguishes a
+    /// connection that timed out from a connection that was refused.
+    /// The interrupt is cleared before returning the error.
+    /// The timeout is determined by the [retry time] and [retry count]
+    /// registers.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`] while the handshake is in progress.
+    /// * [`Error::Timeout`] if the remote host did not respond.
+    /// * [`Error::ConnectionRefused`] if the remote host responded with a
+    ///   reset (RST), or if the socket is closed without a timeout, for
+    ///   example when polling before calling [`tcp_connect`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::Sn,
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Tcp,
+    /// };
+    ///
+    /// const MQTT_SOCKET: Sn = Sn::Sn0;
+    /// const MQTT_SOURCE_PORT: u16 = 33650;
+    /// const MQTT_SERVER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 2, 10), 1883);
+    ///
+    /// w5500.tcp_connect(MQTT_SOCKET, MQTT_SOURCE_PORT, &MQTT_SERVER)?;
+    /// block!(w5500.tcp_poll_connect(MQTT_SOCKET))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`tcp_connect`]: Tcp::tcp_connect
+    /// [`con`]: w5500_ll::SocketInterrupt::con_raised
+    /// [`Established`]: w5500_ll::SocketStatus::Established
+    /// [`CloseWait`]: w5500_ll::SocketStatus::CloseWait
+    /// [`Closed`]: w5500_ll::SocketStatus::Closed
+    /// [`timeout`]: w5500_ll::SocketInterrupt::timeout_raised
+    /// [retry time]: w5500_ll::Registers::set_rtr
+    /// [retry count]: w5500_ll::Registers::set_rcr
+    fn tcp_poll_connect(&mut self, sn: Sn) -> Result<(), Error<Self::Error>> {
+        match self.sn_sr(sn)? {
+            Ok(SocketStatus::Established) | Ok(SocketStatus::CloseWait) => Ok(()),
+            Ok(SocketStatus::Closed) => {
+                if self.sn_ir(sn)?.timeout_raised() {
+                    self.set_sn_ir(sn, SocketInterrupt::TIMEOUT_MASK)?;
+                    Err(Error::Timeout)
+                } else {
+                    self.set_sn_ir(sn, SocketInterrupt::DISCON_MASK)?;
+                    Err(Error::ConnectionRefused)
+                }
+            }
+            _ => Err(Error::WouldBlock),
+        }
+    }
+
+    /// Checks if a remote TCP port is reachable with a connection probe.
+    ///
+    /// This starts a connection with [`tcp_connect`], polls
+    /// [`tcp_poll_connect`] until the handshake completes, calling
+    /// `delay_ms(1)` between polls, then closes the socket and waits for the
+    /// socket to be [`Closed`].
+    /// The socket interrupts are cleared before connecting and before
+    /// returning.
+    ///
+    /// * `Ok(true)` indicates the connection was established.
+    /// * `Ok(false)` indicates the connection was refused by the remote host
+    ///   (RST), or the connection timed out.
+    ///
+    /// The timeout is determined by the [retry time] and [retry count]
+    /// registers, reduce these to shorten the probe.
+    ///
+    /// # Arguments
+    ///
+    /// * `sn` - The socket number to use for the probe.
+    /// * `port` - The local port to use for the probe.
+    /// * `addr` - The IP address and port of the remote host to probe.
+    /// * `delay_ms` - A function that delays for the given number of
+    ///   milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::Timeout`] if the socket did not close after the probe.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn delay_ms(_ms: u32) {}
+    /// use w5500_hl::{
+    ///     ll::Sn,
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Tcp,
+    /// };
+    ///
+    /// const PROBE_SOCKET: Sn = Sn::Sn0;
+    /// const PROBE_SOURCE_PORT: u16 = 49152;
+    /// const SERVICE: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 2, 10), 80);
+    ///
+    /// let reachable: bool = w5500.tcp_probe(PROBE_SOCKET, PROBE_SOURCE_PORT, &SERVICE, delay_ms)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`tcp_connect`]: Tcp::tcp_connect
+    /// [`tcp_poll_connect`]: Tcp::tcp_poll_connect
+    /// [`Closed`]: w5500_ll::SocketStatus::Closed
+    /// [retry time]: w5500_ll::Registers::set_rtr
+    /// [retry count]: w5500_ll::Registers::set_rcr
+    fn tcp_probe<D>(
+        &mut self,
+        sn: Sn,
+        port: u16,
+        addr: &SocketAddrV4,
+        mut delay_ms: D,
+    ) -> Result<bool, Error<Self::Error>>
+    where
+        D: FnMut(u32),
+    {
+        // a stale timeout interrupt would be mistaken for the probe result
+        self.set_sn_ir(sn, ALL_SN_IR)?;
+        self.tcp_connect(sn, port, addr)?;
+
+        let reachable: Result<bool, Error<Self::Error>> = loop {
+            match self.tcp_poll_connect(sn) {
+                Ok(()) => break Ok(true),
+                Err(Error::WouldBlock) => delay_ms(1),
+                Err(Error::ConnectionRefused) | Err(Error::Timeout) => break Ok(false),
+                Err(e) => break Err(e),
+            }
+        };
+
+        self.set_sn_cr(sn, SocketCommand::Close)?;
+        let closed: Result<(), Error<Self::Error>> = wait_for_closed(self, sn);
+        self.set_sn_ir(sn, ALL_SN_IR)?;
+        closed?;
+        reachable
+    }
+
     /// Open a TCP listener on the given port.
     ///
     /// After opening a listener with [`tcp_listen`] and receiving the
@@ -326,9 +579,8 @@ pub trait Tcp: Registers {
     /// * `socket` - The socket number to use for this TCP listener.
     /// * `port` - The local port to listen for remote connections on.
     ///
-    /// # Panics
-    ///
-    /// * (debug) The port must not be in use by any other socket on the W5500.
+    /// Multiple sockets may listen on the same port to serve multiple clients
+    /// at once, see [`tcp_accept`].
     ///
     /// # Example
     ///
@@ -377,28 +629,126 @@ pub trait Tcp: Registers {
     /// [`tcp_read`]: Tcp::tcp_read
     /// [`tcp_listen`]: Tcp::tcp_listen
     /// [`con`]: w5500_ll::SocketInterrupt::con_raised
+    /// [`tcp_accept`]: Tcp::tcp_accept
     fn tcp_listen(&mut self, sn: Sn, port: u16) -> Result<(), Self::Error> {
-        debug_assert!(
-            port_is_unique(self, sn, port)?,
-            "Local port {port} is in use"
-        );
-
         self.set_sn_cr(sn, SocketCommand::Close)?;
         // This will not hang, the socket status will always change to closed
         // after a close command.
         // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Closed) {}
-        const MODE: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Tcp);
-        self.set_sn_mr(sn, MODE)?;
+        wait_for_status(self, sn, SocketStatus::Closed)?;
+        let mode: SocketMode = tcp_mode(self.sn_mr(sn)?);
+        self.set_sn_mr(sn, mode)?;
         self.set_sn_port(sn, port)?;
         self.set_sn_cr(sn, SocketCommand::Open)?;
         // This will not hang, the socket status will always change to Init
         // after a open command with SN_MR set to TCP.
         // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Init) {}
+        wait_for_status(self, sn, SocketStatus::Init)?;
         self.set_sn_cr(sn, SocketCommand::Listen)
     }
 
+    /// Returns the address of the remote host once a TCP listener has
+    /// accepted a connection.
+    ///
+    /// * `Ok(None)` indicates the socket is not connected.
+    /// * `Ok(Some(peer))` indicates a connection has been established with
+    ///   `peer`.
+    ///
+    /// Unlike [`tcp_try_accept`] this does not distinguish between a socket
+    /// that is listening and one that has closed, use [`tcp_is_closed`] to
+    /// detect when the socket needs to listen again.
+    ///
+    /// After the connection is closed the socket will not accept another
+    /// connection until [`tcp_listen`] is called again.
+    ///
+    /// # Example
+    ///
+    /// Serve multiple HTTP clients at once by listening with multiple
+    /// sockets on the same port, re-listening after each connection closes.
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// const HTTP_SOCKETS: [Sn; 2] = [Sn::Sn1, Sn::Sn2];
+    /// const HTTP_PORT: u16 = 80;
+    ///
+    /// for sn in HTTP_SOCKETS {
+    ///     w5500.tcp_listen(sn, HTTP_PORT)?;
+    /// }
+    ///
+    /// loop {
+    ///     for sn in HTTP_SOCKETS {
+    ///         if w5500.tcp_is_closed(sn)? {
+    ///             w5500.tcp_listen(sn, HTTP_PORT)?;
+    ///         } else if let Some(peer) = w5500.tcp_accept(sn)? {
+    ///             // ... serve the client, then disconnect
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`tcp_is_closed`]: Tcp::tcp_is_closed
+    /// [`tcp_listen`]: Tcp::tcp_listen
+    /// [`tcp_try_accept`]: Tcp::tcp_try_accept
+    fn tcp_accept(&mut self, sn: Sn) -> Result<Option<SocketAddrV4>, Self::Error> {
+        match self.tcp_try_accept(sn) {
+            Err(Error::Other(e)) => Err(e),
+            Err(_) => Ok(None),
+            Ok(peer) => Ok(peer),
+        }
+    }
+
+    /// Polls a TCP listener for an incoming connection.
+    ///
+    /// * `Ok(None)` indicates the socket is still waiting for a connection.
+    /// * `Ok(Some(peer))` indicates a connection has been established with
+    ///   `peer`.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::NotListening`]
+    ///
+    /// [`Error::NotListening`] is returned when the socket is neither
+    /// listening nor connected, for example when the socket was never opened
+    /// with [`tcp_listen`], or when an accepted connection was closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, net::SocketAddrV4, Tcp};
+    ///
+    /// const HTTP_SOCKET: Sn = Sn::Sn1;
+    /// const HTTP_PORT: u16 = 80;
+    ///
+    /// w5500.tcp_listen(HTTP_SOCKET, HTTP_PORT)?;
+    ///
+    /// loop {
+    ///     if let Some(peer) = w5500.tcp_try_accept(HTTP_SOCKET)? {
+    ///         // ... serve the client
+    ///         break;
+    ///     }
+    ///     // ... do other work
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`tcp_listen`]: Tcp::tcp_listen
+    fn tcp_try_accept(&mut self, sn: Sn) -> Result<Option<SocketAddrV4>, Error<Self::Error>> {
+        match self.sn_sr(sn)? {
+            Ok(SocketStatus::Listen) | Ok(SocketStatus::SynRecv) => Ok(None),
+            Ok(SocketStatus::Established) | Ok(SocketStatus::CloseWait) => {
+                Ok(Some(self.sn_dest(sn)?))
+            }
+            _ => Err(Error::NotListening),
+        }
+    }
+
     /// Read data from the remote host, returning the number of bytes read.
     ///
     /// You should wait for the socket [`recv`] interrupt before calling this method.
@@ -450,7 +800,7 @@ pub trait Tcp: Registers {
         ));
 
         let rx_bytes: u16 = {
-            let rsr: u16 = self.sn_rx_rsr(sn)?;
+            let rsr: u16 = sn_rx_rsr_stable(self, sn)?;
             min(rsr, u16::try_from(buf.len()).unwrap_or(u16::MAX))
         };
         if rx_bytes != 0 {
@@ -462,6 +812,96 @@ pub trait Tcp: Registers {
         Ok(rx_bytes)
     }
 
+    /// Read a single length prefixed record, such as a TLS record.
+    ///
+    /// This reads a 5 byte header into `header`, followed by a body with the
+    /// big endian length from bytes 3 and 4 of the header into `body`.
+    /// On success, returns the length of the body.
+    ///
+    /// Data is only removed from the socket buffer when the entire record has
+    /// been received, this can be called again when more data arrives until
+    /// the record is complete.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// [`Error::OutOfMemory`] is returned when the record body is larger than
+    /// `body`, or when the record is larger than the socket RX buffer and can
+    /// never be received.
+    /// The record is not removed from the socket buffer.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be an [`Established`] TCP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{block, ll::Sn, Tcp};
+    ///
+    /// let mut header: [u8; 5] = [0; 5];
+    /// let mut body: [u8; 1024] = [0; 1024];
+    /// let len: u16 = block!(w5500.tcp_read_record(Sn::Sn0, &mut header, &mut body))?;
+    /// let body: &[u8] = &body[..len.into()];
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`Established`]: w5500_ll::SocketStatus::Established
+    fn tcp_read_record(
+        &mut self,
+        sn: Sn,
+        header: &mut [u8; 5],
+        body: &mut [u8],
+    ) -> Result<u16, Error<Self::Error>> {
+        const HEADER_LEN: u16 = 5;
+
+        debug_assert!(!matches!(
+            self.sn_sr(sn)?,
+            Ok(SocketStatus::Udp) | Ok(SocketStatus::Init) | Ok(SocketStatus::Macraw)
+        ));
+
+        let rsr: u16 = sn_rx_rsr_stable(self, sn)?;
+        if rsr < HEADER_LEN {
+            return Err(Error::WouldBlock);
+        }
+
+        let ptr: u16 = self.sn_rx_rd(sn)?;
+        self.sn_rx_buf(sn, ptr, header)?;
+        let body_len: u16 = u16::from_be_bytes([header[3], header[4]]);
+        if usize::from(body_len) > body.len() {
+            return Err(Error::OutOfMemory);
+        }
+
+        if rsr - HEADER_LEN < body_len {
+            if let Ok(size) = self.sn_rxbuf_size(sn)? {
+                if usize::from(HEADER_LEN) + usize::from(body_len) > size.size_in_bytes() {
+                    return Err(Error::OutOfMemory);
+                }
+            }
+            return Err(Error::WouldBlock);
+        }
+
+        if body_len != 0 {
+            self.sn_rx_buf(
+                sn,
+                ptr.wrapping_add(HEADER_LEN),
+                &mut body[..usize::from(body_len)],
+            )?;
+        }
+        self.set_sn_rx_rd(sn, ptr.wrapping_add(HEADER_LEN).wrapping_add(body_len))?;
+        self.set_sn_cr(sn, SocketCommand::Recv)?;
+        Ok(body_len)
+    }
+
     /// Send data to the remote host, returning the number of bytes written.
     ///
     /// # Panics
@@ -505,7 +945,7 @@ pub trait Tcp: Registers {
 
         let tx_bytes: u16 = {
             let data_len: u16 = u16::try_from(buf.len()).unwrap_or(u16::MAX);
-            let free_size: u16 = self.sn_tx_fsr(sn)?;
+            let free_size: u16 = sn_tx_fsr_stable(self, sn)?;
             min(data_len, free_size)
         };
         if tx_bytes != 0 {
@@ -526,7 +966,8 @@ pub trait Tcp: Registers {
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be an [`Established`] TCP socket.
+    /// * (debug) The socket must be a connected TCP socket, such as an
+    ///   [`Established`] or [`CloseWait`] socket.
     ///
     /// # Example
     ///
@@ -553,16 +994,252 @@ pub trait Tcp: Registers {
     /// ```
     ///
     /// [`Closed`]: w5500_ll::SocketStatus::Closed
+    /// [`CloseWait`]: w5500_ll::SocketStatus::CloseWait
     /// [`Established`]: w5500_ll::SocketStatus::Established
     /// [timeout interrupt]: w5500_ll::SocketInterrupt::timeout_raised
     fn tcp_disconnect(&mut self, sn: Sn) -> Result<(), Self::Error> {
         debug_assert!(!matches!(
             self.sn_sr(sn)?,
-            Ok(SocketStatus::Udp) | Ok(SocketStatus::Init) | Ok(SocketStatus::Macraw)
+            Ok(SocketStatus::Udp)
+                | Ok(SocketStatus::Init)
+                | Ok(SocketStatus::Macraw)
+                | Ok(SocketStatus::Listen)
         ));
         self.set_sn_cr(sn, SocketCommand::Disconnect)
     }
 
+    /// Returns `true` if the socket status is [`Closed`].
+    ///
+    /// This can be polled after [`tcp_disconnect`] to wait for the disconnect
+    /// to complete.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// const HTTP_SOCKET: Sn = Sn::Sn1;
+    ///
+    /// // ... send the HTTP response with "Connection: close"
+    ///
+    /// w5500.tcp_disconnect(HTTP_SOCKET)?;
+    /// while !w5500.tcp_is_closed(HTTP_SOCKET)? {}
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`Closed`]: w5500_ll::SocketStatus::Closed
+    /// [`tcp_disconnect`]: Tcp::tcp_disconnect
+    fn tcp_is_closed(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        Ok(self.sn_sr(sn)? == Ok(SocketStatus::Closed))
+    }
+
+    /// Sets the interval for automatic TCP keep alive packets.
+    ///
+    /// The interval is rounded to the nearest multiple of 5 seconds, and
+    /// clamped to the range supported by the W5500 of 5 seconds to 21 minutes
+    /// and 15 seconds.
+    /// `None` or a zero duration disables automatic keep alive packets.
+    ///
+    /// The keep alive timer only runs when the socket is [`Established`], and
+    /// after data has been transmitted or received at least once.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use core::time::Duration;
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// w5500.set_tcp_keepalive(Sn::Sn0, Some(Duration::from_secs(60)))?;
+    /// assert_eq!(
+    ///     w5500.tcp_keepalive(Sn::Sn0)?,
+    ///     Some(Duration::from_secs(60))
+    /// );
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`Established`]: w5500_ll::SocketStatus::Established
+    fn set_tcp_keepalive(&mut self, sn: Sn, interval: Option<Duration>) -> Result<(), Self::Error> {
+        const TICK_MILLIS: u128 = 5000;
+        let kpalvtr: u8 = match interval {
+            Some(interval) if !interval.is_zero() => {
+                let ticks: u128 = (interval.as_millis() + TICK_MILLIS / 2) / TICK_MILLIS;
+                u8::try_from(ticks.max(1)).unwrap_or(u8::MAX)
+            }
+            _ => 0,
+        };
+        self.set_sn_kpalvtr(sn, kpalvtr)
+    }
+
+    /// Returns the interval for automatic TCP keep alive packets.
+    ///
+    /// `None` indicates automatic keep alive packets are disabled.
+    ///
+    /// See [`set_tcp_keepalive`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// w5500.set_tcp_keepalive(Sn::Sn0, None)?;
+    /// assert_eq!(w5500.tcp_keepalive(Sn::Sn0)?, None);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`set_tcp_keepalive`]: Tcp::set_tcp_keepalive
+    fn tcp_keepalive(&mut self, sn: Sn) -> Result<Option<Duration>, Self::Error> {
+        match self.sn_kpalvtr(sn)? {
+            0 => Ok(None),
+            ticks => Ok(Some(Duration::from_secs(u64::from(ticks) * 5))),
+        }
+    }
+
+    /// Enables or disables the TCP no delayed ACK option.
+    ///
+    /// When enabled the W5500 sends an ACK as soon as data is received,
+    /// instead of waiting for the [retry time].
+    /// This reduces the latency of request/response protocols at the cost of
+    /// more ACK packets.
+    ///
+    /// This is a read-modify-write of the [`Registers::sn_mr`] register, the
+    /// other mode bits are preserved.
+    /// The W5500 applies the socket mode when the socket is opened, the
+    /// setting is retained by [`tcp_connect`] and [`tcp_listen`], and will
+    /// take effect on the next connection.
+    ///
+    /// **Note:** The no delayed ACK bit is shared with the UDP multicast IGMP
+    /// version and the MACRAW multicast blocking bits.
+    /// Binding the socket in UDP or MACRAW mode overwrites this setting.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::Sn,
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Tcp,
+    /// };
+    ///
+    /// const MQTT_SOCKET: Sn = Sn::Sn0;
+    /// const MQTT_SOURCE_PORT: u16 = 33650;
+    /// const MQTT_SERVER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 2, 10), 1883);
+    ///
+    /// w5500.set_tcp_no_delay(MQTT_SOCKET, true)?;
+    /// w5500.tcp_connect(MQTT_SOCKET, MQTT_SOURCE_PORT, &MQTT_SERVER)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [retry time]: crate::Common::set_retry_time
+    /// [`tcp_connect`]: Tcp::tcp_connect
+    /// [`tcp_listen`]: Tcp::tcp_listen
+    fn set_tcp_no_delay(&mut self, sn: Sn, no_delay: bool) -> Result<(), Self::Error> {
+        let mode: SocketMode = self.sn_mr(sn)?;
+        let mode: SocketMode = if no_delay {
+            mode.enable_nd()
+        } else {
+            mode.disable_nd()
+        };
+        self.set_sn_mr(sn, mode)
+    }
+
+    /// Returns `true` if the TCP no delayed ACK option is enabled.
+    ///
+    /// See [`set_tcp_no_delay`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// let no_delay: bool = w5500.tcp_no_delay(Sn::Sn0)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`set_tcp_no_delay`]: Tcp::set_tcp_no_delay
+    fn tcp_no_delay(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        Ok(self.sn_mr(sn)?.nd_enabled())
+    }
+
+    /// Sends a TCP keep alive packet to check if the remote host is alive.
+    ///
+    /// Use [`tcp_poll_alive`] to check the result.
+    ///
+    /// The W5500 does not signal when the remote host responds, only when it
+    /// does not.
+    /// If the remote host does not respond within the [retry time] and
+    /// [retry count] the connection is closed and the timeout interrupt is
+    /// raised.
+    ///
+    /// This is independent of the automatic keep alive packets configured
+    /// with [`set_tcp_keepalive`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be an [`Established`] TCP socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn, Tcp};
+    ///
+    /// w5500.tcp_probe_alive(Sn::Sn0)?;
+    ///
+    /// // ... wait for the retry time multiplied by the retry count + 1
+    ///
+    /// if !w5500.tcp_poll_alive(Sn::Sn0)? {
+    ///     // remote host is gone
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`Established`]: w5500_ll::SocketStatus::Established
+    /// [`set_tcp_keepalive`]: Tcp::set_tcp_keepalive
+    /// [`tcp_poll_alive`]: Tcp::tcp_poll_alive
+    /// [retry count]: crate::Common::set_retry_count
+    /// [retry time]: crate::Common::set_retry_time
+    fn tcp_probe_alive(&mut self, sn: Sn) -> Result<(), Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Established));
+        self.set_sn_ir(sn, SocketInterrupt::TIMEOUT_MASK)?;
+        self.set_sn_cr(sn, SocketCommand::SendKeep)
+    }
+
+    /// Returns `false` if the connection was lost after a
+    /// [`tcp_probe_alive`].
+    ///
+    /// This returns `true` while the connection is open and no timeout has
+    /// occurred; the remote host should be considered alive when this
+    /// returns `true` after the retransmission time has elapsed.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// See [`tcp_probe_alive`].
+    ///
+    /// [`tcp_probe_alive`]: Tcp::tcp_probe_alive
+    fn tcp_poll_alive(&mut self, sn: Sn) -> Result<bool, Self::Error> {
+        if self.sn_ir(sn)?.timeout_raised() {
+            return Ok(false);
+        }
+        Ok(matches!(
+            self.sn_sr(sn)?,
+            Ok(SocketStatus::Established) | Ok(SocketStatus::CloseWait)
+        ))
+    }
+
     /// Create a TCP reader.
     ///
     /// This returns a [`TcpReader`] structure, which contains functions to
@@ -589,7 +1266,7 @@ pub trait Tcp: Registers {
             Ok(SocketStatus::Udp) | Ok(SocketStatus::Init) | Ok(SocketStatus::Macraw)
         ));
 
-        let sn_rx_rsr: u16 = self.sn_rx_rsr(sn)?;
+        let sn_rx_rsr: u16 = sn_rx_rsr_stable(self, sn)?;
         if sn_rx_rsr == 0 {
             return Err(Error::WouldBlock);
         }
@@ -617,14 +1294,16 @@ pub trait Tcp: Registers {
     where
         Self: Sized,
     {
-        let tx_ptrs: TxPtrs = self.sn_tx_ptrs(sn)?;
+        let fsr: u16 = sn_tx_fsr_stable(self, sn)?;
+        let wr: u16 = self.sn_tx_wr(sn)?;
 
         Ok(TcpWriter {
             w5500: self,
             sn,
-            head_ptr: tx_ptrs.wr,
-            tail_ptr: tx_ptrs.wr.wrapping_add(tx_ptrs.fsr),
-            ptr: tx_ptrs.wr,
+            head_ptr: wr,
+            tail_ptr: wr.wrapping_add(fsr),
+            ptr: wr,
+            end_ptr: wr,
         })
     }
 }
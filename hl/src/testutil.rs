@@ -0,0 +1,525 @@
+//! Test utilities.
+//!
+//! This module contains [`Loopback`], an in-memory model of the W5500 for
+//! testing protocol code on the host without hardware, or hand-scripted
+//! SPI transactions.
+//!
+//! Unlike [`w5500-regsim`], which simulates the W5500 with the network
+//! sockets of the host, the model never leaves memory, which keeps tests
+//! deterministic.
+//!
+//! [`w5500-regsim`]: https://crates.io/crates/w5500-regsim
+
+use std::{convert::Infallible, vec::Vec};
+use w5500_ll::{
+    Protocol, Reg, Registers, Sn, SnReg, SocketCommand, SocketInterrupt, SocketMode, SocketStatus,
+    COMMON_BLOCK_OFFSET, SOCKETS, VERSION,
+};
+
+/// Length of the common register block.
+const COMMON_LEN: usize = Reg::VERSIONR.addr() as usize + 1;
+/// Length of a socket register block.
+const SOCKET_LEN: usize = SnReg::KPALVTR.addr() as usize + 1;
+/// Maximum size of a socket buffer.
+const MAX_BUF_SIZE: usize = 16 * 1024;
+/// Length of the W5500 UDP header prepended to received datagrams.
+const UDP_HEADER_LEN: u16 = 8;
+
+#[derive(Debug)]
+struct Socket {
+    regs: [u8; SOCKET_LEN],
+    tx_buf: Vec<u8>,
+    rx_buf: Vec<u8>,
+    /// Value of the TX write pointer at the last send command.
+    tx_sent: u16,
+    /// Connected TCP socket.
+    peer: Option<Sn>,
+}
+
+impl Socket {
+    fn new() -> Self {
+        let mut socket: Socket = Self {
+            regs: [0; SOCKET_LEN],
+            tx_buf: vec![0; MAX_BUF_SIZE],
+            rx_buf: vec![0; MAX_BUF_SIZE],
+            tx_sent: 0,
+            peer: None,
+        };
+        socket.regs[usize::from(SnReg::IMR.addr())] = 0xFF;
+        socket.regs[usize::from(SnReg::TTL.addr())] = 0x80;
+        socket.regs[usize::from(SnReg::RXBUF_SIZE.addr())] = 2;
+        socket.regs[usize::from(SnReg::TXBUF_SIZE.addr())] = 2;
+        socket.set_u16(SnReg::TX_FSR0, socket.tx_size());
+        socket
+    }
+
+    fn reg(&self, reg: SnReg) -> u8 {
+        self.regs[usize::from(reg.addr())]
+    }
+
+    fn set_reg(&mut self, reg: SnReg, val: u8) {
+        self.regs[usize::from(reg.addr())] = val
+    }
+
+    fn u16(&self, reg: SnReg) -> u16 {
+        let addr: usize = reg.addr().into();
+        u16::from_be_bytes([self.regs[addr], self.regs[addr + 1]])
+    }
+
+    fn set_u16(&mut self, reg: SnReg, val: u16) {
+        let addr: usize = reg.addr().into();
+        self.regs[addr..addr + 2].copy_from_slice(&val.to_be_bytes())
+    }
+
+    fn status(&self) -> u8 {
+        self.reg(SnReg::SR)
+    }
+
+    fn set_status(&mut self, status: SocketStatus) {
+        self.set_reg(SnReg::SR, status.into())
+    }
+
+    fn raise(&mut self, mask: u8) {
+        let ir: u8 = self.reg(SnReg::IR) | mask;
+        self.set_reg(SnReg::IR, ir)
+    }
+
+    fn tx_size(&self) -> u16 {
+        buf_size(self.reg(SnReg::TXBUF_SIZE))
+    }
+
+    fn rx_size(&self) -> u16 {
+        buf_size(self.reg(SnReg::RXBUF_SIZE))
+    }
+
+    fn rx_free(&self) -> u16 {
+        self.rx_size().saturating_sub(self.u16(SnReg::RX_RSR0))
+    }
+
+    /// Copies data out of the TX buffer, starting at the TX read pointer.
+    fn tx_data(&self, len: u16) -> Vec<u8> {
+        let mask: usize = usize::from(self.tx_size()).saturating_sub(1);
+        let rd: u16 = self.u16(SnReg::TX_RD0);
+        (0..len)
+            .map(|n| self.tx_buf[usize::from(rd.wrapping_add(n)) & mask])
+            .collect()
+    }
+
+    /// Appends data to the RX buffer, starting at the RX write pointer.
+    fn push_rx(&mut self, data: &[u8]) {
+        let mask: usize = usize::from(self.rx_size()).saturating_sub(1);
+        let wr: u16 = self.u16(SnReg::RX_WR0);
+        data.iter().enumerate().for_each(|(n, byte)| {
+            self.rx_buf[usize::from(wr.wrapping_add(n as u16)) & mask] = *byte
+        });
+        let len: u16 = data.len() as u16;
+        self.set_u16(SnReg::RX_WR0, wr.wrapping_add(len));
+        self.set_u16(SnReg::RX_RSR0, self.u16(SnReg::RX_RSR0) + len);
+        self.raise(SocketInterrupt::RECV_MASK)
+    }
+
+    fn update_fsr(&mut self) {
+        let in_flight: u16 = self.tx_sent.wrapping_sub(self.u16(SnReg::TX_RD0));
+        self.set_u16(SnReg::TX_FSR0, self.tx_size().saturating_sub(in_flight))
+    }
+}
+
+fn buf_size(kb: u8) -> u16 {
+    match kb {
+        1 | 2 | 4 | 8 | 16 => u16::from(kb) * 1024,
+        _ => 0,
+    }
+}
+
+/// In-memory model of the W5500.
+///
+/// This implements the [`Registers`] trait with the socket buffers and
+/// socket state kept in memory, every socket command completes immediately.
+/// Data sent on one socket is received by another socket of the same
+/// device, which makes it possible to test both ends of a protocol with the
+/// [`Udp`](crate::Udp) and [`Tcp`](crate::Tcp) traits.
+///
+/// The model covers:
+///
+/// * The socket TX and RX ring buffers, including the buffer size
+///   registers, and the free size and received size registers.
+/// * The socket status transitions of the open, listen, connect,
+///   disconnect, and close commands.
+/// * The socket interrupts, and the socket interrupt register (SIR).
+/// * UDP datagram framing, received datagrams are prefixed with the 8 byte
+///   W5500 UDP header.
+///
+/// Addressing is simplified, the destination IP address is ignored:
+///
+/// * A UDP datagram is delivered to the UDP socket bound to the destination
+///   port. The datagram is dropped if there is no such socket, or if it
+///   does not fit in the RX buffer, as a real network would.
+/// * A TCP connect command connects to the listening TCP socket on the
+///   destination port. The connection is refused if there is no such
+///   socket, raising the DISCON interrupt.
+///   TCP data that does not fit in the RX buffer of the peer is held in the
+///   TX buffer until the peer has received enough data.
+///
+/// The source address of received data is the configured source IP address
+/// (SIPR) and the port of the sending socket.
+///
+/// All other registers are plain memory, the PHY reports a 100 Mbps full
+/// duplex link.
+///
+/// # Example
+///
+/// ```
+/// use w5500_hl::{
+///     ll::{
+///         net::{Ipv4Addr, SocketAddrV4},
+///         Registers, Sn,
+///     },
+///     testutil::Loopback,
+///     Udp,
+/// };
+///
+/// let mut w5500 = Loopback::new();
+/// w5500.set_sipr(&Ipv4Addr::new(192, 168, 0, 2))?;
+/// w5500.udp_bind(Sn::Sn0, 8080)?;
+/// w5500.udp_bind(Sn::Sn1, 8081)?;
+///
+/// let dest: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 8081);
+/// w5500.udp_send_to(Sn::Sn0, b"hello", &dest)?;
+///
+/// let mut buf: [u8; 8] = [0; 8];
+/// let (len, src) = w5500.udp_recv_from(Sn::Sn1, &mut buf)?;
+/// assert_eq!(&buf[..usize::from(len)], b"hello");
+/// assert_eq!(src.port(), 8080);
+/// # Ok::<(), w5500_hl::Error<_>>(())
+/// ```
+#[derive(Debug)]
+pub struct Loopback {
+    common: [u8; COMMON_LEN],
+    sockets: [Socket; SOCKETS.len()],
+}
+
+impl Default for Loopback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Loopback {
+    /// Creates a new W5500 model, with all registers set to their reset
+    /// values.
+    pub fn new() -> Self {
+        let mut common: [u8; COMMON_LEN] = [0; COMMON_LEN];
+        common[usize::from(Reg::RTR0.addr())..usize::from(Reg::RCR.addr())]
+            .copy_from_slice(&0x07D0_u16.to_be_bytes());
+        common[usize::from(Reg::RCR.addr())] = 0x08;
+        common[usize::from(Reg::PTIMER.addr())] = 0x28;
+        // link up, 100 Mbps, full duplex
+        common[usize::from(Reg::PHYCFGR.addr())] = 0xBF;
+        common[usize::from(Reg::VERSIONR.addr())] = VERSION;
+        Self {
+            common,
+            sockets: core::array::from_fn(|_| Socket::new()),
+        }
+    }
+
+    fn socket(&mut self, sn: Sn) -> &mut Socket {
+        &mut self.sockets[usize::from(sn)]
+    }
+
+    fn sir_value(&self) -> u8 {
+        SOCKETS
+            .iter()
+            .filter(|sn| {
+                let socket: &Socket = &self.sockets[usize::from(**sn)];
+                socket.reg(SnReg::IR) & socket.reg(SnReg::IMR) != 0
+            })
+            .fold(0, |sir, sn| sir | sn.bitmask())
+    }
+
+    /// Finds the open socket with the given protocol status and local port.
+    fn find(&self, status: SocketStatus, port: u16) -> Option<Sn> {
+        SOCKETS.iter().copied().find(|sn| {
+            let socket: &Socket = &self.sockets[usize::from(*sn)];
+            socket.status() == u8::from(status) && socket.u16(SnReg::PORT0) == port
+        })
+    }
+
+    fn command(&mut self, sn: Sn, cmd: u8) {
+        let status: u8 = self.socket(sn).status();
+        match SocketCommand::try_from(cmd) {
+            Ok(SocketCommand::Open) => self.open(sn),
+            Ok(SocketCommand::Listen) if status == u8::from(SocketStatus::Init) => {
+                self.socket(sn).set_status(SocketStatus::Listen)
+            }
+            Ok(SocketCommand::Connect) if status == u8::from(SocketStatus::Init) => {
+                self.connect(sn)
+            }
+            Ok(SocketCommand::Disconnect) => self.disconnect(sn),
+            Ok(SocketCommand::Close) => {
+                let socket: &mut Socket = self.socket(sn);
+                socket.set_status(SocketStatus::Closed);
+                socket.peer = None;
+            }
+            Ok(SocketCommand::Send) | Ok(SocketCommand::SendMac) => self.send(sn),
+            Ok(SocketCommand::Recv) => self.recv(sn),
+            _ => (),
+        }
+    }
+
+    fn open(&mut self, sn: Sn) {
+        let socket: &mut Socket = self.socket(sn);
+        for reg in [
+            SnReg::TX_RD0,
+            SnReg::TX_WR0,
+            SnReg::RX_RSR0,
+            SnReg::RX_RD0,
+            SnReg::RX_WR0,
+        ] {
+            socket.set_u16(reg, 0);
+        }
+        socket.tx_sent = 0;
+        socket.peer = None;
+        socket.update_fsr();
+        let status: SocketStatus = match SocketMode::from(socket.reg(SnReg::MR)).protocol() {
+            Ok(Protocol::Tcp) => SocketStatus::Init,
+            Ok(Protocol::Udp) => SocketStatus::Udp,
+            Ok(Protocol::Macraw) if sn == Sn::Sn0 => SocketStatus::Macraw,
+            _ => SocketStatus::Closed,
+        };
+        socket.set_status(status);
+    }
+
+    fn connect(&mut self, sn: Sn) {
+        let dport: u16 = self.socket(sn).u16(SnReg::DPORT0);
+        match self.find(SocketStatus::Listen, dport) {
+            Some(listener) => {
+                let sipr: [u8; 4] = self.sipr_octets();
+                let port: u16 = self.socket(sn).u16(SnReg::PORT0);
+                let server: &mut Socket = self.socket(listener);
+                server.regs[usize::from(SnReg::DIPR0.addr())..usize::from(SnReg::DPORT0.addr())]
+                    .copy_from_slice(&sipr);
+                server.set_u16(SnReg::DPORT0, port);
+                server.set_status(SocketStatus::Established);
+                server.raise(SocketInterrupt::CON_MASK);
+                server.peer = Some(sn);
+                let client: &mut Socket = self.socket(sn);
+                client.set_status(SocketStatus::Established);
+                client.raise(SocketInterrupt::CON_MASK);
+                client.peer = Some(listener);
+            }
+            None => {
+                let client: &mut Socket = self.socket(sn);
+                client.set_status(SocketStatus::Closed);
+                client.raise(SocketInterrupt::DISCON_MASK);
+            }
+        }
+    }
+
+    fn disconnect(&mut self, sn: Sn) {
+        let status: u8 = self.socket(sn).status();
+        if status != u8::from(SocketStatus::Established)
+            && status != u8::from(SocketStatus::CloseWait)
+        {
+            return;
+        }
+        if let Some(peer) = self.socket(sn).peer.take() {
+            let peer: &mut Socket = self.socket(peer);
+            peer.peer = None;
+            if peer.status() == u8::from(SocketStatus::Established) {
+                peer.set_status(SocketStatus::CloseWait);
+            } else {
+                peer.set_status(SocketStatus::Closed);
+            }
+            peer.raise(SocketInterrupt::DISCON_MASK);
+        }
+        let socket: &mut Socket = self.socket(sn);
+        socket.set_status(SocketStatus::Closed);
+        socket.raise(SocketInterrupt::DISCON_MASK);
+    }
+
+    fn sipr_octets(&self) -> [u8; 4] {
+        let start: usize = Reg::SIPR0.addr().into();
+        [
+            self.common[start],
+            self.common[start + 1],
+            self.common[start + 2],
+            self.common[start + 3],
+        ]
+    }
+
+    fn send(&mut self, sn: Sn) {
+        let socket: &mut Socket = self.socket(sn);
+        socket.tx_sent = socket.u16(SnReg::TX_WR0);
+        let status: u8 = socket.status();
+        if status == u8::from(SocketStatus::Udp) {
+            self.send_udp(sn)
+        } else if status == u8::from(SocketStatus::Established)
+            || status == u8::from(SocketStatus::CloseWait)
+        {
+            self.send_tcp(sn)
+        } else {
+            // MACRAW frames have nowhere to go
+            let socket: &mut Socket = self.socket(sn);
+            socket.set_u16(SnReg::TX_RD0, socket.tx_sent);
+            socket.raise(SocketInterrupt::SENDOK_MASK);
+        }
+        self.socket(sn).update_fsr();
+    }
+
+    fn send_udp(&mut self, sn: Sn) {
+        let sipr: [u8; 4] = self.sipr_octets();
+        let socket: &mut Socket = self.socket(sn);
+        let len: u16 = socket.tx_sent.wrapping_sub(socket.u16(SnReg::TX_RD0));
+        let data: Vec<u8> = socket.tx_data(len);
+        let port: u16 = socket.u16(SnReg::PORT0);
+        let dport: u16 = socket.u16(SnReg::DPORT0);
+        socket.set_u16(SnReg::TX_RD0, socket.tx_sent);
+        socket.raise(SocketInterrupt::SENDOK_MASK);
+
+        if let Some(dest) = self.find(SocketStatus::Udp, dport) {
+            let dest: &mut Socket = self.socket(dest);
+            if dest.rx_free() >= UDP_HEADER_LEN + len {
+                let mut header: [u8; UDP_HEADER_LEN as usize] = [0; UDP_HEADER_LEN as usize];
+                header[..4].copy_from_slice(&sipr);
+                header[4..6].copy_from_slice(&port.to_be_bytes());
+                header[6..].copy_from_slice(&len.to_be_bytes());
+                let datagram: Vec<u8> = header.iter().chain(data.iter()).copied().collect();
+                dest.push_rx(&datagram);
+            }
+        }
+    }
+
+    /// Moves as much pending data as fits from the TX buffer of the socket
+    /// to the RX buffer of the peer.
+    fn send_tcp(&mut self, sn: Sn) {
+        let peer: Sn = match self.socket(sn).peer {
+            Some(peer) => peer,
+            None => return,
+        };
+        let free: u16 = self.socket(peer).rx_free();
+        let socket: &mut Socket = self.socket(sn);
+        let pending: u16 = socket.tx_sent.wrapping_sub(socket.u16(SnReg::TX_RD0));
+        if pending == 0 {
+            return;
+        }
+        let len: u16 = pending.min(free);
+        let data: Vec<u8> = socket.tx_data(len);
+        let rd: u16 = socket.u16(SnReg::TX_RD0).wrapping_add(len);
+        socket.set_u16(SnReg::TX_RD0, rd);
+        if len == pending {
+            socket.raise(SocketInterrupt::SENDOK_MASK);
+        }
+        socket.update_fsr();
+        if len != 0 {
+            self.socket(peer).push_rx(&data);
+        }
+    }
+
+    fn recv(&mut self, sn: Sn) {
+        let socket: &mut Socket = self.socket(sn);
+        let rsr: u16 = socket
+            .u16(SnReg::RX_WR0)
+            .wrapping_sub(socket.u16(SnReg::RX_RD0));
+        socket.set_u16(SnReg::RX_RSR0, rsr);
+        // the peer may have data that did not fit before
+        if let Some(peer) = socket.peer {
+            self.send_tcp(peer)
+        }
+    }
+
+    fn write_socket_reg(&mut self, sn: Sn, address: usize, byte: u8) {
+        const READ_ONLY: [SnReg; 9] = [
+            SnReg::SR,
+            SnReg::TX_FSR0,
+            SnReg::TX_FSR1,
+            SnReg::TX_RD0,
+            SnReg::TX_RD1,
+            SnReg::RX_RSR0,
+            SnReg::RX_RSR1,
+            SnReg::RX_WR0,
+            SnReg::RX_WR1,
+        ];
+        if address == usize::from(SnReg::CR.addr()) {
+            self.command(sn, byte)
+        } else if address == usize::from(SnReg::IR.addr()) {
+            let socket: &mut Socket = self.socket(sn);
+            let ir: u8 = socket.reg(SnReg::IR) & !byte;
+            socket.set_reg(SnReg::IR, ir);
+        } else if address < SOCKET_LEN
+            && !READ_ONLY
+                .iter()
+                .any(|reg| usize::from(reg.addr()) == address)
+        {
+            self.socket(sn).regs[address] = byte;
+        }
+    }
+}
+
+/// Decodes the block select bits into a socket and an offset from the
+/// socket register block.
+fn decode_block(block: u8) -> (Sn, u8) {
+    let sn: Sn = Sn::try_from(block >> 2).unwrap_or(Sn::Sn0);
+    (sn, block & 0b11)
+}
+
+impl Registers for Loopback {
+    type Error = Infallible;
+
+    fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        let sir: u8 = self.sir_value();
+        let (sn, offset) = decode_block(block);
+        data.iter_mut().enumerate().for_each(|(n, byte)| {
+            let address: u16 = address.wrapping_add(n as u16);
+            *byte = if block == COMMON_BLOCK_OFFSET {
+                if address == Reg::SIR.addr() {
+                    sir
+                } else {
+                    self.common.get(usize::from(address)).copied().unwrap_or(0)
+                }
+            } else {
+                let socket: &Socket = &self.sockets[usize::from(sn)];
+                match offset {
+                    1 => socket.regs.get(usize::from(address)).copied().unwrap_or(0),
+                    2 => {
+                        let mask: u16 = socket.tx_size().saturating_sub(1);
+                        socket.tx_buf[usize::from(address & mask)]
+                    }
+                    _ => {
+                        let mask: u16 = socket.rx_size().saturating_sub(1);
+                        socket.rx_buf[usize::from(address & mask)]
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+        let (sn, offset) = decode_block(block);
+        data.iter().enumerate().for_each(|(n, byte)| {
+            let address: u16 = address.wrapping_add(n as u16);
+            if block == COMMON_BLOCK_OFFSET {
+                if address == Reg::IR.addr() {
+                    self.common[usize::from(address)] &= !byte;
+                } else if address != Reg::SIR.addr() && address < Reg::VERSIONR.addr() {
+                    self.common[usize::from(address)] = *byte;
+                }
+            } else {
+                match offset {
+                    1 => self.write_socket_reg(sn, address.into(), *byte),
+                    2 => {
+                        let socket: &mut Socket = self.socket(sn);
+                        let mask: u16 = socket.tx_size().saturating_sub(1);
+                        socket.tx_buf[usize::from(address & mask)] = *byte;
+                    }
+                    _ => {
+                        let socket: &mut Socket = self.socket(sn);
+                        let mask: u16 = socket.rx_size().saturating_sub(1);
+                        socket.rx_buf[usize::from(address & mask)] = *byte;
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+}
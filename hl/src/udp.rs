@@ -1,11 +1,15 @@
+#[cfg(feature = "stats")]
+use crate::stats;
 use crate::{
-    io::{Read, Seek, SeekFrom, Write},
-    port_is_unique, Error, TcpReader,
+    ephemeral_port,
+    io::{furthest_ptr, Read, Seek, SeekFrom, Write, CHUNK_LEN},
+    port_is_unique, port_owner, sn_rx_rsr_stable, sn_tx_fsr_stable, unique_port, wait_for_status,
+    Error, TcpReader,
 };
-use core::cmp::min;
+use core::{cmp::min, mem::MaybeUninit, ops::Deref};
 use w5500_ll::{
-    net::{Ipv4Addr, SocketAddrV4},
-    Protocol, Registers, Sn, SocketCommand, SocketMode, SocketStatus, TxPtrs,
+    net::{Eui48Addr, Ipv4Addr, SocketAddrV4},
+    Protocol, Registers, Sn, SocketCommand, SocketInterrupt, SocketMode, SocketStatus,
 };
 
 /// W5500 UDP Header.
@@ -27,11 +31,11 @@ impl UdpHeader {
     // * 4 bytes origin IP
     // * 2 bytes origin port
     // * 2 bytes size
-    const LEN: u16 = 8;
-    const LEN_USIZE: usize = Self::LEN as usize;
+    pub(crate) const LEN: u16 = 8;
+    pub(crate) const LEN_USIZE: usize = Self::LEN as usize;
 
     /// Deserialize a UDP header.
-    fn deser(buf: [u8; Self::LEN_USIZE]) -> UdpHeader {
+    pub(crate) fn deser(buf: [u8; Self::LEN_USIZE]) -> UdpHeader {
         UdpHeader {
             origin: SocketAddrV4::new(
                 Ipv4Addr::new(buf[0], buf[1], buf[2], buf[3]),
@@ -42,6 +46,88 @@ impl UdpHeader {
     }
 }
 
+/// A received UDP datagram, stored in a fixed capacity buffer.
+///
+/// The datagram dereferences to the received payload.
+///
+/// Created with [`Udp::udp_recv_datagram`].
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{
+///     block,
+///     ll::{Registers, Sn::Sn0},
+///     Datagram, Udp,
+/// };
+///
+/// w5500.udp_bind(Sn0, 8080)?;
+/// let datagram: Datagram<512> = block!(w5500.udp_recv_datagram(Sn0))?;
+/// if !datagram.was_truncated() {
+///     let payload: &[u8] = &datagram;
+/// }
+/// # Ok::<(), w5500_hl::Error<_>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Datagram<const N: usize> {
+    header: UdpHeader,
+    len: u16,
+    buf: [u8; N],
+}
+
+impl<const N: usize> Datagram<N> {
+    /// Get the UDP header.
+    ///
+    /// The header contains the length of the datagram as it was received,
+    /// which may be larger than the capacity `N`.
+    #[inline]
+    pub fn header(&self) -> &UdpHeader {
+        &self.header
+    }
+
+    /// Origin IP address and port.
+    #[inline]
+    pub fn origin(&self) -> SocketAddrV4 {
+        self.header.origin
+    }
+
+    /// Returns `true` if the payload was truncated to fit in the capacity
+    /// `N`, or by the W5500.
+    #[inline]
+    pub fn was_truncated(&self) -> bool {
+        self.len != self.header.len
+    }
+}
+
+impl<const N: usize> Deref for Datagram<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len.into()]
+    }
+}
+
+/// Result of a previous send.
+///
+/// Returned by [`Udp::udp_last_send_result`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SendOutcome {
+    /// Neither the send complete nor the timeout interrupt is raised.
+    ///
+    /// The send is still in progress, or the result was already read.
+    Pending,
+    /// The data was sent.
+    Sent,
+    /// The send timed out, for example because the destination did not
+    /// respond to ARP requests.
+    ///
+    /// The data was not sent.
+    Timeout,
+}
+
 /// Streaming reader for a UDP socket buffer.
 ///
 /// This implements the [`Read`] and [`Seek`] traits.
@@ -76,6 +162,28 @@ impl UdpHeader {
 /// reader.done()?;
 /// # Ok::<(), w5500_hl::Error<_>>(())
 /// ```
+///
+/// # Early returns
+///
+/// No registers are written until [`done`](Read::done) or
+/// [`commit_partial`](Self::commit_partial) is called.
+/// When the reader is dropped without calling either, for example when an
+/// error is returned with `?` or when a panic unwinds, the datagram stays at
+/// the front of the queue, and the next reader starts at the beginning of
+/// the same datagram.
+///
+/// Use [`Udp::udp_reader_with`] to remove a datagram from the queue when
+/// processing it fails.
+///
+/// # Truncation
+///
+/// A reader is only created once the entire datagram is in the socket
+/// buffer, [`Udp::udp_reader`] returns [`Error::WouldBlock`] until then.
+/// The reader always contains the full length indicated by the
+/// [`header`](Self::header).
+///
+/// Datagrams copied into a fixed capacity buffer can be truncated, use
+/// [`Udp::udp_recv_datagram`] and [`Datagram::was_truncated`] for this.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct UdpReader<'w, W5500> {
@@ -110,6 +218,10 @@ impl<'w, W5500: Registers> Read<W5500::Error> for UdpReader<'w, W5500> {
         self.inner.read(buf)
     }
 
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<u16, W5500::Error> {
+        self.inner.read_uninit(buf)
+    }
+
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error<W5500::Error>> {
         self.inner.read_exact(buf)
     }
@@ -168,11 +280,13 @@ pub struct UdpWriter<'w, W5500> {
     pub(crate) head_ptr: u16,
     pub(crate) tail_ptr: u16,
     pub(crate) ptr: u16,
+    /// Furthest position written, the end of the data that will be sent.
+    pub(crate) end_ptr: u16,
 }
 
 impl<'w, W5500> Seek for UdpWriter<'w, W5500> {
     fn seek<E>(&mut self, pos: SeekFrom) -> Result<(), Error<E>> {
-        self.ptr = pos.new_ptr(self.ptr, self.head_ptr, self.tail_ptr)?;
+        self.ptr = pos.new_ptr(self.ptr, self.head_ptr, self.end_ptr)?;
         Ok(())
     }
 
@@ -200,6 +314,7 @@ impl<'w, W5500: Registers> Write<W5500::Error> for UdpWriter<'w, W5500> {
             self.w5500
                 .set_sn_tx_buf(self.sn, self.ptr, &buf[..usize::from(write_size)])?;
             self.ptr = self.ptr.wrapping_add(write_size);
+            self.end_ptr = furthest_ptr(self.head_ptr, self.end_ptr, self.ptr);
 
             Ok(write_size)
         } else {
@@ -215,17 +330,42 @@ impl<'w, W5500: Registers> Write<W5500::Error> for UdpWriter<'w, W5500> {
         } else {
             self.w5500.set_sn_tx_buf(self.sn, self.ptr, buf)?;
             self.ptr = self.ptr.wrapping_add(write_size);
+            self.end_ptr = furthest_ptr(self.head_ptr, self.end_ptr, self.ptr);
             Ok(())
         }
     }
 
     fn send(self) -> Result<(), W5500::Error> {
-        self.w5500.set_sn_tx_wr(self.sn, self.ptr)?;
+        self.w5500.set_sn_tx_wr(self.sn, self.end_ptr)?;
         self.w5500.set_sn_cr(self.sn, SocketCommand::Send)?;
         Ok(())
     }
 }
 
+/// Write formatted text into the socket buffer.
+///
+/// Formatting fails with [`core::fmt::Error`] when the text does not fit in
+/// the remaining space of the socket buffer, or if a bus error occurs;
+/// the underlying error is not available.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use core::fmt::Write as _;
+/// use w5500_hl::{io::Write, ll::Sn::Sn0, Udp, UdpWriter};
+///
+/// let mut writer: UdpWriter<_> = w5500.udp_writer(Sn0)?;
+/// write!(writer, "GET /{} HTTP/1.0\r\n\r\n", "index.html").unwrap();
+/// writer.send()?;
+/// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+/// ```
+impl<'w, W5500: Registers> core::fmt::Write for UdpWriter<'w, W5500> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write_all(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
 impl<'w, W5500: Registers> UdpWriter<'w, W5500> {
     /// Send all data previously written with [`UdpWriter::write`] and
     /// [`UdpWriter::write_all`] to the given address.
@@ -238,6 +378,54 @@ impl<'w, W5500: Registers> UdpWriter<'w, W5500> {
         self.w5500.set_sn_dest(self.sn, addr)?;
         self.send()
     }
+
+    /// Send all data previously written with [`write`] and [`write_all`],
+    /// and start a new segment at the current position.
+    ///
+    /// This is identical to [`send`], except the writer is not consumed.
+    ///
+    /// For UDP sockets each segment is sent as a separate datagram.
+    ///
+    /// Pointer bookkeeping after sending:
+    ///
+    /// * The start of the writer and the current position move to the end of
+    ///   the sent data, the sent data can no longer be reached with [`seek`]
+    ///   or [`rewind`].
+    /// * The free space is not reclaimed, [`remain`] is not increased.
+    ///   Create a new writer to use the space freed by the W5500 as the data
+    ///   is transmitted.
+    ///
+    /// This does not wait for the transmission to complete.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{io::Write, ll::Sn::Sn0, Udp, UdpWriter};
+    ///
+    /// let mut writer: UdpWriter<_> = w5500.udp_writer(Sn0)?;
+    /// writer.write_all(&[0x01, 0x02])?;
+    /// writer.send_segment()?;
+    /// writer.write_all(&[0x03, 0x04])?;
+    /// writer.send()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`send`]: Write::send
+    /// [`write_all`]: Write::write_all
+    /// [`write`]: Write::write
+    /// [`seek`]: Seek::seek
+    /// [`rewind`]: Seek::rewind
+    /// [`remain`]: Seek::remain
+    pub fn send_segment(&mut self) -> Result<(), W5500::Error> {
+        let end: u16 = self.end_ptr;
+        self.w5500.set_sn_tx_wr(self.sn, end)?;
+        self.w5500.set_sn_cr(self.sn, SocketCommand::Send)?;
+        self.head_ptr = end;
+        self.end_ptr = end;
+        self.ptr = end;
+        Ok(())
+    }
 }
 
 impl<'a, W: Registers> UdpReader<'a, W> {
@@ -265,6 +453,386 @@ impl<'a, W: Registers> UdpReader<'a, W> {
     pub fn header(&self) -> &UdpHeader {
         &self.header
     }
+
+    /// Read a socket address embedded in the datagram payload.
+    ///
+    /// This reads 6 bytes, 4 bytes of IPv4 address followed by a 2 byte big
+    /// endian port number, and deserializes them with
+    /// [`SocketAddrV4::from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::UnexpectedEof`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     io::Read,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::SocketAddrV4,
+    ///     Udp, UdpReader,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    ///
+    /// let mut reader: UdpReader<_> = w5500.udp_reader(Sn0)?;
+    /// let callback: SocketAddrV4 = reader.read_socket_addr()?;
+    /// reader.done()?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    pub fn read_socket_addr(&mut self) -> Result<SocketAddrV4, Error<W::Error>> {
+        let mut buf: [u8; 6] = [0; 6];
+        self.read_exact(&mut buf)?;
+        Ok(SocketAddrV4::from_bytes(buf))
+    }
+
+    /// Removes the data read so far from the queue, keeping the rest of the
+    /// datagram for the next reader.
+    ///
+    /// The bytes before the current [position] are dropped, and a new UDP
+    /// header is written in front of the remaining bytes.
+    /// The next call to [`Udp::udp_reader`] returns a reader for the rest of
+    /// the datagram, with the same [`origin`] and a [`len`] equal to the
+    /// number of remaining bytes.
+    ///
+    /// This allows large datagrams to be processed in multiple passes
+    /// without buffering the entire datagram.
+    ///
+    /// If the position is at the start of the datagram this does nothing,
+    /// and if the position is at the end of the datagram this is equivalent
+    /// to [`done`](Read::done).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     io::Read,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp, UdpReader,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    ///
+    /// let mut reader: UdpReader<_> = w5500.udp_reader(Sn0)?;
+    /// let mut buf: [u8; 512] = [0; 512];
+    /// let n: u16 = reader.read(&mut buf)?;
+    /// // ... process the first chunk
+    /// reader.commit_partial()?;
+    ///
+    /// // the next reader starts with the remaining bytes
+    /// let mut reader: UdpReader<_> = w5500.udp_reader(Sn0)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [position]: Seek::stream_position
+    /// [`origin`]: UdpHeader::origin
+    /// [`len`]: UdpHeader::len
+    pub fn commit_partial(self) -> Result<(), W::Error> {
+        let remain: u16 = self.remain();
+        if remain == 0 {
+            return self.done();
+        }
+        if self.stream_position() == 0 {
+            return Ok(());
+        }
+
+        let header_ptr: u16 = self.inner.ptr.wrapping_sub(UdpHeader::LEN);
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        header[..6].copy_from_slice(&self.header.origin.to_bytes());
+        header[6..].copy_from_slice(&remain.to_be_bytes());
+
+        let sn: Sn = self.inner.sn;
+        self.inner.w5500.set_sn_rx_buf(sn, header_ptr, &header)?;
+        self.inner.w5500.set_sn_rx_rd(sn, header_ptr)?;
+        self.inner.w5500.set_sn_cr(sn, SocketCommand::Recv)?;
+        Ok(())
+    }
+}
+
+/// Iterator over the datagrams queued in a UDP socket buffer.
+///
+/// Each item is the [`UdpHeader`] of the next datagram that has been
+/// completely buffered.
+/// The payload of the most recently returned datagram can be read with
+/// [`read_payload`](Self::read_payload), datagrams that are not read are
+/// skipped without transferring their payload over the bus.
+///
+/// The socket RX read pointer is only updated once the iterator is exhausted,
+/// or by calling [`done`](Self::done).
+/// If the iterator is dropped before then no datagrams are removed from the
+/// queue.
+///
+/// Created with [`Udp::udp_datagrams`].
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+/// use w5500_hl::{
+///     ll::{Registers, Sn::Sn0},
+///     Udp, UdpDatagrams, UdpHeader,
+/// };
+///
+/// w5500.udp_bind(Sn0, 8080)?;
+///
+/// let mut datagrams: UdpDatagrams<_> = w5500.udp_datagrams(Sn0)?;
+/// while let Some(header) = datagrams.next() {
+///     let header: UdpHeader = header?;
+///     // only read datagrams from port 1234, skip the rest
+///     if header.origin.port() == 1234 {
+///         let mut buf: [u8; 16] = [0; 16];
+///         let n: u16 = datagrams.read_payload(&mut buf)?;
+///     }
+/// }
+/// # Ok::<(), w5500_hl::Error<_>>(())
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UdpDatagrams<'w, W5500> {
+    w5500: &'w mut W5500,
+    sn: Sn,
+    /// Pointer to the header of the next datagram.
+    ptr: u16,
+    /// Number of bytes received after `ptr`.
+    remain: u16,
+    /// Payload pointer and length of the most recently returned datagram.
+    current: Option<(u16, u16)>,
+    /// Set once the iterator has returned `None` or an error.
+    finished: bool,
+}
+
+impl<'w, W5500: Registers> UdpDatagrams<'w, W5500> {
+    /// Read the payload of the most recently returned datagram into `buf`.
+    ///
+    /// On success, returns the number of bytes read.
+    /// If `buf` is too small to hold the payload the remaining bytes are
+    /// discarded.
+    ///
+    /// Returns `Ok(0)` if [`next`](Iterator::next) has not returned a
+    /// datagram yet.
+    pub fn read_payload(&mut self, buf: &mut [u8]) -> Result<u16, W5500::Error> {
+        match self.current {
+            Some((ptr, len)) => {
+                let read_size: u16 = min(len, buf.len().try_into().unwrap_or(u16::MAX));
+                if read_size != 0 {
+                    self.w5500
+                        .sn_rx_buf(self.sn, ptr, &mut buf[..read_size.into()])?;
+                }
+                Ok(read_size)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Removes the datagrams returned so far from the queue.
+    ///
+    /// This is only required when stopping before the iterator is
+    /// exhausted.
+    pub fn done(mut self) -> Result<(), W5500::Error> {
+        self.commit()
+    }
+
+    fn commit(&mut self) -> Result<(), W5500::Error> {
+        if self.current.take().is_some() {
+            self.w5500.set_sn_rx_rd(self.sn, self.ptr)?;
+            self.w5500.set_sn_cr(self.sn, SocketCommand::Recv)?;
+        }
+        Ok(())
+    }
+
+    fn next_header(&mut self) -> Result<Option<UdpHeader>, W5500::Error> {
+        let rsr: u16 = match self.remain.checked_sub(UdpHeader::LEN) {
+            Some(rsr) => rsr,
+            None => return Ok(None),
+        };
+
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.w5500.sn_rx_buf(self.sn, self.ptr, &mut header)?;
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        // not all data as indicated by the header has been buffered
+        if rsr < header.len {
+            return Ok(None);
+        }
+
+        let payload_ptr: u16 = self.ptr.wrapping_add(UdpHeader::LEN);
+        self.current = Some((payload_ptr, header.len));
+        self.ptr = payload_ptr.wrapping_add(header.len);
+        self.remain = rsr - header.len;
+        Ok(Some(header))
+    }
+}
+
+impl<'w, W5500: Registers> Iterator for UdpDatagrams<'w, W5500> {
+    type Item = Result<UdpHeader, W5500::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.next_header() {
+            Ok(Some(header)) => Some(Ok(header)),
+            Ok(None) => {
+                self.finished = true;
+                self.commit().err().map(Err)
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Returns [`Error::InvalidState`] if the socket is not opened as a UDP socket.
+fn udp_status_checked<T, E>(w5500: &mut T, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let actual: Result<SocketStatus, u8> = w5500.sn_sr(sn)?;
+    if actual != Ok(SocketStatus::Udp) {
+        return Err(Error::InvalidState {
+            expected: SocketStatus::Udp,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the socket is opened as a UDP socket.
+///
+/// With the `strict` feature this returns [`Error::InvalidState`], otherwise
+/// this is only a debug assertion.
+fn udp_status<T, E>(w5500: &mut T, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    #[cfg(feature = "strict")]
+    udp_status_checked(w5500, sn)?;
+    #[cfg(not(feature = "strict"))]
+    debug_assert_eq!(w5500.sn_sr(sn)?, Ok(SocketStatus::Udp));
+    Ok(())
+}
+
+/// Returns [`Error::NotConnected`] if the destination port of the socket is
+/// zero.
+fn udp_dest_checked<T, E>(w5500: &mut T, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    if w5500.sn_dport(sn)? == 0 {
+        return Err(Error::NotConnected);
+    }
+    Ok(())
+}
+
+/// Checks that a destination is configured for the socket.
+///
+/// With the `strict` feature this returns [`Error::NotConnected`] when the
+/// destination port is zero, otherwise this does nothing.
+fn udp_dest_status<T, E>(w5500: &mut T, sn: Sn) -> Result<(), Error<E>>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    #[cfg(feature = "strict")]
+    udp_dest_checked(w5500, sn)?;
+    #[cfg(not(feature = "strict"))]
+    let _ = (w5500, sn);
+    Ok(())
+}
+
+/// Sends data to the currently configured destination, without checking the
+/// socket status.
+fn udp_send_unchecked<T, E>(w5500: &mut T, sn: Sn, buf: &[u8]) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let data_len: u16 = u16::try_from(buf.len()).unwrap_or(u16::MAX);
+    let free_size: u16 = sn_tx_fsr_stable(w5500, sn)?;
+    let tx_bytes: u16 = min(data_len, free_size);
+    if tx_bytes != 0 {
+        let ptr: u16 = w5500.sn_tx_wr(sn)?;
+        w5500.set_sn_tx_buf(sn, ptr, &buf[..tx_bytes.into()])?;
+        w5500.set_sn_tx_wr(sn, ptr.wrapping_add(tx_bytes))?;
+        w5500.set_sn_cr(sn, SocketCommand::Send)?;
+    }
+    Ok(tx_bytes)
+}
+
+/// Sends data to the currently configured destination if there is enough
+/// free space, without checking the socket status.
+fn udp_send_if_free_unchecked<T, E>(w5500: &mut T, sn: Sn, buf: &[u8]) -> Result<u16, E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    let data_len: u16 = match u16::try_from(buf.len()) {
+        Ok(0) | Err(_) => return Ok(0),
+        Ok(l) => l,
+    };
+    if data_len > sn_tx_fsr_stable(w5500, sn)? {
+        return Ok(0);
+    }
+    let ptr: u16 = w5500.sn_tx_wr(sn)?;
+    w5500.set_sn_tx_buf(sn, ptr, buf)?;
+    w5500.set_sn_tx_wr(sn, ptr.wrapping_add(data_len))?;
+    w5500.set_sn_cr(sn, SocketCommand::Send)?;
+    Ok(data_len)
+}
+
+/// Opens a socket in UDP mode with the given socket mode.
+fn udp_open<T, E>(w5500: &mut T, sn: Sn, port: u16, mode: SocketMode) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    debug_assert!(
+        port_is_unique(w5500, sn, port)?,
+        "Local port {port} is in use"
+    );
+    udp_open_unchecked(w5500, sn, port, mode)
+}
+
+/// Opens a socket in UDP mode with the given socket mode, without checking
+/// if the port is in use.
+fn udp_open_unchecked<T, E>(w5500: &mut T, sn: Sn, port: u16, mode: SocketMode) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    w5500.set_sn_cr(sn, SocketCommand::Close)?;
+    // This will not hang, the socket status will always change to closed
+    // after a close command.
+    // (unless you do somthing silly like holding the W5500 in reset)
+    wait_for_status(w5500, sn, SocketStatus::Closed)?;
+    w5500.set_sn_port(sn, port)?;
+    w5500.set_sn_mr(sn, mode)?;
+    w5500.set_sn_cr(sn, SocketCommand::Open)?;
+    // This will not hang, the socket status will always change to Udp
+    // after a open command with SN_MR set to UDP.
+    // (unless you do somthing silly like holding the W5500 in reset)
+    wait_for_status(w5500, sn, SocketStatus::Udp)?;
+    Ok(())
+}
+
+/// Records an RX buffer overflow event if the socket RX buffer is full.
+///
+/// `rsr` is the value of the RX received size register.
+#[cfg(feature = "stats")]
+fn check_rx_overflow<T, E>(w5500: &mut T, sn: Sn, rsr: u16) -> Result<(), E>
+where
+    T: ?Sized + Registers<Error = E>,
+{
+    if let Ok(size) = w5500.sn_rxbuf_size(sn)? {
+        if usize::from(rsr) >= size.size_in_bytes() {
+            stats::rx_overflow(sn)
+        }
+    }
+    Ok(())
 }
 
 /// A W5500 UDP socket trait.
@@ -306,6 +874,8 @@ pub trait Udp: Registers {
     /// # Panics
     ///
     /// * (debug) The port must not be in use by any other socket on the W5500.
+    ///   Use [`udp_bind_checked`](Udp::udp_bind_checked) to check the port in
+    ///   release builds.
     ///
     /// # Example
     ///
@@ -324,101 +894,296 @@ pub trait Udp: Registers {
     /// [`std::net::UdpSocket::bind`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.bind
     /// [source IP register]: w5500_ll::Registers::sipr
     fn udp_bind(&mut self, sn: Sn, port: u16) -> Result<(), Self::Error> {
-        debug_assert!(
-            port_is_unique(self, sn, port)?,
-            "Local port {port} is in use"
-        );
-
-        self.set_sn_cr(sn, SocketCommand::Close)?;
-        // This will not hang, the socket status will always change to closed
-        // after a close command.
-        // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Closed) {}
-        self.set_sn_port(sn, port)?;
         const MODE: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Udp);
-        self.set_sn_mr(sn, MODE)?;
-        self.set_sn_cr(sn, SocketCommand::Open)?;
-        // This will not hang, the socket status will always change to Udp
-        // after a open command with SN_MR set to UDP.
-        // (unless you do somthing silly like holding the W5500 in reset)
-        while self.sn_sr(sn)? != Ok(SocketStatus::Udp) {}
-        Ok(())
+        udp_open(self, sn, port, MODE)
     }
 
-    /// Receives a single datagram message on the socket.
-    /// On success, returns the number of bytes read and the origin.
+    /// Binds the socket to the given port, returning an error if the port is
+    /// in use by another socket.
     ///
-    /// The function must be called with valid byte array `buf` of sufficient
-    /// size to hold the message bytes.
-    /// If a message is too long to fit in the supplied buffer, excess bytes
-    /// will be discarded.
+    /// This is identical to [`udp_bind`], except the port is always checked,
+    /// not only with debug assertions.
+    /// Two sockets bound to the same port will cause one of the sockets to
+    /// never receive data.
     ///
-    /// # Comparison to [`std::net::UdpSocket::recv_from`]
-    ///
-    /// * This method will always discard excess bytes from the socket buffer.
-    /// * This method is non-blocking, use [`block`] to treat it as blocking.
+    /// The check reads the port and status registers of the other 7 sockets.
     ///
     /// # Errors
     ///
     /// This method can only return:
     ///
     /// * [`Error::Other`]
-    /// * [`Error::WouldBlock`]
-    ///
-    /// # Panics
-    ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * [`Error::PortInUse`]
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
     /// use w5500_hl::{
-    ///     block,
-    ///     ll::{Registers, Sn::Sn0},
-    ///     Udp,
+    ///     ll::{Registers, Sn},
+    ///     Error, Udp,
     /// };
     ///
-    /// w5500.udp_bind(Sn0, 8080)?;
-    /// let mut buf = [0; 10];
-    /// let (number_of_bytes, src_addr) = block!(w5500.udp_recv_from(Sn0, &mut buf))?;
-    ///
-    /// // panics if bytes were discarded
-    /// assert!(
-    ///     usize::from(number_of_bytes) < buf.len(),
-    ///     "Buffer was too small to receive all data"
+    /// w5500.udp_bind_checked(Sn::Sn0, 8080)?;
+    /// assert_eq!(
+    ///     w5500.udp_bind_checked(Sn::Sn1, 8080),
+    ///     Err(Error::PortInUse(Sn::Sn0))
     /// );
-    ///
-    /// let filled_buf = &mut buf[..number_of_bytes.into()];
     /// # Ok::<(), w5500_hl::Error<_>>(())
     /// ```
     ///
-    /// [`std::net::UdpSocket::recv_from`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.recv_from
-    /// [`block`]: crate::block
-    fn udp_recv_from(
-        &mut self,
-        sn: Sn,
-        buf: &mut [u8],
-    ) -> Result<(u16, SocketAddrV4), Error<Self::Error>> {
-        let rsr: u16 = match self.sn_rx_rsr(sn)?.checked_sub(UdpHeader::LEN) {
-            Some(rsr) => rsr,
-            // nothing to recieve
-            None => return Err(Error::WouldBlock),
-        };
-
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+    /// [`udp_bind`]: Udp::udp_bind
+    fn udp_bind_checked(&mut self, sn: Sn, port: u16) -> Result<(), Error<Self::Error>> {
+        const MODE: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Udp);
+        if let Some(owner) = port_owner(self, Some(sn), port)? {
+            return Err(Error::PortInUse(owner));
+        }
+        udp_open_unchecked(self, sn, port, MODE)?;
+        Ok(())
+    }
 
-        let mut ptr: u16 = self.sn_rx_rd(sn)?;
-        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
-        self.sn_rx_buf(sn, ptr, &mut header)?;
-        ptr = ptr.wrapping_add(UdpHeader::LEN);
-        let header: UdpHeader = UdpHeader::deser(header);
+    /// Binds the socket to the given port, ignoring broadcast datagrams.
+    ///
+    /// This is identical to [`udp_bind`], except the socket is opened with the
+    /// broadcast blocking bit set in the [socket mode register].
+    ///
+    /// Filtering is done by the W5500 hardware; broadcast datagrams are
+    /// dropped before they are written to the socket RX buffer.
+    /// This cannot be done in software after the fact because the W5500 UDP
+    /// header only contains the origin address, not the destination address.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The port must not be in use by any other socket on the W5500.
+    ///
+    /// # Example
+    ///
+    /// Bind the first socket to port 8080, ignoring broadcasts.
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn::Sn0};
+    /// use w5500_hl::Udp;
+    ///
+    /// w5500.udp_bind_unicast(Sn0, 8080)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_bind`]: Udp::udp_bind
+    /// [socket mode register]: w5500_ll::Registers::sn_mr
+    fn udp_bind_unicast(&mut self, sn: Sn, port: u16) -> Result<(), Self::Error> {
+        const MODE: SocketMode = SocketMode::DEFAULT
+            .set_protocol(Protocol::Udp)
+            .enable_bcastb();
+        udp_open(self, sn, port, MODE)
+    }
+
+    /// Binds the socket to the given port, and joins the multicast `group`.
+    ///
+    /// The destination hardware address of the socket is set to the multicast
+    /// hardware address of the group, and the destination of the socket is
+    /// set to `group`, before opening the socket with the multicast bit set
+    /// in the [socket mode register].
+    /// The W5500 sends an IGMP join report when the socket is opened, and
+    /// datagrams sent with [`udp_send`] are sent to the group.
+    ///
+    /// The multicast TTL is not modified, see [`Registers::set_sn_ttl`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The port must not be in use by any other socket on the W5500.
+    /// * (debug) The group address must be a multicast address.
+    ///
+    /// # Example
+    ///
+    /// Join the mDNS multicast group.
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const MDNS: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(224, 0, 0, 251), 5353);
+    ///
+    /// w5500.udp_bind_multicast(Sn0, 5353, &MDNS)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_send`]: Udp::udp_send
+    /// [socket mode register]: w5500_ll::Registers::sn_mr
+    /// [`Registers::set_sn_ttl`]: w5500_ll::Registers::set_sn_ttl
+    fn udp_bind_multicast(
+        &mut self,
+        sn: Sn,
+        port: u16,
+        group: &SocketAddrV4,
+    ) -> Result<(), Self::Error> {
+        debug_assert!(
+            group.ip().is_multicast(),
+            "{} is not a multicast address",
+            group.ip()
+        );
+        const MODE: SocketMode = SocketMode::DEFAULT
+            .set_protocol(Protocol::Udp)
+            .enable_multi();
+        // the low 23 bits of the group address are mapped to the hardware
+        // address, RFC 1112 section 6.4
+        let [_, b, c, d] = group.ip().octets;
+        self.set_sn_dhar(sn, &Eui48Addr::new(0x01, 0x00, 0x5E, b & 0x7F, c, d))?;
+        self.set_sn_dest(sn, group)?;
+        udp_open(self, sn, port, MODE)
+    }
+
+    /// Binds the socket to the given port, or the next free port if the given
+    /// port is in use by another socket.
+    /// On success, returns the port the socket was bound to.
+    ///
+    /// Ports are scanned upwards from `port`, wrapping around to port 1
+    /// after port 65535.
+    /// Port 0 is never selected.
+    ///
+    /// This is otherwise identical to [`udp_bind`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn};
+    /// use w5500_hl::Udp;
+    ///
+    /// w5500.udp_bind(Sn::Sn0, 8080)?;
+    /// let port: u16 = w5500.udp_bind_or_next(Sn::Sn1, 8080)?;
+    /// assert_eq!(port, 8081);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_bind`]: Udp::udp_bind
+    fn udp_bind_or_next(&mut self, sn: Sn, port: u16) -> Result<u16, Self::Error> {
+        let port: u16 = unique_port(self, sn, port)?;
+        self.udp_bind(sn, port)?;
+        Ok(port)
+    }
+
+    /// Binds the socket to a free port in the dynamic port range, 49152 to
+    /// 65535.
+    /// On success, returns the port the socket was bound to.
+    ///
+    /// This is useful for client sockets, where the local port does not
+    /// matter.
+    /// The lowest port that is not in use by another socket is selected.
+    ///
+    /// This is otherwise identical to [`udp_bind`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::ll::{Registers, Sn};
+    /// use w5500_hl::Udp;
+    ///
+    /// let port: u16 = w5500.udp_bind_ephemeral(Sn::Sn0)?;
+    /// assert!(port >= 49152);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_bind`]: Udp::udp_bind
+    fn udp_bind_ephemeral(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        let port: u16 = ephemeral_port(self, sn)?;
+        self.udp_bind(sn, port)?;
+        Ok(port)
+    }
+
+    /// Receives a single datagram message on the socket.
+    /// On success, returns the number of bytes read and the origin.
+    ///
+    /// The function must be called with valid byte array `buf` of sufficient
+    /// size to hold the message bytes.
+    /// If a message is too long to fit in the supplied buffer, excess bytes
+    /// will be discarded.
+    ///
+    /// # Comparison to [`std::net::UdpSocket::recv_from`]
+    ///
+    /// * This method will always discard excess bytes from the socket buffer.
+    /// * This method is non-blocking, use [`block`] to treat it as blocking.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let mut buf = [0; 10];
+    /// let (number_of_bytes, src_addr) = block!(w5500.udp_recv_from(Sn0, &mut buf))?;
+    ///
+    /// // panics if bytes were discarded
+    /// assert!(
+    ///     usize::from(number_of_bytes) < buf.len(),
+    ///     "Buffer was too small to receive all data"
+    /// );
+    ///
+    /// let filled_buf = &mut buf[..number_of_bytes.into()];
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`std::net::UdpSocket::recv_from`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.recv_from
+    /// [`block`]: crate::block
+    fn udp_recv_from(
+        &mut self,
+        sn: Sn,
+        buf: &mut [u8],
+    ) -> Result<(u16, SocketAddrV4), Error<Self::Error>> {
+        udp_status(self, sn)?;
+
+        let rsr: u16 = match sn_rx_rsr_stable(self, sn)?.checked_sub(UdpHeader::LEN) {
+            Some(rsr) => rsr,
+            // nothing to recieve
+            None => return Err(Error::WouldBlock),
+        };
+
+        let mut ptr: u16 = self.sn_rx_rd(sn)?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(sn, ptr, &mut header)?;
+        ptr = ptr.wrapping_add(UdpHeader::LEN);
+        let header: UdpHeader = UdpHeader::deser(header);
 
         // not all data as indicated by the header has been buffered
         if rsr < header.len {
             return Err(Error::WouldBlock);
         }
 
+        #[cfg(feature = "stats")]
+        check_rx_overflow(self, sn, rsr + UdpHeader::LEN)?;
+
         let read_size: u16 = min(header.len, buf.len().try_into().unwrap_or(u16::MAX));
         if read_size != 0 {
             self.sn_rx_buf(sn, ptr, &mut buf[..read_size.into()])?;
@@ -429,6 +1194,146 @@ pub trait Udp: Registers {
         Ok((read_size, header.origin))
     }
 
+    /// Receives a single datagram message on the socket, into a
+    /// [`Datagram`] with a capacity of `N` bytes.
+    ///
+    /// This is identical to [`udp_recv_from`], except the payload, origin,
+    /// and original length are returned together.
+    /// Bytes that do not fit in the capacity are discarded, use
+    /// [`Datagram::was_truncated`] to check for this.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// See [`Datagram`].
+    ///
+    /// [`udp_recv_from`]: Udp::udp_recv_from
+    fn udp_recv_datagram<const N: usize>(
+        &mut self,
+        sn: Sn,
+    ) -> Result<Datagram<N>, Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        let mut reader: UdpReader<Self> = self.udp_reader(sn)?;
+        let header: UdpHeader = *reader.header();
+        let mut buf: [u8; N] = [0; N];
+        let len: u16 = reader.read(&mut buf)?;
+        reader.done()?;
+        Ok(Datagram { header, len, buf })
+    }
+
+    /// Receives a single datagram message on the socket, passing the payload
+    /// to `sink` in chunks.
+    /// On success, returns the number of bytes received and the origin.
+    ///
+    /// The payload is read from the W5500 in chunks of up to 64 bytes, this
+    /// allows forwarding large datagrams to another peripheral without
+    /// buffering the entire datagram in RAM.
+    ///
+    /// If `sink` returns an error the datagram is left in the queue, and the
+    /// error is returned.
+    /// The next call passes the datagram to `sink` again from the start.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`]
+    /// * Any error returned by `sink`
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn framebuffer_write(_: &[u8]) {}
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let (number_of_bytes, src_addr) = block!(w5500.udp_recv_into(Sn0, |chunk| {
+    ///     framebuffer_write(chunk);
+    ///     Ok(())
+    /// }))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_recv_into<F>(
+        &mut self,
+        sn: Sn,
+        mut sink: F,
+    ) -> Result<(u16, SocketAddrV4), Error<Self::Error>>
+    where
+        F: FnMut(&[u8]) -> Result<(), Error<Self::Error>>,
+    {
+        udp_status(self, sn)?;
+
+        let rsr: u16 = match sn_rx_rsr_stable(self, sn)?.checked_sub(UdpHeader::LEN) {
+            Some(rsr) => rsr,
+            // nothing to recieve
+            None => return Err(Error::WouldBlock),
+        };
+
+        let mut ptr: u16 = self.sn_rx_rd(sn)?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(sn, ptr, &mut header)?;
+        ptr = ptr.wrapping_add(UdpHeader::LEN);
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        // not all data as indicated by the header has been buffered
+        if rsr < header.len {
+            return Err(Error::WouldBlock);
+        }
+
+        let end: u16 = ptr.wrapping_add(header.len);
+        let mut remain: u16 = header.len;
+        let mut chunk: [u8; CHUNK_LEN as usize] = [0; CHUNK_LEN as usize];
+        while remain != 0 {
+            let chunk_len: u16 = min(remain, CHUNK_LEN);
+            let chunk: &mut [u8] = &mut chunk[..chunk_len.into()];
+            self.sn_rx_buf(sn, ptr, chunk)?;
+            ptr = ptr.wrapping_add(chunk_len);
+            remain -= chunk_len;
+            sink(chunk)?;
+        }
+
+        // counted once the datagram is removed, the datagram is received
+        // again after a sink error
+        #[cfg(feature = "stats")]
+        check_rx_overflow(self, sn, rsr + UdpHeader::LEN)?;
+
+        self.set_sn_rx_rd(sn, end)?;
+        self.set_sn_cr(sn, SocketCommand::Recv)?;
+        Ok((header.len, header.origin))
+    }
+
     /// Receives a single datagram message on the socket, without removing it
     /// from the queue.
     /// On success, returns the number of bytes read and the UDP header.
@@ -444,10 +1349,12 @@ pub trait Udp: Registers {
     ///
     /// * [`Error::Other`]
     /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
     ///
     /// # Example
     ///
@@ -483,14 +1390,14 @@ pub trait Udp: Registers {
         sn: Sn,
         buf: &mut [u8],
     ) -> Result<(u16, UdpHeader), Error<Self::Error>> {
-        let rsr: u16 = match self.sn_rx_rsr(sn)?.checked_sub(UdpHeader::LEN) {
+        udp_status(self, sn)?;
+
+        let rsr: u16 = match sn_rx_rsr_stable(self, sn)?.checked_sub(UdpHeader::LEN) {
             Some(rsr) => rsr,
             // nothing to recieve
             None => return Err(Error::WouldBlock),
         };
 
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
-
         let mut ptr: u16 = self.sn_rx_rd(sn)?;
         let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
         self.sn_rx_buf(sn, ptr, &mut header)?;
@@ -522,10 +1429,12 @@ pub trait Udp: Registers {
     ///
     /// * [`Error::Other`]
     /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
     ///
     /// # Example
     ///
@@ -551,32 +1460,41 @@ pub trait Udp: Registers {
     /// # Ok::<(), w5500_hl::Error<_>>(())
     /// ```
     fn udp_peek_from_header(&mut self, sn: Sn) -> Result<UdpHeader, Error<Self::Error>> {
-        let rsr: u16 = self.sn_rx_rsr(sn)?;
+        udp_status(self, sn)?;
+
+        let rsr: u16 = sn_rx_rsr_stable(self, sn)?;
 
         // nothing to recieve
         if rsr < UdpHeader::LEN {
             return Err(Error::WouldBlock);
         }
 
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
-
         let ptr: u16 = self.sn_rx_rd(sn)?;
         let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
         self.sn_rx_buf(sn, ptr, &mut header)?;
         Ok(UdpHeader::deser(header))
     }
 
-    /// Sends data on the socket to the given address.
-    /// On success, returns the number of bytes written.
+    /// Returns the number of payload bytes currently buffered for the next
+    /// datagram, and the header of that datagram, without removing it from the
+    /// queue.
     ///
-    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    /// Unlike [`udp_peek_from`] this does not wait for the entire datagram to
+    /// be buffered; the number of buffered bytes may be less than
+    /// [`UdpHeader::len`] while the datagram is still being received.
     ///
-    /// * You cannot transmit more than `u16::MAX` bytes at once.
-    /// * You can only provide one destination.
+    /// * `Ok(None)` indicates there is no datagram header in the buffer yet.
+    /// * `Ok(Some((n, header)))` indicates `n` of `header.len` payload bytes
+    ///   are buffered.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_peek_available_checked`](Udp::udp_peek_available_checked) to
+    ///   check the socket status in release builds.
     ///
     /// # Example
     ///
@@ -584,39 +1502,1089 @@ pub trait Udp: Registers {
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
     /// use w5500_hl::{
     ///     ll::{Registers, Sn::Sn0},
-    ///     net::{Ipv4Addr, SocketAddrV4},
     ///     Udp,
     /// };
     ///
-    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
-    ///
     /// w5500.udp_bind(Sn0, 8080)?;
-    /// let buf: [u8; 10] = [0; 10];
-    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST)?;
-    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// if let Some((buffered, header)) = w5500.udp_peek_available(Sn0)? {
+    ///     if buffered < header.len {
+    ///         // datagram is still arriving
+    ///     }
+    /// }
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
     ///
-    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
+    /// [`udp_peek_from`]: Udp::udp_peek_from
+    fn udp_peek_available(&mut self, sn: Sn) -> Result<Option<(u16, UdpHeader)>, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+
+        let rsr: u16 = match sn_rx_rsr_stable(self, sn)?.checked_sub(UdpHeader::LEN) {
+            Some(rsr) => rsr,
+            // nothing to recieve
+            None => return Ok(None),
+        };
+
+        let ptr: u16 = self.sn_rx_rd(sn)?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(sn, ptr, &mut header)?;
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        // limit to the length of the first datagram if we have more than a
+        // single datagram enqueued
+        Ok(Some((min(header.len, rsr), header)))
+    }
+
+    /// Checked version of [`udp_peek_available`](Udp::udp_peek_available).
+    ///
+    /// This is identical to [`udp_peek_available`](Udp::udp_peek_available),
+    /// except the socket status is always checked, not only with debug
+    /// assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// if let Some((buffered, header)) = w5500.udp_peek_available_checked(Sn0)? {
+    ///     if buffered < header.len {
+    ///         // datagram is still arriving
+    ///     }
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_peek_available_checked(
+        &mut self,
+        sn: Sn,
+    ) -> Result<Option<(u16, UdpHeader)>, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        Ok(self.udp_peek_available(sn)?)
+    }
+
+    /// Discards all datagrams currently queued in the socket RX buffer.
+    ///
+    /// On success, returns the number of bytes discarded, including the
+    /// W5500 UDP headers.
+    ///
+    /// This is faster than receiving each datagram into a throwaway buffer
+    /// because the payloads are never read over the bus; only the RX read
+    /// pointer is advanced.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_drain_checked`](Udp::udp_drain_checked) to check the socket
+    ///   status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let discarded: u16 = w5500.udp_drain(Sn0)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn udp_drain(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+
+        let rsr: u16 = sn_rx_rsr_stable(self, sn)?;
+        if rsr != 0 {
+            let ptr: u16 = self.sn_rx_rd(sn)?;
+            self.set_sn_rx_rd(sn, ptr.wrapping_add(rsr))?;
+            self.set_sn_cr(sn, SocketCommand::Recv)?;
+        }
+        Ok(rsr)
+    }
+
+    /// Checked version of [`udp_drain`](Udp::udp_drain).
+    ///
+    /// This is identical to [`udp_drain`](Udp::udp_drain), except the socket
+    /// status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let discarded: u16 = w5500.udp_drain_checked(Sn0)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_drain_checked(&mut self, sn: Sn) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        Ok(self.udp_drain(sn)?)
+    }
+
+    /// Returns the number of bytes received in the socket RX buffer.
+    ///
+    /// This includes the W5500 UDP headers, and may span multiple datagrams.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_rx_available_checked`](Udp::udp_rx_available_checked) to check
+    ///   the socket status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let rx_bytes: u16 = w5500.udp_rx_available(Sn0)?;
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn udp_rx_available(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+        sn_rx_rsr_stable(self, sn)
+    }
+
+    /// Checked version of [`udp_rx_available`](Udp::udp_rx_available).
+    ///
+    /// This is identical to [`udp_rx_available`](Udp::udp_rx_available), except
+    /// the socket status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let rx_bytes: u16 = w5500.udp_rx_available_checked(Sn0)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_rx_available_checked(&mut self, sn: Sn) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        Ok(sn_rx_rsr_stable(self, sn)?)
+    }
+
+    /// Returns the number of free bytes in the socket TX buffer.
+    ///
+    /// A datagram of this length or less can be sent without truncation.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_tx_free_checked`](Udp::udp_tx_free_checked) to check the socket
+    ///   status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// if usize::from(w5500.udp_tx_free(Sn0)?) >= buf.len() {
+    ///     w5500.udp_send(Sn0, &buf)?;
+    /// }
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    fn udp_tx_free(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+        sn_tx_fsr_stable(self, sn)
+    }
+
+    /// Checked version of [`udp_tx_free`](Udp::udp_tx_free).
+    ///
+    /// This is identical to [`udp_tx_free`](Udp::udp_tx_free), except the
+    /// socket status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// if usize::from(w5500.udp_tx_free_checked(Sn0)?) >= buf.len() {
+    ///     w5500.udp_send(Sn0, &buf)?;
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_tx_free_checked(&mut self, sn: Sn) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        Ok(sn_tx_fsr_stable(self, sn)?)
+    }
+
+    /// Returns the length of the largest datagram that can be sent on the
+    /// socket.
+    ///
+    /// This is the size of the socket TX buffer, datagrams larger than this
+    /// can never be sent without truncation.
+    /// A datagram of this length can only be sent once the TX buffer is
+    /// empty, see [`udp_tx_free`] for the space currently available.
+    ///
+    /// Returns `0` if the TX buffer size register holds an invalid value.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let chunk_len: u16 = w5500.udp_max_send_len(Sn0)?.min(1024);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_tx_free`]: Udp::udp_tx_free
+    fn udp_max_send_len(&mut self, sn: Sn) -> Result<u16, Self::Error> {
+        Ok(match self.sn_txbuf_size(sn)? {
+            Ok(size) => size.size_in_bytes().try_into().unwrap_or(u16::MAX),
+            Err(_) => 0,
+        })
+    }
+
+    /// Returns the number of RX buffer overflow events recorded for the
+    /// socket.
+    ///
+    /// The W5500 silently drops datagrams that do not fit in a full RX
+    /// buffer, and does not count them.
+    /// As a heuristic an overflow event is recorded whenever
+    /// [`udp_recv_from`] or [`udp_recv_into`] receives a datagram while the
+    /// RX buffer is full.
+    /// This is only an approximate indicator of dropped datagrams, useful
+    /// for tuning buffer sizes and receive rates.
+    ///
+    /// The counters are global, they are shared between all W5500 instances.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{block, ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.udp_reset_overflow_events(Sn0);
+    /// let mut buf = [0; 10];
+    /// block!(w5500.udp_recv_from(Sn0, &mut buf))?;
+    /// let overflows: u32 = w5500.udp_overflow_events(Sn0);
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_recv_from`]: Udp::udp_recv_from
+    /// [`udp_recv_into`]: Udp::udp_recv_into
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    fn udp_overflow_events(&self, sn: Sn) -> u32 {
+        stats::rx_overflows(sn)
+    }
+
+    /// Resets the number of RX buffer overflow events recorded for the
+    /// socket to zero.
+    ///
+    /// See [`udp_overflow_events`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{ll::Sn::Sn0, Udp};
+    ///
+    /// w5500.udp_reset_overflow_events(Sn0);
+    /// assert_eq!(w5500.udp_overflow_events(Sn0), 0);
+    /// ```
+    ///
+    /// [`udp_overflow_events`]: Udp::udp_overflow_events
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    fn udp_reset_overflow_events(&self, sn: Sn) {
+        stats::reset_rx_overflows(sn)
+    }
+
+    /// Returns the currently configured destination address.
+    ///
+    /// This is the address used by [`udp_send`] and [`udp_send_if_free`],
+    /// it is set by [`udp_send_to`] and [`set_sn_dest`].
+    ///
+    /// # Comparison to [`std::net::UdpSocket::peer_addr`]
+    ///
+    /// This will return the destination address even if it has not been
+    /// set, the reset value is `0.0.0.0:0`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.udp_send_to(Sn0, b"hello", &DEST)?;
+    /// assert_eq!(w5500.udp_dest(Sn0)?, DEST);
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_send`]: Udp::udp_send
+    /// [`udp_send_if_free`]: Udp::udp_send_if_free
+    /// [`udp_send_to`]: Udp::udp_send_to
+    /// [`set_sn_dest`]: w5500_ll::Registers::set_sn_dest
+    /// [`std::net::UdpSocket::peer_addr`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.peer_addr
+    fn udp_dest(&mut self, sn: Sn) -> Result<SocketAddrV4, Self::Error> {
+        self.sn_dest(sn)
+    }
+
+    /// Returns `true` if the currently configured destination is equal to
+    /// `expected`.
+    ///
+    /// This is useful to check that another code path has not changed the
+    /// destination before calling [`udp_send`] or [`udp_send_if_free`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.set_sn_dest(Sn0, &DEST)?;
+    /// assert!(w5500.udp_assert_peer(Sn0, &DEST)?);
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_send`]: Udp::udp_send
+    /// [`udp_send_if_free`]: Udp::udp_send_if_free
+    fn udp_assert_peer(&mut self, sn: Sn, expected: &SocketAddrV4) -> Result<bool, Self::Error> {
+        Ok(self.udp_dest(sn)? == *expected)
+    }
+
+    /// Sends data on the socket to the given address.
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    ///
+    /// * You cannot transmit more than `u16::MAX` bytes at once.
+    /// * You can only provide one destination.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_send_to_checked`](Udp::udp_send_to_checked) to check the socket
+    ///   status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
     fn udp_send_to(&mut self, sn: Sn, buf: &[u8], addr: &SocketAddrV4) -> Result<u16, Self::Error> {
         self.set_sn_dest(sn, addr)?;
-        self.udp_send(sn, buf)
+        self.udp_send(sn, buf)
+    }
+
+    /// Checked version of [`udp_send_to`](Udp::udp_send_to).
+    ///
+    /// This is identical to [`udp_send_to`](Udp::udp_send_to), except the
+    /// socket status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to_checked(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_send_to_checked(
+        &mut self,
+        sn: Sn,
+        buf: &[u8],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        self.set_sn_dest(sn, addr)?;
+        Ok(udp_send_unchecked(self, sn, buf)?)
+    }
+
+    /// Sends data on the socket to the given address, and waits for the
+    /// transmission to complete.
+    /// On success, returns the number of bytes written.
+    ///
+    /// This clears any pending send complete or timeout interrupts for the
+    /// socket before sending, then polls the socket interrupt register with
+    /// [`udp_poll_send`] until the transmission completes, calling
+    /// `delay_ms(1)` between polls.
+    ///
+    /// # Arguments
+    ///
+    /// * `sn` - The socket number to send on.
+    /// * `buf` - The data to send.
+    /// * `addr` - The IP address and port of the destination.
+    /// * `delay_ms` - A function that delays for the given number of
+    ///   milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::SendTimeout`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    ///
+    /// * You cannot transmit more than `u16::MAX` bytes at once.
+    /// * You can only provide one destination.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn delay_ms(_ms: u32) {}
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to_blocking(Sn0, &buf, &DEST, delay_ms)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_poll_send`]: Udp::udp_poll_send
+    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
+    fn udp_send_to_blocking<D>(
+        &mut self,
+        sn: Sn,
+        buf: &[u8],
+        addr: &SocketAddrV4,
+        mut delay_ms: D,
+    ) -> Result<u16, Error<Self::Error>>
+    where
+        D: FnMut(u32),
+    {
+        const SEND_IR: u8 = SocketInterrupt::SENDOK_MASK | SocketInterrupt::TIMEOUT_MASK;
+        udp_status(self, sn)?;
+        self.set_sn_ir(sn, SEND_IR)?;
+        let tx_bytes: u16 = self.udp_send_to(sn, buf, addr)?;
+        if tx_bytes != 0 {
+            loop {
+                match self.udp_poll_send(sn) {
+                    Err(Error::WouldBlock) => delay_ms(1),
+                    result => break result?,
+                }
+            }
+        }
+        Ok(tx_bytes)
+    }
+
+    /// Polls the socket interrupt register for the completion of a previous
+    /// send.
+    ///
+    /// The send complete or timeout interrupt is cleared when returning
+    /// `Ok(())` or [`Error::SendTimeout`] respectively.
+    ///
+    /// **Note:** A send complete interrupt left over from a previous send is
+    /// indistinguishable from a new one, clear the send complete and timeout
+    /// interrupts before sending when using this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::SendTimeout`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::{Registers, Sn::Sn0, SocketInterrupt},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.set_sn_ir(Sn0, SocketInterrupt::SENDOK_MASK | SocketInterrupt::TIMEOUT_MASK)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// w5500.udp_send_to(Sn0, &buf, &DEST)?;
+    /// // ... do other work
+    /// block!(w5500.udp_poll_send(Sn0))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_poll_send(&mut self, sn: Sn) -> Result<(), Error<Self::Error>> {
+        match self.udp_last_send_result(sn)? {
+            SendOutcome::Sent => Ok(()),
+            SendOutcome::Timeout => Err(Error::SendTimeout),
+            SendOutcome::Pending => Err(Error::WouldBlock),
+        }
+    }
+
+    /// Reads the result of a previous send from the socket interrupt
+    /// register.
+    ///
+    /// The send complete or timeout interrupt is cleared when returning
+    /// [`SendOutcome::Sent`] or [`SendOutcome::Timeout`] respectively, the
+    /// result of each send can only be read once.
+    ///
+    /// A send to an unreachable host returns `Ok` from [`udp_send_to`],
+    /// the data is dropped later when the W5500 times out waiting for an ARP
+    /// response.
+    /// This method allows detecting that loss, for example to retransmit.
+    ///
+    /// This is the non-blocking equivalent of [`udp_poll_send`].
+    /// [`udp_send_to_blocking`] clears the send interrupts before sending, and
+    /// reads the result itself; this method returns
+    /// [`SendOutcome::Pending`] after it.
+    ///
+    /// **Note:** A send complete interrupt left over from a previous send is
+    /// indistinguishable from a new one, clear the send complete and timeout
+    /// interrupts before sending when using this method.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0, SocketInterrupt},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     SendOutcome, Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.set_sn_ir(Sn0, SocketInterrupt::SENDOK_MASK | SocketInterrupt::TIMEOUT_MASK)?;
+    /// w5500.udp_send_to(Sn0, b"ping", &DEST)?;
+    /// // ... do other work
+    /// match w5500.udp_last_send_result(Sn0)? {
+    ///     SendOutcome::Pending => (), // check again later
+    ///     SendOutcome::Sent => (),
+    ///     SendOutcome::Timeout => (), // retransmit
+    /// }
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_poll_send`]: Udp::udp_poll_send
+    /// [`udp_send_to`]: Udp::udp_send_to
+    /// [`udp_send_to_blocking`]: Udp::udp_send_to_blocking
+    fn udp_last_send_result(&mut self, sn: Sn) -> Result<SendOutcome, Self::Error> {
+        let sn_ir: SocketInterrupt = self.sn_ir(sn)?;
+        if sn_ir.sendok_raised() {
+            self.set_sn_ir(sn, SocketInterrupt::SENDOK_MASK)?;
+            Ok(SendOutcome::Sent)
+        } else if sn_ir.timeout_raised() {
+            self.set_sn_ir(sn, SocketInterrupt::TIMEOUT_MASK)?;
+            Ok(SendOutcome::Timeout)
+        } else {
+            Ok(SendOutcome::Pending)
+        }
+    }
+
+    /// Checks if the `target` host responds to ARP requests.
+    /// Returns `true` if the hardware address of `target` was resolved.
+    ///
+    /// This is useful to check that the gateway is reachable before trusting
+    /// the network configuration.
+    ///
+    /// The socket is bound to a port in the dynamic port range, and a one
+    /// byte datagram is sent to the discard port, 9, of `target`.
+    /// The W5500 resolves the hardware address of `target` with ARP before
+    /// sending, `false` is returned when ARP times out, see
+    /// [`Error::SendTimeout`].
+    ///
+    /// This blocks until the datagram is sent or ARP times out, calling
+    /// `delay_ms(1)` between polls, see [`udp_send_to_blocking`].
+    /// The time taken for ARP to time out is determined by the retry time and
+    /// retry count, see [`Common::set_retry_time`] and
+    /// [`Common::set_retry_count`].
+    ///
+    /// **Note:** This consumes the socket, any previous configuration of the
+    /// socket is lost, and the socket is closed on return.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// # fn delay_ms(_ms: u32) {}
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn7},
+    ///     Udp,
+    /// };
+    ///
+    /// let gateway = w5500.gar()?;
+    /// let healthy: bool = w5500.udp_arp_probe(Sn7, &gateway, delay_ms)?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_send_to_blocking`]: Udp::udp_send_to_blocking
+    /// [`Common::set_retry_time`]: crate::Common::set_retry_time
+    /// [`Common::set_retry_count`]: crate::Common::set_retry_count
+    fn udp_arp_probe<D>(
+        &mut self,
+        sn: Sn,
+        target: &Ipv4Addr,
+        delay_ms: D,
+    ) -> Result<bool, Error<Self::Error>>
+    where
+        D: FnMut(u32),
+    {
+        const DISCARD_PORT: u16 = 9;
+
+        self.udp_bind_ephemeral(sn)?;
+        let result = self.udp_send_to_blocking(
+            sn,
+            &[0],
+            &SocketAddrV4::new(*target, DISCARD_PORT),
+            delay_ms,
+        );
+        self.set_sn_cr(sn, SocketCommand::Close)?;
+        match result {
+            Ok(_) => Ok(true),
+            Err(Error::SendTimeout) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sends data gathered from multiple buffers on the socket to the given
+    /// address, as a single datagram.
+    /// On success, returns the total number of bytes written.
+    ///
+    /// The buffers are written sequentially into the socket TX buffer, this
+    /// avoids copying them into a contiguous buffer beforehand.
+    ///
+    /// Like [`udp_send_to`] the datagram is truncated if there is not enough
+    /// free space in the W5500 transmit buffer; the buffer where the space ran
+    /// out is partially written, and any following buffers are not written.
+    ///
+    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    ///
+    /// * You cannot transmit more than `u16::MAX` bytes at once.
+    /// * You can only provide one destination.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_send_to_vectored_checked`](Udp::udp_send_to_vectored_checked) to
+    ///   check the socket status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let header: [u8; 4] = [0; 4];
+    /// let body: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to_vectored(Sn0, &[&header[..], &body[..]], &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), header.len() + body.len());
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`udp_send_to`]: Udp::udp_send_to
+    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
+    fn udp_send_to_vectored(
+        &mut self,
+        sn: Sn,
+        bufs: &[&[u8]],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+
+        self.set_sn_dest(sn, addr)?;
+
+        let mut free_size: u16 = sn_tx_fsr_stable(self, sn)?;
+        let head_ptr: u16 = self.sn_tx_wr(sn)?;
+        let mut ptr: u16 = head_ptr;
+        for buf in bufs {
+            if free_size == 0 {
+                break;
+            }
+            let data_len: u16 = u16::try_from(buf.len()).unwrap_or(u16::MAX);
+            let write_size: u16 = min(data_len, free_size);
+            if write_size != 0 {
+                self.set_sn_tx_buf(sn, ptr, &buf[..write_size.into()])?;
+                ptr = ptr.wrapping_add(write_size);
+                free_size -= write_size;
+            }
+        }
+
+        let tx_bytes: u16 = ptr.wrapping_sub(head_ptr);
+        if tx_bytes != 0 {
+            self.set_sn_tx_wr(sn, ptr)?;
+            self.set_sn_cr(sn, SocketCommand::Send)?;
+        }
+        Ok(tx_bytes)
+    }
+
+    /// Checked version of [`udp_send_to_vectored`](Udp::udp_send_to_vectored).
+    ///
+    /// This is identical to
+    /// [`udp_send_to_vectored`](Udp::udp_send_to_vectored), except the socket
+    /// status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let header: [u8; 4] = [0; 4];
+    /// let body: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 =
+    ///     w5500.udp_send_to_vectored_checked(Sn0, &[&header[..], &body[..]], &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), header.len() + body.len());
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_send_to_vectored_checked(
+        &mut self,
+        sn: Sn,
+        bufs: &[&[u8]],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        Ok(self.udp_send_to_vectored(sn, bufs, addr)?)
+    }
+
+    /// Sends data on the socket to the given address.
+    /// On success, returns the number of bytes written.
+    ///
+    /// This will transmit only if there is enough free space in the W5500
+    /// transmit buffer for the entire buffer, otherwise nothing is written and
+    /// `Ok(0)` is returned.
+    /// Empty buffers, and buffers longer than `u16::MAX`, are never sent.
+    /// Use [`udp_send_all`](Udp::udp_send_all) to get [`Error::WouldBlock`]
+    /// instead.
+    ///
+    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    ///
+    /// * You cannot transmit more than `u16::MAX` bytes at once.
+    /// * You can only provide one destination.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_send_to_if_free_checked`](Udp::udp_send_to_if_free_checked) to
+    ///   check the socket status in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to_if_free(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
+    fn udp_send_to_if_free(
+        &mut self,
+        sn: Sn,
+        buf: &[u8],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Self::Error> {
+        self.set_sn_dest(sn, addr)?;
+        self.udp_send_if_free(sn, buf)
+    }
+
+    /// Checked version of [`udp_send_to_if_free`](Udp::udp_send_to_if_free).
+    ///
+    /// This is identical to [`udp_send_to_if_free`](Udp::udp_send_to_if_free),
+    /// except the socket status is always checked, not only with debug
+    /// assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to_if_free_checked(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_send_to_if_free_checked(
+        &mut self,
+        sn: Sn,
+        buf: &[u8],
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        self.set_sn_dest(sn, addr)?;
+        Ok(udp_send_if_free_unchecked(self, sn, buf)?)
+    }
+
+    /// Sends data to the currently configured destination.
+    /// On success, returns the number of bytes written.
+    ///
+    /// The destination is set by the last call to [`Registers::set_sn_dest`],
+    /// [`Udp::udp_send_to`], or [`UdpWriter::udp_send_to`].
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_send_checked`](Udp::udp_send_checked) to check the socket status
+    ///   in release builds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// // send the same to the same destination
+    /// let tx_bytes: u16 = w5500.udp_send(Sn0, &buf)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// ```
+    ///
+    /// [`UdpWriter::udp_send_to`]: crate::UdpWriter::udp_send_to
+    fn udp_send(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+        udp_send_unchecked(self, sn, buf)
+    }
+
+    /// Checked version of [`udp_send`](Udp::udp_send).
+    ///
+    /// This is identical to [`udp_send`](Udp::udp_send), except the socket
+    /// status is always checked, not only with debug assertions.
+    /// The destination is also checked, the destination port must not be
+    /// zero.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    /// * [`Error::NotConnected`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// let buf: [u8; 10] = [0; 10];
+    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// // send the same to the same destination
+    /// let tx_bytes: u16 = w5500.udp_send_checked(Sn0, &buf)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_send_checked(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        udp_dest_checked(self, sn)?;
+        Ok(udp_send_unchecked(self, sn, buf)?)
     }
 
-    /// Sends data on the socket to the given address.
+    /// Sends data to the currently configured destination.
     /// On success, returns the number of bytes written.
     ///
-    /// This will transmit only if there is enough free space in the W5500
-    /// transmit buffer.
-    ///
-    /// # Comparison to [`std::net::UdpSocket::send_to`]
+    /// The destination is set by the last call to [`Registers::set_sn_dest`],
+    /// [`Udp::udp_send_to`], or [`UdpWriter::udp_send_to`].
     ///
-    /// * You cannot transmit more than `u16::MAX` bytes at once.
-    /// * You can only provide one destination.
+    /// This will transmit only if there is enough free space in the W5500
+    /// transmit buffer for the entire buffer, otherwise nothing is written and
+    /// `Ok(0)` is returned.
+    /// Empty buffers, and buffers longer than `u16::MAX`, are never sent.
+    /// Use [`udp_send_all`](Udp::udp_send_all) to get [`Error::WouldBlock`]
+    /// instead.
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_send_if_free_checked`](Udp::udp_send_if_free_checked) to check
+    ///   the socket status in release builds.
     ///
     /// # Example
     ///
@@ -634,29 +2602,32 @@ pub trait Udp: Registers {
     /// let buf: [u8; 10] = [0; 10];
     /// let tx_bytes: u16 = w5500.udp_send_to_if_free(Sn0, &buf, &DEST)?;
     /// assert_eq!(usize::from(tx_bytes), buf.len());
+    /// // send the same to the same destination
+    /// let tx_bytes: u16 = w5500.udp_send_if_free(Sn0, &buf)?;
+    /// assert_eq!(usize::from(tx_bytes), buf.len());
     /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
     /// ```
     ///
-    /// [`std::net::UdpSocket::send_to`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.send_to
-    fn udp_send_to_if_free(
-        &mut self,
-        sn: Sn,
-        buf: &[u8],
-        addr: &SocketAddrV4,
-    ) -> Result<u16, Self::Error> {
-        self.set_sn_dest(sn, addr)?;
-        self.udp_send_if_free(sn, buf)
+    /// [`UdpWriter::udp_send_to`]: crate::UdpWriter::udp_send_to
+    fn udp_send_if_free(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Self::Error> {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+        udp_send_if_free_unchecked(self, sn, buf)
     }
 
-    /// Sends data to the currently configured destination.
-    /// On success, returns the number of bytes written.
+    /// Checked version of [`udp_send_if_free`](Udp::udp_send_if_free).
     ///
-    /// The destination is set by the last call to [`Registers::set_sn_dest`],
-    /// [`Udp::udp_send_to`], or [`UdpWriter::udp_send_to`].
+    /// This is identical to [`udp_send_if_free`](Udp::udp_send_if_free), except
+    /// the socket status is always checked, not only with debug assertions.
+    /// The destination is also checked, the destination port must not be
+    /// zero.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    /// * [`Error::NotConnected`]
     ///
     /// # Example
     ///
@@ -672,48 +2643,57 @@ pub trait Udp: Registers {
     ///
     /// w5500.udp_bind(Sn0, 8080)?;
     /// let buf: [u8; 10] = [0; 10];
-    /// let tx_bytes: u16 = w5500.udp_send_to(Sn0, &buf, &DEST)?;
+    /// let tx_bytes: u16 = w5500.udp_send_to_if_free(Sn0, &buf, &DEST)?;
     /// assert_eq!(usize::from(tx_bytes), buf.len());
     /// // send the same to the same destination
-    /// let tx_bytes: u16 = w5500.udp_send(Sn0, &buf)?;
+    /// let tx_bytes: u16 = w5500.udp_send_if_free_checked(Sn0, &buf)?;
     /// assert_eq!(usize::from(tx_bytes), buf.len());
-    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// # Ok::<(), w5500_hl::Error<_>>(())
     /// ```
-    ///
-    /// [`UdpWriter::udp_send_to`]: crate::UdpWriter::udp_send_to
-    fn udp_send(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Self::Error> {
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
-
-        let data_len: u16 = u16::try_from(buf.len()).unwrap_or(u16::MAX);
-        let free_size: u16 = self.sn_tx_fsr(sn)?;
-        let tx_bytes: u16 = min(data_len, free_size);
-        if tx_bytes != 0 {
-            let ptr: u16 = self.sn_tx_wr(sn)?;
-            self.set_sn_tx_buf(sn, ptr, &buf[..tx_bytes.into()])?;
-            self.set_sn_tx_wr(sn, ptr.wrapping_add(tx_bytes))?;
-            self.set_sn_cr(sn, SocketCommand::Send)?;
-        }
-        Ok(tx_bytes)
+    fn udp_send_if_free_checked(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Error<Self::Error>> {
+        udp_status_checked(self, sn)?;
+        udp_dest_checked(self, sn)?;
+        Ok(udp_send_if_free_unchecked(self, sn, buf)?)
     }
 
-    /// Sends data to the currently configured destination.
-    /// On success, returns the number of bytes written.
+    /// Sends the entire buffer to the currently configured destination.
     ///
     /// The destination is set by the last call to [`Registers::set_sn_dest`],
     /// [`Udp::udp_send_to`], or [`UdpWriter::udp_send_to`].
     ///
-    /// This will transmit only if there is enough free space in the W5500
-    /// transmit buffer.
+    /// Data is only written, and the send command only issued, when the entire
+    /// buffer fits in the free space of the W5500 transmit buffer.
+    /// This is intended to be used with the [`block!`] macro.
+    ///
+    /// An empty buffer is not sent, `Ok(())` is returned without issuing the
+    /// send command.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// [`Error::OutOfMemory`] is returned when the buffer is larger than the
+    /// socket transmit buffer, and can never be sent.
+    ///
+    /// [`Error::WouldBlock`] is returned when there is not enough free space
+    /// in the transmit buffer, nothing is sent.
     ///
     /// # Panics
     ///
-    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) The socket must be opened as a UDP socket, unless the
+    ///   `strict` feature is enabled.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
     /// use w5500_hl::{
+    ///     block,
     ///     ll::{Registers, Sn::Sn0},
     ///     net::{Ipv4Addr, SocketAddrV4},
     ///     Udp,
@@ -722,31 +2702,210 @@ pub trait Udp: Registers {
     /// const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
     ///
     /// w5500.udp_bind(Sn0, 8080)?;
+    /// w5500.set_sn_dest(Sn0, &DEST)?;
     /// let buf: [u8; 10] = [0; 10];
-    /// let tx_bytes: u16 = w5500.udp_send_to_if_free(Sn0, &buf, &DEST)?;
-    /// assert_eq!(usize::from(tx_bytes), buf.len());
-    /// // send the same to the same destination
-    /// let tx_bytes: u16 = w5500.udp_send_if_free(Sn0, &buf)?;
-    /// assert_eq!(usize::from(tx_bytes), buf.len());
-    /// # Ok::<(), embedded_hal::spi::ErrorKind>(())
+    /// block!(w5500.udp_send_all(Sn0, &buf))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
     /// ```
     ///
+    /// [`block!`]: crate::block
     /// [`UdpWriter::udp_send_to`]: crate::UdpWriter::udp_send_to
-    fn udp_send_if_free(&mut self, sn: Sn, buf: &[u8]) -> Result<u16, Self::Error> {
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+    fn udp_send_all(&mut self, sn: Sn, buf: &[u8]) -> Result<(), Error<Self::Error>> {
+        udp_status(self, sn)?;
+        udp_dest_status(self, sn)?;
 
         let data_len: u16 = match u16::try_from(buf.len()) {
-            Ok(l) => l,
-            Err(_) => return Ok(0),
+            Ok(0) => return Ok(()),
+            Ok(data_len) => data_len,
+            Err(_) => return Err(Error::OutOfMemory),
         };
-        let free_size: u16 = self.sn_tx_fsr(sn)?;
-        if data_len <= free_size {
-            let ptr: u16 = self.sn_tx_wr(sn)?;
-            self.set_sn_tx_buf(sn, ptr, buf)?;
-            self.set_sn_tx_wr(sn, ptr.wrapping_add(data_len))?;
-            self.set_sn_cr(sn, SocketCommand::Send)?;
+        let free_size: u16 = sn_tx_fsr_stable(self, sn)?;
+        if data_len > free_size {
+            if let Ok(size) = self.sn_txbuf_size(sn)? {
+                if buf.len() > size.size_in_bytes() {
+                    return Err(Error::OutOfMemory);
+                }
+            }
+            return Err(Error::WouldBlock);
+        }
+
+        let ptr: u16 = self.sn_tx_wr(sn)?;
+        self.set_sn_tx_buf(sn, ptr, buf)?;
+        self.set_sn_tx_wr(sn, ptr.wrapping_add(data_len))?;
+        self.set_sn_cr(sn, SocketCommand::Send)?;
+        Ok(())
+    }
+
+    /// Relays the next datagram received on `src_sn` to `addr`, sending it
+    /// from `dst_sn`.
+    /// On success, returns the number of payload bytes relayed.
+    ///
+    /// The payload is copied from the `src_sn` RX buffer to the `dst_sn` TX
+    /// buffer in small chunks, without buffering the whole datagram.
+    /// The datagram is only removed from the `src_sn` queue once it has been
+    /// sent.
+    ///
+    /// The relayed datagram is sent from the IP address and port of the W5500,
+    /// the W5500 cannot send datagrams with the origin address of the
+    /// original datagram.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// [`Error::WouldBlock`] is returned when there is no datagram to relay,
+    /// or when there is not enough free space in the `dst_sn` transmit buffer.
+    ///
+    /// [`Error::OutOfMemory`] is returned when the datagram is larger than the
+    /// `dst_sn` transmit buffer, and can never be relayed.
+    /// The datagram is left in the `src_sn` queue.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) Both sockets must be opened as UDP sockets, unless the
+    ///   `strict` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::{Registers, Sn},
+    ///     net::{Ipv4Addr, SocketAddrV4},
+    ///     Udp,
+    /// };
+    ///
+    /// const UPSTREAM: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+    ///
+    /// w5500.udp_bind(Sn::Sn0, 8080)?;
+    /// w5500.udp_bind(Sn::Sn1, 8081)?;
+    /// let relayed: u16 = block!(w5500.udp_relay(Sn::Sn0, Sn::Sn1, &UPSTREAM))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_relay(
+        &mut self,
+        src_sn: Sn,
+        dst_sn: Sn,
+        addr: &SocketAddrV4,
+    ) -> Result<u16, Error<Self::Error>> {
+        udp_status(self, src_sn)?;
+        udp_status(self, dst_sn)?;
+
+        let rsr: u16 = match sn_rx_rsr_stable(self, src_sn)?.checked_sub(UdpHeader::LEN) {
+            Some(rsr) => rsr,
+            // nothing to recieve
+            None => return Err(Error::WouldBlock),
+        };
+
+        let sn_rx_rd: u16 = self.sn_rx_rd(src_sn)?;
+        let mut header: [u8; UdpHeader::LEN_USIZE] = [0; UdpHeader::LEN_USIZE];
+        self.sn_rx_buf(src_sn, sn_rx_rd, &mut header)?;
+        let header: UdpHeader = UdpHeader::deser(header);
+
+        // not all data as indicated by the header has been buffered
+        if rsr < header.len {
+            return Err(Error::WouldBlock);
+        }
+
+        #[cfg(feature = "stats")]
+        check_rx_overflow(self, src_sn, rsr + UdpHeader::LEN)?;
+
+        if header.len > sn_tx_fsr_stable(self, dst_sn)? {
+            if let Ok(size) = self.sn_txbuf_size(dst_sn)? {
+                if usize::from(header.len) > size.size_in_bytes() {
+                    return Err(Error::OutOfMemory);
+                }
+            }
+            return Err(Error::WouldBlock);
+        }
+
+        let rx_ptr: u16 = sn_rx_rd.wrapping_add(UdpHeader::LEN);
+        let tx_ptr: u16 = self.sn_tx_wr(dst_sn)?;
+        let mut chunk: [u8; CHUNK_LEN as usize] = [0; CHUNK_LEN as usize];
+        let mut offset: u16 = 0;
+        while offset < header.len {
+            let chunk_len: u16 = min(CHUNK_LEN, header.len - offset);
+            let chunk: &mut [u8] = &mut chunk[..chunk_len.into()];
+            self.sn_rx_buf(src_sn, rx_ptr.wrapping_add(offset), chunk)?;
+            self.set_sn_tx_buf(dst_sn, tx_ptr.wrapping_add(offset), chunk)?;
+            offset += chunk_len;
         }
-        Ok(data_len)
+
+        self.set_sn_dest(dst_sn, addr)?;
+        self.set_sn_tx_wr(dst_sn, tx_ptr.wrapping_add(header.len))?;
+        self.set_sn_cr(dst_sn, SocketCommand::Send)?;
+
+        self.set_sn_rx_rd(src_sn, rx_ptr.wrapping_add(header.len))?;
+        self.set_sn_cr(src_sn, SocketCommand::Recv)?;
+
+        Ok(header.len)
+    }
+
+    /// Broadcasts a Wake-on-LAN magic packet for the device with the hardware
+    /// address `mac`.
+    ///
+    /// The magic packet is 6 bytes of `0xFF` followed by 16 repetitions of
+    /// `mac`, sent to 255.255.255.255 on the discard port, 9.
+    /// The socket must be bound, this overwrites the socket destination.
+    ///
+    /// This is otherwise identical to [`udp_send_all`].
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    /// * [`Error::WouldBlock`]
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket.
+    /// * (debug) `mac` must not be [`Eui48Addr::UNSPECIFIED`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     block,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     net::Eui48Addr,
+    ///     Udp,
+    /// };
+    ///
+    /// const NAS: Eui48Addr = Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC);
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    /// block!(w5500.udp_send_wol(Sn0, &NAS))?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    ///
+    /// [`udp_send_all`]: Udp::udp_send_all
+    fn udp_send_wol(&mut self, sn: Sn, mac: &Eui48Addr) -> Result<(), Error<Self::Error>> {
+        const WOL_DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::BROADCAST, 9);
+
+        debug_assert_ne!(mac, &Eui48Addr::UNSPECIFIED, "MAC address is all zeros");
+
+        let mut packet: [u8; 102] = [0xFF; 102];
+        packet[6..]
+            .chunks_exact_mut(6)
+            .for_each(|chunk| chunk.copy_from_slice(&mac.octets));
+
+        self.set_sn_dest(sn, &WOL_DEST)?;
+        self.udp_send_all(sn, &packet)
     }
 
     /// Create a UDP reader.
@@ -754,7 +2913,8 @@ pub trait Udp: Registers {
     /// This returns a [`UdpReader`] structure, which contains functions to
     /// stream data from the W5500 socket buffers incrementally.
     ///
-    /// This will return [`Error::WouldBlock`] if there is no data to read.
+    /// This will return [`Error::WouldBlock`] if there is no data to read,
+    /// or if the W5500 has not yet buffered the entire datagram.
     ///
     /// # Errors
     ///
@@ -762,6 +2922,7 @@ pub trait Udp: Registers {
     ///
     /// * [`Error::Other`]
     /// * [`Error::WouldBlock`]
+    /// * [`Error::InvalidState`], only with the `strict` feature
     ///
     /// # Example
     ///
@@ -770,9 +2931,9 @@ pub trait Udp: Registers {
     where
         Self: Sized,
     {
-        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+        udp_status(self, sn)?;
 
-        let rsr: u16 = match self.sn_rx_rsr(sn)?.checked_sub(UdpHeader::LEN) {
+        let rsr: u16 = match sn_rx_rsr_stable(self, sn)?.checked_sub(UdpHeader::LEN) {
             Some(rsr) => rsr,
             // nothing to recieve
             None => return Err(Error::WouldBlock),
@@ -783,9 +2944,10 @@ pub trait Udp: Registers {
         self.sn_rx_buf(sn, sn_rx_rd, &mut header)?;
         let header: UdpHeader = UdpHeader::deser(header);
 
-        // limit to the length of the first datagram if we have more than a
-        // single datagram enqueued
-        let rsr_or_datagram_len: u16 = min(header.len, rsr);
+        // not all data as indicated by the header has been buffered
+        if rsr < header.len {
+            return Err(Error::WouldBlock);
+        }
 
         let head_ptr: u16 = sn_rx_rd.wrapping_add(UdpHeader::LEN);
 
@@ -794,13 +2956,146 @@ pub trait Udp: Registers {
                 w5500: self,
                 sn,
                 head_ptr,
-                tail_ptr: head_ptr.wrapping_add(rsr_or_datagram_len),
+                // limit to the length of the first datagram if we have more
+                // than a single datagram enqueued
+                tail_ptr: head_ptr.wrapping_add(header.len),
                 ptr: head_ptr,
             },
             header,
         })
     }
 
+    /// Create a UDP reader, and pass it to `f`.
+    ///
+    /// When `f` returns `Ok` the datagram is removed from the queue, the
+    /// same as calling [`done`](Read::done).
+    ///
+    /// When `f` returns an error:
+    ///
+    /// * If `discard_on_error` is `true` the datagram is removed from the
+    ///   queue. This is useful for protocols that drop malformed datagrams.
+    /// * If `discard_on_error` is `false` the datagram is kept, and the next
+    ///   reader starts at the beginning of the same datagram.
+    ///
+    /// If `f` panics the datagram is kept, see
+    /// [early returns](UdpReader#early-returns).
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`]
+    /// * Any error returned by `f`
+    /// * [`Error::InvalidState`], only with the `strict` feature
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut w5500 = w5500_ll::eh1::vdm::W5500::new(embedded_hal_mock::spi::Mock::new(&[]));
+    /// use w5500_hl::{
+    ///     io::Read,
+    ///     ll::{Registers, Sn::Sn0},
+    ///     Error, Udp,
+    /// };
+    ///
+    /// w5500.udp_bind(Sn0, 8080)?;
+    ///
+    /// // datagrams that are too short or have the wrong version are dropped
+    /// let msg_id: u16 = w5500.udp_reader_with(Sn0, true, |reader| {
+    ///     let header: [u8; 4] = reader.read_array()?;
+    ///     if header[0] != 0x01 {
+    ///         return Err(Error::NotFound);
+    ///     }
+    ///     Ok(u16::from_be_bytes([header[2], header[3]]))
+    /// })?;
+    /// # Ok::<(), w5500_hl::Error<_>>(())
+    /// ```
+    fn udp_reader_with<T, F>(
+        &mut self,
+        sn: Sn,
+        discard_on_error: bool,
+        f: F,
+    ) -> Result<T, Error<Self::Error>>
+    where
+        Self: Sized,
+        F: FnOnce(&mut UdpReader<Self>) -> Result<T, Error<Self::Error>>,
+    {
+        let mut reader: UdpReader<Self> = self.udp_reader(sn)?;
+        match f(&mut reader) {
+            Ok(ret) => {
+                reader.done()?;
+                Ok(ret)
+            }
+            Err(e) => {
+                if discard_on_error {
+                    reader.done()?;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Create an iterator over the datagrams queued in the socket buffer.
+    ///
+    /// This returns a [`UdpDatagrams`] structure, which returns the header of
+    /// each completely buffered datagram, and allows skipping datagrams
+    /// without reading their payload.
+    ///
+    /// There is no [`std::net`](https://doc.rust-lang.org/std/net) equivalent
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) The socket must be opened as a UDP socket. Use
+    ///   [`udp_datagrams_checked`](Udp::udp_datagrams_checked) to check the
+    ///   socket status in release builds.
+    ///
+    /// # Example
+    ///
+    /// See [`UdpDatagrams`].
+    fn udp_datagrams(&mut self, sn: Sn) -> Result<UdpDatagrams<Self>, Self::Error>
+    where
+        Self: Sized,
+    {
+        debug_assert_eq!(self.sn_sr(sn)?, Ok(SocketStatus::Udp));
+
+        let remain: u16 = sn_rx_rsr_stable(self, sn)?;
+        let ptr: u16 = self.sn_rx_rd(sn)?;
+
+        Ok(UdpDatagrams {
+            w5500: self,
+            sn,
+            ptr,
+            remain,
+            current: None,
+            finished: false,
+        })
+    }
+
+    /// Checked version of [`udp_datagrams`](Udp::udp_datagrams).
+    ///
+    /// This is identical to [`udp_datagrams`](Udp::udp_datagrams), except the
+    /// socket status is always checked, not only with debug assertions.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::InvalidState`]
+    ///
+    /// # Example
+    ///
+    /// See [`UdpDatagrams`].
+    fn udp_datagrams_checked(&mut self, sn: Sn) -> Result<UdpDatagrams<Self>, Error<Self::Error>>
+    where
+        Self: Sized,
+    {
+        udp_status_checked(self, sn)?;
+        Ok(self.udp_datagrams(sn)?)
+    }
+
     /// Create a UDP writer.
     ///
     /// This returns a [`UdpWriter`] structure, which contains functions to
@@ -813,14 +3108,16 @@ pub trait Udp: Registers {
     where
         Self: Sized,
     {
-        let tx_ptrs: TxPtrs = self.sn_tx_ptrs(sn)?;
+        let fsr: u16 = sn_tx_fsr_stable(self, sn)?;
+        let wr: u16 = self.sn_tx_wr(sn)?;
 
         Ok(UdpWriter {
             w5500: self,
             sn,
-            head_ptr: tx_ptrs.wr,
-            tail_ptr: tx_ptrs.wr.wrapping_add(tx_ptrs.fsr),
-            ptr: tx_ptrs.wr,
+            head_ptr: wr,
+            tail_ptr: wr.wrapping_add(fsr),
+            ptr: wr,
+            end_ptr: wr,
         })
     }
 }
@@ -0,0 +1,590 @@
+#![cfg(feature = "async")]
+#![feature(async_fn_in_trait)]
+#![allow(incomplete_features)]
+
+use std::{cell::Cell, convert::Infallible, rc::Rc};
+use w5500_hl::aio::Udp;
+use w5500_ll::{
+    aio::Registers,
+    net::{Ipv4Addr, SocketAddrV4},
+    Sn, SocketCommand, SocketInterrupt, SocketInterruptMask, SocketMode, SocketStatus,
+};
+
+const TEST_SOCKET: Sn = Sn::Sn3;
+const ORIGIN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 5000);
+
+/// Only the first `visible` bytes of `rx` have been received by the socket.
+struct MockRegisters {
+    rx: Vec<u8>,
+    visible: Rc<Cell<usize>>,
+    rx_rd: u16,
+    sn_cr: Vec<SocketCommand>,
+    sn_ir_clears: usize,
+    sn_imr: Option<SocketInterruptMask>,
+    simr: u8,
+    sn_sr: SocketStatus,
+}
+
+impl MockRegisters {
+    fn new(rx: Vec<u8>, visible: Rc<Cell<usize>>) -> Self {
+        Self {
+            rx,
+            visible,
+            rx_rd: 0,
+            sn_cr: Vec::new(),
+            sn_ir_clears: 0,
+            sn_imr: None,
+            simr: 0,
+            sn_sr: SocketStatus::Udp,
+        }
+    }
+}
+
+impl Registers for MockRegisters {
+    type Error = Infallible;
+
+    async fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(Ok(self.sn_sr))
+    }
+
+    async fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        match cmd {
+            SocketCommand::Close => self.sn_sr = SocketStatus::Closed,
+            SocketCommand::Open => self.sn_sr = SocketStatus::Udp,
+            _ => (),
+        }
+        self.sn_cr.push(cmd);
+        Ok(())
+    }
+
+    async fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        assert_eq!(port, 8080);
+        Ok(())
+    }
+
+    async fn set_sn_mr(&mut self, socket: Sn, _mode: SocketMode) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(())
+    }
+
+    async fn set_sn_imr(
+        &mut self,
+        socket: Sn,
+        mask: SocketInterruptMask,
+    ) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        self.sn_imr = Some(mask);
+        Ok(())
+    }
+
+    async fn simr(&mut self) -> Result<u8, Self::Error> {
+        Ok(self.simr)
+    }
+
+    async fn set_simr(&mut self, simr: u8) -> Result<(), Self::Error> {
+        self.simr = simr;
+        Ok(())
+    }
+
+    async fn set_sn_ir(&mut self, socket: Sn, sn_ir: u8) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        assert_eq!(sn_ir, SocketInterrupt::RECV_MASK);
+        self.sn_ir_clears += 1;
+        Ok(())
+    }
+
+    async fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        let visible: usize = self.visible.get().min(self.rx.len());
+        Ok((visible - usize::from(self.rx_rd)).try_into().unwrap())
+    }
+
+    async fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(self.rx_rd)
+    }
+
+    async fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        self.rx_rd = ptr;
+        Ok(())
+    }
+
+    async fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        let start: usize = ptr.into();
+        buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+        Ok(())
+    }
+
+    async fn read(
+        &mut self,
+        _address: u16,
+        _block: u8,
+        _data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+/// Interrupt pin that makes `step` more bytes visible on every falling edge.
+struct MockIrq {
+    visible: Rc<Cell<usize>>,
+    step: usize,
+    waits: usize,
+}
+
+impl embedded_hal::digital::ErrorType for MockIrq {
+    type Error = Infallible;
+}
+
+impl eha0a::digital::Wait for MockIrq {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.waits += 1;
+        self.visible.set(self.visible.get() + self.step);
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+/// A datagram from `ORIGIN` with the given payload, as stored by the W5500.
+fn datagram(payload: &[u8]) -> Vec<u8> {
+    let mut rx: Vec<u8> = ORIGIN.to_bytes().to_vec();
+    rx.extend_from_slice(&u16::try_from(payload.len()).unwrap().to_be_bytes());
+    rx.extend_from_slice(payload);
+    rx
+}
+
+fn setup(step: usize) -> (MockRegisters, MockIrq) {
+    let visible: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    (
+        MockRegisters::new(datagram(&[1, 2, 3, 4]), visible.clone()),
+        MockIrq {
+            visible,
+            step,
+            waits: 0,
+        },
+    )
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn bind_unmasks_recv() {
+    let (mut mock, _) = setup(0);
+    mock.simr = Sn::Sn0.bitmask();
+    mock.udp_bind(TEST_SOCKET, 8080).await.unwrap();
+    assert_eq!(
+        mock.sn_imr,
+        Some(SocketInterruptMask::ALL_MASKED.unmask_recv())
+    );
+    assert_eq!(mock.simr, Sn::Sn0.bitmask() | TEST_SOCKET.bitmask());
+    assert_eq!(mock.sn_cr, [SocketCommand::Close, SocketCommand::Open]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn recv_from_waits_for_interrupt() {
+    let (mut mock, mut irq) = setup(usize::MAX / 2);
+    let mut buf: [u8; 8] = [0; 8];
+    let (len, origin) = mock
+        .udp_recv_from(&mut irq, TEST_SOCKET, &mut buf)
+        .await
+        .unwrap();
+    assert_eq!(len, 4);
+    assert_eq!(origin, ORIGIN);
+    assert_eq!(buf[..4], [1, 2, 3, 4]);
+    assert_eq!(irq.waits, 1);
+    assert_eq!(mock.sn_ir_clears, 2);
+    assert_eq!(mock.rx_rd, 12);
+    assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn recv_from_waits_for_whole_datagram() {
+    // header and half of the payload arrive after the first interrupt
+    let (mut mock, mut irq) = setup(10);
+    let mut buf: [u8; 8] = [0; 8];
+    let (len, _) = mock
+        .udp_recv_from(&mut irq, TEST_SOCKET, &mut buf)
+        .await
+        .unwrap();
+    assert_eq!(len, 4);
+    assert_eq!(irq.waits, 2);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn peek_from_does_not_consume() {
+    let (mut mock, mut irq) = setup(usize::MAX / 2);
+    let mut buf: [u8; 2] = [0; 2];
+    let (len, header) = mock
+        .udp_peek_from(&mut irq, TEST_SOCKET, &mut buf)
+        .await
+        .unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(header.origin, ORIGIN);
+    assert_eq!(header.len, 4);
+    assert_eq!(buf, [1, 2]);
+    assert_eq!(mock.rx_rd, 0);
+    assert!(mock.sn_cr.is_empty());
+}
+
+/// Tests the async TCP reader and writer
+mod tcp {
+    use super::MockIrq;
+    use std::{cell::Cell, convert::Infallible, rc::Rc};
+    use w5500_hl::{aio::Tcp, Error};
+    use w5500_ll::{aio::Registers, Sn, SocketCommand, SocketInterrupt, SocketStatus};
+
+    const TEST_SOCKET: Sn = Sn::Sn5;
+
+    /// Only the first `events` bytes of `rx` have been received by the
+    /// socket, and sends complete after an event.
+    struct MockRegisters {
+        rx: Vec<u8>,
+        events: Rc<Cell<usize>>,
+        rx_rd: u16,
+        tx: Vec<u8>,
+        tx_wr: u16,
+        sn_cr: Vec<SocketCommand>,
+        sn_sr: SocketStatus,
+        sent_at: Option<usize>,
+        timeout: bool,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        async fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(self.sn_sr))
+        }
+
+        async fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            if cmd == SocketCommand::Send {
+                self.sent_at = Some(self.events.get());
+            }
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        async fn sn_ir(&mut self, socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let completed: bool =
+                matches!(self.sent_at, Some(sent_at) if self.events.get() > sent_at);
+            Ok(match (completed, self.timeout) {
+                (true, false) => SocketInterrupt::SENDOK_MASK,
+                (true, true) => SocketInterrupt::TIMEOUT_MASK,
+                (false, _) => 0,
+            }
+            .into())
+        }
+
+        async fn set_sn_ir(&mut self, socket: Sn, _sn_ir: u8) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(())
+        }
+
+        async fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let visible: usize = self.events.get().min(self.rx.len());
+            Ok((visible - usize::from(self.rx_rd)).try_into().unwrap())
+        }
+
+        async fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rx_rd)
+        }
+
+        async fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.rx_rd = ptr;
+            Ok(())
+        }
+
+        async fn sn_rx_buf(
+            &mut self,
+            socket: Sn,
+            ptr: u16,
+            buf: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.into();
+            buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+            Ok(())
+        }
+
+        async fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(4)
+        }
+
+        async fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(0)
+        }
+
+        async fn set_sn_tx_buf(
+            &mut self,
+            socket: Sn,
+            ptr: u16,
+            buf: &[u8],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(usize::from(ptr), self.tx.len());
+            self.tx.extend_from_slice(buf);
+            Ok(())
+        }
+
+        async fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.tx_wr = ptr;
+            Ok(())
+        }
+
+        async fn read(
+            &mut self,
+            _address: u16,
+            _block: u8,
+            _data: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn write(
+            &mut self,
+            _address: u16,
+            _block: u8,
+            _data: &[u8],
+        ) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn setup(step: usize) -> (MockRegisters, MockIrq) {
+        let events: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+        (
+            MockRegisters {
+                rx: vec![1, 2, 3, 4, 5],
+                events: events.clone(),
+                rx_rd: 0,
+                tx: Vec::new(),
+                tx_wr: 0,
+                sn_cr: Vec::new(),
+                sn_sr: SocketStatus::Established,
+                sent_at: None,
+                timeout: false,
+            },
+            MockIrq {
+                visible: events,
+                step,
+                waits: 0,
+            },
+        )
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn reader_waits_for_data() {
+        let (mut mock, mut irq) = setup(3);
+        let mut reader = mock.tcp_reader(&mut irq, TEST_SOCKET).await.unwrap();
+        assert_eq!(reader.stream_len(), 3);
+        let mut buf: [u8; 2] = [0; 2];
+        assert_eq!(reader.read(&mut buf).await, Ok(2));
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(reader.read_exact(&mut buf).await, Err(Error::UnexpectedEof));
+        assert_eq!(reader.read(&mut buf).await, Ok(1));
+        assert_eq!(buf[..1], [3]);
+        assert_eq!(reader.read(&mut buf).await, Ok(0));
+        reader.done().await.unwrap();
+        assert_eq!(irq.waits, 1);
+        assert_eq!(mock.rx_rd, 3);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn reader_empty_on_close() {
+        let (mut mock, mut irq) = setup(0);
+        mock.sn_sr = SocketStatus::CloseWait;
+        let reader = mock.tcp_reader(&mut irq, TEST_SOCKET).await.unwrap();
+        assert_eq!(reader.remain(), 0);
+        assert_eq!(irq.waits, 0);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn writer_waits_for_sendok() {
+        let (mut mock, mut irq) = setup(1);
+        let mut writer = mock.tcp_writer(TEST_SOCKET).await.unwrap();
+        assert_eq!(writer.write(&[1, 2, 3]).await, Ok(3));
+        assert_eq!(writer.write_all(&[4, 5]).await, Err(Error::OutOfMemory));
+        assert_eq!(writer.remain(), 1);
+        writer.send(&mut irq).await.unwrap();
+        assert_eq!(irq.waits, 1);
+        assert_eq!(mock.tx, [1, 2, 3]);
+        assert_eq!(mock.tx_wr, 3);
+        assert_eq!(mock.sn_cr, [SocketCommand::Send]);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn writer_send_timeout() {
+        let (mut mock, mut irq) = setup(1);
+        mock.timeout = true;
+        let mut writer = mock.tcp_writer(TEST_SOCKET).await.unwrap();
+        writer.write_all(&[1]).await.unwrap();
+        assert_eq!(writer.send(&mut irq).await, Err(Error::SendTimeout));
+    }
+}
+
+/// Tests the InterruptContext structure
+mod interrupt_context {
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll, Wake, Waker},
+    };
+    use w5500_hl::aio::InterruptContext;
+    use w5500_ll::{Registers, Sn, SocketInterrupt, SocketStatus};
+
+    const TEST_SOCKET: Sn = Sn::Sn6;
+
+    struct MockRegisters {
+        rx: Vec<u8>,
+        rsr: u16,
+        sn_ir_clears: usize,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = std::convert::Infallible;
+
+        fn sir(&mut self) -> Result<u8, Self::Error> {
+            Ok(TEST_SOCKET.bitmask())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Established))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(
+                sn_ir.into(),
+                SocketInterrupt::RECV_MASK | SocketInterrupt::DISCON_MASK
+            );
+            self.sn_ir_clears += 1;
+            Ok(())
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rsr)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(0)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, _ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.into();
+            buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+            Ok(())
+        }
+
+        fn set_sn_cr(
+            &mut self,
+            socket: Sn,
+            _cmd: w5500_ll::SocketCommand,
+        ) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn tcp_read_woken_by_interrupt() {
+        static CONTEXT: InterruptContext = InterruptContext::new();
+
+        let wakes: Arc<CountingWaker> = Arc::new(CountingWaker::default());
+        let waker: Waker = wakes.clone().into();
+        let mut cx: Context = Context::from_waker(&waker);
+
+        let mut irq_w5500 = MockRegisters {
+            rx: Vec::new(),
+            rsr: 0,
+            sn_ir_clears: 0,
+        };
+        let mut w5500 = MockRegisters {
+            rx: vec![1, 2, 3],
+            rsr: 0,
+            sn_ir_clears: 0,
+        };
+        let mut buf: [u8; 4] = [0; 4];
+
+        {
+            let mut fut = pin!(CONTEXT.tcp_read(&mut w5500, TEST_SOCKET, &mut buf));
+            assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+            assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+
+            CONTEXT.on_interrupt(&mut irq_w5500).unwrap();
+            assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+
+            // spurious wake without data
+            assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        }
+
+        w5500.rsr = 3;
+        {
+            let mut fut = pin!(CONTEXT.tcp_read(&mut w5500, TEST_SOCKET, &mut buf));
+            assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(3)));
+        }
+        assert_eq!(buf[..3], [1, 2, 3]);
+        assert_eq!(w5500.sn_ir_clears, 3);
+    }
+}
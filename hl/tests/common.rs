@@ -38,6 +38,108 @@ mod local_addr {
     }
 }
 
+mod mac {
+    use super::*;
+    use w5500_hl::net::Eui48Addr;
+
+    struct MockRegisters {
+        shar: Eui48Addr,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn shar(&mut self) -> Result<Eui48Addr, Self::Error> {
+            Ok(self.shar)
+        }
+
+        fn set_shar(&mut self, shar: &Eui48Addr) -> Result<(), Self::Error> {
+            self.shar = *shar;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn set_mac() {
+        const MAC: Eui48Addr = Eui48Addr::new(0x02, 0x00, 0x00, 0x12, 0x34, 0x56);
+        let mut mock = MockRegisters {
+            shar: Eui48Addr::UNSPECIFIED,
+        };
+        assert_eq!(mock.set_mac(&MAC), Ok(()));
+        assert_eq!(mock.mac(), Ok(MAC));
+    }
+
+    #[test]
+    #[should_panic = "MAC address is multicast"]
+    fn multicast() {
+        let mut mock = MockRegisters {
+            shar: Eui48Addr::UNSPECIFIED,
+        };
+        mock.set_mac(&Eui48Addr::new(0x01, 0x00, 0x5E, 0x00, 0x00, 0x01))
+            .ok();
+    }
+}
+
+mod net_config {
+    use super::*;
+    use w5500_hl::{
+        ll::{Reg, COMMON_BLOCK_OFFSET},
+        NetConfig,
+    };
+
+    /// Common register block.
+    struct MockRegisters {
+        regs: [u8; 0x40],
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(block, COMMON_BLOCK_OFFSET);
+            let start: usize = address.into();
+            data.copy_from_slice(&self.regs[start..start + data.len()]);
+            Ok(())
+        }
+
+        fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(block, COMMON_BLOCK_OFFSET);
+            let start: usize = address.into();
+            self.regs[start..start + data.len()].copy_from_slice(data);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        const CONFIG: NetConfig = NetConfig {
+            ip: Ipv4Addr::new(192, 168, 0, 50),
+            subnet: Ipv4Addr::new(255, 255, 255, 0),
+            gateway: Ipv4Addr::new(192, 168, 0, 1),
+        };
+        const SHAR: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC];
+        let mut mock = MockRegisters { regs: [0; 0x40] };
+        let shar: usize = Reg::SHAR0.addr().into();
+        mock.regs[shar..shar + 6].copy_from_slice(&SHAR);
+
+        assert_eq!(mock.set_net_config(&CONFIG), Ok(()));
+        assert_eq!(mock.net_config(), Ok(CONFIG));
+        assert_eq!(mock.gar(), Ok(CONFIG.gateway));
+        assert_eq!(mock.subr(), Ok(CONFIG.subnet));
+        assert_eq!(mock.sipr(), Ok(CONFIG.ip));
+        // the hardware address is not modified
+        assert_eq!(mock.regs[shar..shar + 6], SHAR);
+    }
+}
+
 mod close {
     use super::*;
 
@@ -68,6 +170,50 @@ mod close {
     }
 }
 
+mod socket_status {
+    use w5500_hl::ll::SocketStatus;
+
+    use super::*;
+
+    const SOCKET: Sn = Sn::Sn2;
+
+    struct MockRegisters {
+        states: Vec<Result<SocketStatus, u8>>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, SOCKET);
+            Ok(self.states.pop().expect("Unexpected call to sn_sr"))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn socket_status() {
+        let mut mock = MockRegisters {
+            states: vec![
+                Err(0x1F),
+                Ok(SocketStatus::CloseWait),
+                Ok(SocketStatus::SynSent),
+            ],
+        };
+        assert_eq!(mock.socket_status(SOCKET), Ok(Ok(SocketStatus::SynSent)));
+        assert_eq!(mock.socket_status(SOCKET), Ok(Ok(SocketStatus::CloseWait)));
+        assert_eq!(mock.socket_status(SOCKET), Ok(Err(0x1F)));
+        assert!(mock.states.is_empty())
+    }
+}
+
 mod is_state_closed {
     use w5500_hl::ll::SocketStatus;
 
@@ -249,3 +395,1332 @@ mod is_state_udp {
         assert!(mock.states.is_empty())
     }
 }
+
+mod take_common_interrupts {
+    use super::*;
+    use w5500_hl::ll::Interrupt;
+
+    struct MockRegisters {
+        ir: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn ir(&mut self) -> Result<Interrupt, Self::Error> {
+            Ok(Interrupt::from(self.ir))
+        }
+
+        fn set_ir(&mut self, interrupt: Interrupt) -> Result<(), Self::Error> {
+            self.ir &= !u8::from(interrupt);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn take_common_interrupts() {
+        let mut mock = MockRegisters {
+            ir: Interrupt::CONFLICT_MASK | Interrupt::MP_MASK,
+        };
+        let ir: Interrupt = mock.take_common_interrupts().unwrap();
+        assert!(ir.conflict());
+        assert!(!ir.unreach());
+        assert!(!ir.pppoe());
+        assert!(ir.mp());
+        assert_eq!(mock.ir, 0);
+        assert_eq!(mock.take_common_interrupts(), Ok(Interrupt::default()));
+    }
+}
+
+mod recv_ready {
+    use super::*;
+    use w5500_hl::ll::{Reg, SocketStatus, COMMON_BLOCK_OFFSET};
+
+    struct MockRegisters {
+        sir: u8,
+        simr: u8,
+        sn_sr: [SocketStatus; 8],
+        sn_rx_rsr: [u16; 8],
+        /// Payload length in the UDP header at the RX read pointer.
+        udp_len: u16,
+        rsr_reads: u8,
+    }
+
+    impl MockRegisters {
+        fn new() -> Self {
+            Self {
+                sir: 0,
+                simr: 0,
+                sn_sr: [SocketStatus::Established; 8],
+                sn_rx_rsr: [0; 8],
+                udp_len: 0,
+                rsr_reads: 0,
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            self.rsr_reads |= socket.bitmask();
+            Ok(self.sn_rx_rsr[usize::from(socket)])
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.sn_sr[usize::from(socket)]))
+        }
+
+        fn sn_rx_rd(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0xFFFA)
+        }
+
+        fn sn_rx_buf(&mut self, _socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(ptr, 0);
+            buf.copy_from_slice(&self.udp_len.to_be_bytes());
+            Ok(())
+        }
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(address, Reg::SIR.addr());
+            assert_eq!(block, COMMON_BLOCK_OFFSET);
+            data.copy_from_slice(&[self.sir, self.simr]);
+            Ok(())
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn recv_ready() {
+        let mut mock = MockRegisters::new();
+        assert_eq!(mock.recv_ready(), Ok(None));
+        assert_eq!(mock.rsr_reads, 0xFF);
+
+        mock.sn_rx_rsr[6] = 12;
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn6)));
+
+        mock.sn_rx_rsr[2] = 1;
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn2)));
+
+        mock.sn_rx_rsr[2] = 0;
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn6)));
+    }
+
+    #[test]
+    fn interrupt_mask() {
+        let mut mock = MockRegisters::new();
+        mock.simr = Sn::Sn1.bitmask() | Sn::Sn4.bitmask();
+        mock.sn_rx_rsr = [5; 8];
+        mock.sn_rx_rsr[0] = 0;
+
+        // sockets with interrupts enabled are skipped without an interrupt
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn2)));
+        assert_eq!(mock.rsr_reads & mock.simr, 0);
+
+        mock.sir = Sn::Sn1.bitmask();
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn1)));
+
+        mock.simr = 0xFF;
+        mock.sir = Sn::Sn4.bitmask();
+        mock.rsr_reads = 0;
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn4)));
+        assert_eq!(mock.rsr_reads, Sn::Sn4.bitmask());
+
+        mock.sir = 0;
+        assert_eq!(mock.recv_ready(), Ok(None));
+    }
+
+    #[test]
+    fn udp_partial_datagram() {
+        let mut mock = MockRegisters::new();
+        mock.sn_sr[3] = SocketStatus::Udp;
+        mock.udp_len = 10;
+
+        // only part of the header
+        mock.sn_rx_rsr[3] = 6;
+        assert_eq!(mock.recv_ready(), Ok(None));
+
+        // only part of the payload
+        mock.sn_rx_rsr[3] = 8 + 9;
+        assert_eq!(mock.recv_ready(), Ok(None));
+
+        mock.sn_rx_rsr[3] = 8 + 10;
+        assert_eq!(mock.recv_ready(), Ok(Some(Sn::Sn3)));
+    }
+}
+
+mod maximize_socket_rx {
+    use super::*;
+    use w5500_hl::ll::BufferSize;
+
+    struct MockRegisters {
+        rxbuf_size: [BufferSize; 8],
+        /// Value read back from the RX buffer size register, if not the
+        /// written value.
+        readback: Option<u8>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_rxbuf_size(&mut self, socket: Sn, size: BufferSize) -> Result<(), Self::Error> {
+            self.rxbuf_size[usize::from(socket)] = size;
+            let total: usize = self.rxbuf_size.iter().map(BufferSize::size_in_bytes).sum();
+            assert!(total <= 16 * 1024, "RX buffer total exceeds 16 KiB");
+            Ok(())
+        }
+
+        fn sn_rxbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            Ok(match self.readback {
+                Some(value) => BufferSize::try_from(value),
+                None => Ok(self.rxbuf_size[usize::from(socket)]),
+            })
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn maximize_socket_rx() {
+        for socket in SOCKETS.iter() {
+            let mut mock = MockRegisters {
+                rxbuf_size: [BufferSize::KB2; 8],
+                readback: None,
+            };
+            assert_eq!(mock.maximize_socket_rx(*socket), Ok(16 * 1024));
+            for other in SOCKETS.iter() {
+                let expected = if other == socket {
+                    BufferSize::KB16
+                } else {
+                    BufferSize::KB0
+                };
+                assert_eq!(mock.rxbuf_size[usize::from(*other)], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn readback() {
+        let mut mock = MockRegisters {
+            rxbuf_size: [BufferSize::KB2; 8],
+            readback: Some(BufferSize::KB8.into()),
+        };
+        assert_eq!(mock.maximize_socket_rx(Sn::Sn1), Ok(8 * 1024));
+
+        mock.readback = Some(0x03);
+        assert_eq!(mock.maximize_socket_rx(Sn::Sn1), Ok(0));
+    }
+}
+
+mod set_socket_buffer_sizes {
+    use super::*;
+    use w5500_hl::{ll::BufferSize, Error};
+
+    struct MockRegisters {
+        rxbuf_size: [BufferSize; 8],
+        txbuf_size: [BufferSize; 8],
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_rxbuf_size(&mut self, socket: Sn, size: BufferSize) -> Result<(), Self::Error> {
+            self.rxbuf_size[usize::from(socket)] = size;
+            let total: usize = self.rxbuf_size.iter().map(BufferSize::size_in_bytes).sum();
+            assert!(total <= 16 * 1024, "RX buffer total exceeds 16 KiB");
+            Ok(())
+        }
+
+        fn set_sn_txbuf_size(&mut self, socket: Sn, size: BufferSize) -> Result<(), Self::Error> {
+            self.txbuf_size[usize::from(socket)] = size;
+            let total: usize = self.txbuf_size.iter().map(BufferSize::size_in_bytes).sum();
+            assert!(total <= 16 * 1024, "TX buffer total exceeds 16 KiB");
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn new_mock() -> MockRegisters {
+        MockRegisters {
+            rxbuf_size: [BufferSize::KB2; 8],
+            txbuf_size: [BufferSize::KB2; 8],
+        }
+    }
+
+    #[test]
+    fn set_socket_buffer_sizes() {
+        let mut mock = new_mock();
+        let mut rx: [BufferSize; 8] = [BufferSize::KB0; 8];
+        rx[7] = BufferSize::KB16;
+        let mut tx: [BufferSize; 8] = [BufferSize::KB1; 8];
+        tx[0] = BufferSize::KB8;
+        assert_eq!(mock.set_socket_buffer_sizes(&rx, &tx), Ok(()));
+        assert_eq!(mock.rxbuf_size, rx);
+        assert_eq!(mock.txbuf_size, tx);
+    }
+
+    #[test]
+    fn overallocation() {
+        let mut mock = new_mock();
+        let mut sizes: [BufferSize; 8] = [BufferSize::KB2; 8];
+        sizes[3] = BufferSize::KB4;
+        let defaults: [BufferSize; 8] = [BufferSize::KB2; 8];
+        assert_eq!(
+            mock.set_socket_buffer_sizes(&sizes, &defaults),
+            Err(Error::BufferOverallocation)
+        );
+        assert_eq!(
+            mock.set_socket_buffer_sizes(&defaults, &sizes),
+            Err(Error::BufferOverallocation)
+        );
+        assert_eq!(mock.rxbuf_size, defaults);
+        assert_eq!(mock.txbuf_size, defaults);
+    }
+
+    #[test]
+    fn reset_socket_buffers() {
+        let mut mock = new_mock();
+        let mut sizes: [BufferSize; 8] = [BufferSize::KB0; 8];
+        sizes[1] = BufferSize::KB16;
+        mock.set_socket_buffer_sizes(&sizes, &sizes).unwrap();
+        assert_eq!(mock.reset_socket_buffers(), Ok(()));
+        assert_eq!(mock.rxbuf_size, [BufferSize::KB2; 8]);
+        assert_eq!(mock.txbuf_size, [BufferSize::KB2; 8]);
+    }
+}
+
+mod utilization {
+    use super::*;
+    use w5500_hl::ll::BufferSize;
+
+    struct MockRegisters {
+        size: Result<BufferSize, u8>,
+        tx_fsr: u16,
+        rx_rsr: u16,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_txbuf_size(&mut self, _socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            Ok(self.size)
+        }
+
+        fn sn_rxbuf_size(&mut self, _socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            Ok(self.size)
+        }
+
+        fn sn_tx_fsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.tx_fsr)
+        }
+
+        fn sn_rx_rsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.rx_rsr)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn tx_utilization() {
+        let mut mock = MockRegisters {
+            size: Ok(BufferSize::KB2),
+            tx_fsr: 2048,
+            rx_rsr: 0,
+        };
+        assert_eq!(mock.tx_utilization(Sn::Sn0), Ok(0));
+        mock.tx_fsr = 1024;
+        assert_eq!(mock.tx_utilization(Sn::Sn0), Ok(50));
+        mock.tx_fsr = 0;
+        assert_eq!(mock.tx_utilization(Sn::Sn0), Ok(100));
+
+        mock.size = Ok(BufferSize::KB16);
+        mock.tx_fsr = 4096;
+        assert_eq!(mock.tx_utilization(Sn::Sn0), Ok(75));
+
+        mock.size = Ok(BufferSize::KB0);
+        mock.tx_fsr = 0;
+        assert_eq!(mock.tx_utilization(Sn::Sn0), Ok(100));
+    }
+
+    #[test]
+    fn rx_utilization() {
+        let mut mock = MockRegisters {
+            size: Ok(BufferSize::KB2),
+            tx_fsr: 0,
+            rx_rsr: 0,
+        };
+        assert_eq!(mock.rx_utilization(Sn::Sn0), Ok(0));
+        mock.rx_rsr = 512;
+        assert_eq!(mock.rx_utilization(Sn::Sn0), Ok(25));
+        mock.rx_rsr = 2048;
+        assert_eq!(mock.rx_utilization(Sn::Sn0), Ok(100));
+
+        mock.size = Ok(BufferSize::KB16);
+        mock.rx_rsr = 16 * 1024;
+        assert_eq!(mock.rx_utilization(Sn::Sn0), Ok(100));
+
+        mock.size = Err(3);
+        assert_eq!(mock.rx_utilization(Sn::Sn0), Ok(100));
+    }
+}
+
+mod poll_interrupts {
+    use super::*;
+    use w5500_hl::ll::SocketInterrupt;
+    use w5500_hl::SocketInterrupts;
+
+    struct MockRegisters {
+        sn_ir: [u8; 8],
+        sn_ir_reads: usize,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sir(&mut self) -> Result<u8, Self::Error> {
+            Ok(SOCKETS
+                .iter()
+                .filter(|sn| self.sn_ir[usize::from(**sn)] != 0)
+                .fold(0, |sir, sn| sir | sn.bitmask()))
+        }
+
+        fn sn_ir(&mut self, socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            let sn_ir: u8 = self.sn_ir[usize::from(socket)];
+            assert_ne!(sn_ir, 0, "Unexpected SN_IR read for {socket:?}");
+            self.sn_ir_reads += 1;
+            Ok(SocketInterrupt::from(sn_ir))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir[usize::from(socket)] &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn poll_interrupts() {
+        let mut sn_ir: [u8; 8] = [0; 8];
+        sn_ir[1] = SocketInterrupt::RECV_MASK;
+        sn_ir[6] = SocketInterrupt::DISCON_MASK | SocketInterrupt::TIMEOUT_MASK;
+        let mut mock = MockRegisters {
+            sn_ir,
+            sn_ir_reads: 0,
+        };
+
+        let interrupts: SocketInterrupts = mock.poll_interrupts().unwrap();
+        assert_eq!(mock.sn_ir_reads, 2);
+        assert!(!interrupts.is_empty());
+        assert_eq!(interrupts.get(Sn::Sn0), None);
+        assert!(interrupts.get(Sn::Sn1).unwrap().recv_raised());
+        let pending: Vec<Sn> = interrupts.iter().map(|(sn, _)| sn).collect();
+        assert_eq!(pending, vec![Sn::Sn1, Sn::Sn6]);
+        let sn6_ir: SocketInterrupt = interrupts.get(Sn::Sn6).unwrap();
+        assert!(sn6_ir.discon_raised());
+        assert!(sn6_ir.timeout_raised());
+        assert!(!sn6_ir.recv_raised());
+
+        mock.clear_interrupts(&interrupts).unwrap();
+        assert_eq!(mock.sn_ir, [0; 8]);
+
+        let interrupts: SocketInterrupts = mock.poll_interrupts().unwrap();
+        assert!(interrupts.is_empty());
+        assert_eq!(interrupts, SocketInterrupts::default());
+        assert_eq!(interrupts.iter().count(), 0);
+        assert_eq!(mock.sn_ir_reads, 2);
+    }
+
+    #[test]
+    fn clear_keeps_new_interrupts() {
+        let mut sn_ir: [u8; 8] = [0; 8];
+        sn_ir[0] = SocketInterrupt::RECV_MASK;
+        let mut mock = MockRegisters {
+            sn_ir,
+            sn_ir_reads: 0,
+        };
+
+        let interrupts: SocketInterrupts = mock.poll_interrupts().unwrap();
+        // raised after the poll
+        mock.sn_ir[0] |= SocketInterrupt::SENDOK_MASK;
+        mock.clear_interrupts(&interrupts).unwrap();
+        assert_eq!(mock.sn_ir[0], SocketInterrupt::SENDOK_MASK);
+    }
+}
+
+mod interrupt_mask {
+    use super::*;
+    use w5500_hl::ll::SocketInterruptMask;
+
+    struct MockRegisters {
+        simr: u8,
+        sn_imr: [u8; 8],
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn simr(&mut self) -> Result<u8, Self::Error> {
+            Ok(self.simr)
+        }
+
+        fn set_simr(&mut self, simr: u8) -> Result<(), Self::Error> {
+            self.simr = simr;
+            Ok(())
+        }
+
+        fn set_sn_imr(&mut self, socket: Sn, mask: SocketInterruptMask) -> Result<(), Self::Error> {
+            self.sn_imr[usize::from(socket)] = u8::from(mask);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn enable_socket_interrupts() {
+        let mut mock = MockRegisters {
+            simr: Sn::Sn2.bitmask(),
+            sn_imr: [SocketInterruptMask::RESET; 8],
+        };
+        const MASK: SocketInterruptMask = SocketInterruptMask::ALL_MASKED
+            .unmask_recv()
+            .unmask_discon();
+        mock.enable_socket_interrupts(Sn::Sn5, MASK).unwrap();
+        assert_eq!(mock.simr, Sn::Sn2.bitmask() | Sn::Sn5.bitmask());
+        assert_eq!(mock.sn_imr[5], u8::from(MASK));
+        assert_eq!(mock.sn_imr[2], SocketInterruptMask::RESET);
+    }
+
+    #[test]
+    fn set_interrupt_mask() {
+        let mut mock = MockRegisters {
+            simr: 0xFF,
+            sn_imr: [SocketInterruptMask::RESET; 8],
+        };
+        mock.set_interrupt_mask(&[Sn::Sn1, Sn::Sn3]).unwrap();
+        assert_eq!(mock.simr, 0b1010);
+        mock.set_interrupt_mask(&[]).unwrap();
+        assert_eq!(mock.simr, 0);
+        mock.set_interrupt_mask(&SOCKETS).unwrap();
+        assert_eq!(mock.simr, 0xFF);
+    }
+}
+
+mod retry {
+    use super::*;
+    use std::time::Duration;
+
+    struct MockRegisters {
+        rtr: u16,
+        rcr: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn rtr(&mut self) -> Result<u16, Self::Error> {
+            Ok(self.rtr)
+        }
+
+        fn set_rtr(&mut self, rtr: u16) -> Result<(), Self::Error> {
+            self.rtr = rtr;
+            Ok(())
+        }
+
+        fn rcr(&mut self) -> Result<u8, Self::Error> {
+            Ok(self.rcr)
+        }
+
+        fn set_rcr(&mut self, rcr: u8) -> Result<(), Self::Error> {
+            self.rcr = rcr;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn retry_time() {
+        let mut mock = MockRegisters { rtr: 2000, rcr: 8 };
+        assert_eq!(mock.retry_time(), Ok(Duration::from_millis(200)));
+
+        mock.set_retry_time(Duration::from_millis(500)).unwrap();
+        assert_eq!(mock.rtr, 5000);
+        assert_eq!(mock.retry_time(), Ok(Duration::from_millis(500)));
+
+        // rounding
+        mock.set_retry_time(Duration::from_micros(149)).unwrap();
+        assert_eq!(mock.rtr, 1);
+        mock.set_retry_time(Duration::from_micros(150)).unwrap();
+        assert_eq!(mock.rtr, 2);
+
+        // clamping
+        mock.set_retry_time(Duration::ZERO).unwrap();
+        assert_eq!(mock.rtr, 1);
+        mock.set_retry_time(Duration::from_micros(6_553_500))
+            .unwrap();
+        assert_eq!(mock.rtr, u16::MAX);
+        mock.set_retry_time(Duration::from_secs(7)).unwrap();
+        assert_eq!(mock.rtr, u16::MAX);
+        mock.set_retry_time(Duration::MAX).unwrap();
+        assert_eq!(mock.rtr, u16::MAX);
+        assert_eq!(mock.retry_time(), Ok(Duration::from_micros(6_553_500)));
+    }
+
+    #[test]
+    fn retry_count() {
+        let mut mock = MockRegisters { rtr: 2000, rcr: 8 };
+        assert_eq!(mock.retry_count(), Ok(8));
+        mock.set_retry_count(16).unwrap();
+        assert_eq!(mock.rcr, 16);
+        assert_eq!(mock.retry_count(), Ok(16));
+    }
+}
+
+mod ping_block {
+    use super::*;
+    use w5500_hl::ll::Mode;
+
+    struct MockRegisters {
+        mr: Mode,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn mr(&mut self) -> Result<Mode, Self::Error> {
+            Ok(self.mr)
+        }
+
+        fn set_mr(&mut self, mode: Mode) -> Result<(), Self::Error> {
+            self.mr = mode;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn set_ping_block() {
+        let mut mock = MockRegisters {
+            mr: Mode::DEFAULT.enable_farp(),
+        };
+        assert_eq!(mock.ping_block(), Ok(false));
+
+        mock.set_ping_block(true).unwrap();
+        assert_eq!(mock.mr, Mode::DEFAULT.enable_farp().enable_pb());
+        assert_eq!(mock.ping_block(), Ok(true));
+
+        mock.set_ping_block(false).unwrap();
+        assert_eq!(mock.mr, Mode::DEFAULT.enable_farp());
+        assert_eq!(mock.ping_block(), Ok(false));
+    }
+}
+
+mod force_arp {
+    use super::*;
+    use w5500_hl::ll::Mode;
+
+    struct MockRegisters {
+        mr: Mode,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn mr(&mut self) -> Result<Mode, Self::Error> {
+            Ok(self.mr)
+        }
+
+        fn set_mr(&mut self, mode: Mode) -> Result<(), Self::Error> {
+            self.mr = mode;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn set_force_arp() {
+        let mut mock = MockRegisters {
+            mr: Mode::DEFAULT.enable_pb(),
+        };
+        assert_eq!(mock.force_arp(), Ok(false));
+
+        mock.set_force_arp(true).unwrap();
+        assert_eq!(mock.mr, Mode::DEFAULT.enable_pb().enable_farp());
+        assert_eq!(mock.force_arp(), Ok(true));
+
+        mock.set_force_arp(false).unwrap();
+        assert_eq!(mock.mr, Mode::DEFAULT.enable_pb());
+        assert_eq!(mock.force_arp(), Ok(false));
+    }
+}
+
+mod abort_tx {
+    use super::*;
+
+    const SN: Sn = Sn::Sn2;
+
+    struct MockRegisters {
+        tx_rd: u16,
+        tx_wr: u16,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_tx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, SN);
+            Ok(self.tx_rd)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, SN);
+            Ok(self.tx_wr)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, SN);
+            self.tx_wr = ptr;
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, SN);
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn restores_tx_rd() {
+        // 0x20 bytes staged, wrapping around the end of the buffer
+        let mut mock = MockRegisters {
+            tx_rd: 0xFFF0,
+            tx_wr: 0x0010,
+            sn_cr: Vec::new(),
+        };
+        let saved_wr: u16 = mock.sn_tx_rd(SN).unwrap();
+        mock.abort_tx(SN, saved_wr).unwrap();
+        assert_eq!(mock.sn_tx_wr(SN), Ok(0xFFF0));
+        assert_eq!(mock.tx_wr, mock.tx_rd);
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+mod init_sockets {
+    use super::*;
+    use w5500_hl::ll::{SocketInterrupt, SocketStatus};
+
+    struct MockRegisters {
+        sn_cr: [Option<SocketCommand>; 8],
+        sn_sr_reads: [u8; 8],
+        sn_ir: [u8; 8],
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(cmd, SocketCommand::Close);
+            self.sn_cr[usize::from(socket)] = Some(cmd);
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            let idx: usize = usize::from(socket);
+            assert!(self.sn_cr[idx].is_some(), "{socket:?} was not closed");
+            self.sn_sr_reads[idx] += 1;
+            // every other socket takes an extra poll to close
+            if idx % 2 == 1 && self.sn_sr_reads[idx] == 1 {
+                Ok(Ok(SocketStatus::Closing))
+            } else {
+                Ok(Ok(SocketStatus::Closed))
+            }
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir[usize::from(socket)] &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn init_sockets() {
+        let mut mock = MockRegisters {
+            sn_cr: [None; 8],
+            sn_sr_reads: [0; 8],
+            sn_ir: [SocketInterrupt::RECV_MASK | SocketInterrupt::DISCON_MASK; 8],
+        };
+        mock.sn_ir[3] = 0x1F;
+        mock.init_sockets().unwrap();
+        assert_eq!(mock.sn_cr, [Some(SocketCommand::Close); 8]);
+        assert_eq!(mock.sn_sr_reads, [1, 2, 1, 2, 1, 2, 1, 2]);
+        assert_eq!(mock.sn_ir, [0; 8]);
+    }
+}
+
+mod close_all {
+    use super::*;
+    use w5500_hl::{ll::SocketStatus, Error};
+
+    struct MockRegisters {
+        sn_sr: [SocketStatus; 8],
+        sn_sr_reads: [u32; 8],
+        stuck: Option<Sn>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(cmd, SocketCommand::Close);
+            if Some(socket) != self.stuck {
+                self.sn_sr[usize::from(socket)] = SocketStatus::Closed;
+            }
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            self.sn_sr_reads[usize::from(socket)] += 1;
+            Ok(Ok(self.sn_sr[usize::from(socket)]))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn close_all() {
+        let mut mock = MockRegisters {
+            sn_sr: [SocketStatus::Closed; 8],
+            sn_sr_reads: [0; 8],
+            stuck: None,
+        };
+        mock.sn_sr[2] = SocketStatus::Udp;
+        mock.sn_sr[5] = SocketStatus::Established;
+        assert_eq!(mock.close_all(), Ok(()));
+        assert_eq!(mock.sn_sr, [SocketStatus::Closed; 8]);
+        assert_eq!(mock.sn_sr_reads, [1; 8]);
+    }
+
+    #[test]
+    fn stuck() {
+        let mut mock = MockRegisters {
+            sn_sr: [SocketStatus::Closed; 8],
+            sn_sr_reads: [0; 8],
+            stuck: Some(Sn::Sn4),
+        };
+        mock.sn_sr[4] = SocketStatus::Established;
+        assert_eq!(mock.close_all(), Err(Error::Timeout));
+        assert_eq!(mock.sn_sr_reads[4], 1000);
+        assert_eq!(mock.sn_sr_reads[5..], [0; 3]);
+    }
+}
+
+mod bring_up {
+    use super::*;
+    use std::time::Duration;
+    use w5500_hl::{
+        ll::{BufferSize, Mode, PhyCfg},
+        net::Eui48Addr,
+        BringUpConfig, BringUpError,
+    };
+
+    const CONFIG: BringUpConfig = BringUpConfig::new(
+        Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC),
+        Ipv4Addr::new(192, 168, 0, 50),
+        Ipv4Addr::new(192, 168, 0, 1),
+        Ipv4Addr::new(255, 255, 255, 0),
+    );
+
+    #[derive(Default)]
+    struct MockRegisters {
+        /// Polls of the mode register before the reset bit clears.
+        reset_polls: usize,
+        /// Polls of the PHY configuration register before the link is up.
+        link_polls: usize,
+        version: u8,
+        reset: bool,
+        shar: Option<Eui48Addr>,
+        sipr: Option<Ipv4Addr>,
+        gar: Option<Ipv4Addr>,
+        subr: Option<Ipv4Addr>,
+        rxbuf_size: [Option<BufferSize>; 8],
+        txbuf_size: [Option<BufferSize>; 8],
+        mr: Option<Mode>,
+        rtr: Option<u16>,
+        rcr: Option<u8>,
+        simr: Option<u8>,
+    }
+
+    impl MockRegisters {
+        fn new() -> Self {
+            Self {
+                reset_polls: 2,
+                link_polls: 3,
+                version: 0x04,
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_mr(&mut self, mode: Mode) -> Result<(), Self::Error> {
+            if u8::from(mode) & Mode::RST_MASK != 0 {
+                assert_eq!(mode, Mode::DEFAULT.rst());
+                self.reset = true;
+            } else {
+                assert!(self.reset, "mode set before reset");
+                self.mr = Some(mode);
+            }
+            Ok(())
+        }
+
+        fn set_rtr(&mut self, rtr: u16) -> Result<(), Self::Error> {
+            self.rtr = Some(rtr);
+            Ok(())
+        }
+
+        fn set_rcr(&mut self, rcr: u8) -> Result<(), Self::Error> {
+            self.rcr = Some(rcr);
+            Ok(())
+        }
+
+        fn set_simr(&mut self, simr: u8) -> Result<(), Self::Error> {
+            self.simr = Some(simr);
+            Ok(())
+        }
+
+        fn mr(&mut self) -> Result<Mode, Self::Error> {
+            assert!(self.reset, "mode polled before reset");
+            if self.reset_polls == 0 {
+                Ok(Mode::DEFAULT)
+            } else {
+                self.reset_polls -= 1;
+                Ok(Mode::DEFAULT.rst())
+            }
+        }
+
+        fn version(&mut self) -> Result<u8, Self::Error> {
+            assert!(self.reset, "version read before reset");
+            Ok(self.version)
+        }
+
+        fn set_shar(&mut self, shar: &Eui48Addr) -> Result<(), Self::Error> {
+            self.shar = Some(*shar);
+            Ok(())
+        }
+
+        fn set_sipr(&mut self, sipr: &Ipv4Addr) -> Result<(), Self::Error> {
+            self.sipr = Some(*sipr);
+            Ok(())
+        }
+
+        fn set_gar(&mut self, gar: &Ipv4Addr) -> Result<(), Self::Error> {
+            self.gar = Some(*gar);
+            Ok(())
+        }
+
+        fn set_subr(&mut self, subr: &Ipv4Addr) -> Result<(), Self::Error> {
+            self.subr = Some(*subr);
+            Ok(())
+        }
+
+        fn set_sn_rxbuf_size(&mut self, sn: Sn, size: BufferSize) -> Result<(), Self::Error> {
+            self.rxbuf_size[usize::from(sn)] = Some(size);
+            Ok(())
+        }
+
+        fn set_sn_txbuf_size(&mut self, sn: Sn, size: BufferSize) -> Result<(), Self::Error> {
+            self.txbuf_size[usize::from(sn)] = Some(size);
+            Ok(())
+        }
+
+        fn phycfgr(&mut self) -> Result<PhyCfg, Self::Error> {
+            if self.link_polls == 0 {
+                Ok(PhyCfg::from(PhyCfg::LNK_MASK))
+            } else {
+                self.link_polls -= 1;
+                Ok(PhyCfg::from(0))
+            }
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn bring_up() {
+        let mut mock = MockRegisters::new();
+        let mut delays: Vec<u32> = Vec::new();
+        mock.bring_up(&CONFIG, |ms| delays.push(ms)).unwrap();
+        assert_eq!(delays, [1, 1, 10, 10, 10]);
+        assert_eq!(mock.shar, Some(CONFIG.mac));
+        assert_eq!(mock.sipr, Some(CONFIG.ip));
+        assert_eq!(mock.gar, Some(CONFIG.gateway));
+        assert_eq!(mock.subr, Some(CONFIG.subnet_mask));
+        assert_eq!(mock.rxbuf_size, [Some(BufferSize::KB2); 8]);
+        assert_eq!(mock.txbuf_size, [Some(BufferSize::KB2); 8]);
+        assert_eq!(mock.mr, None);
+        assert_eq!(mock.rtr, None);
+        assert_eq!(mock.rcr, None);
+        assert_eq!(mock.simr, None);
+    }
+
+    #[test]
+    fn optional_registers() {
+        let mut mock = MockRegisters::new();
+        let mut config: BringUpConfig = CONFIG;
+        config.mode = Some(Mode::DEFAULT.enable_pb());
+        config.retry_time = Some(1000);
+        config.retry_count = Some(3);
+        config.socket_interrupt_mask = Some(0x81);
+        mock.bring_up(&config, |_| ()).unwrap();
+        assert_eq!(mock.mr, Some(Mode::DEFAULT.enable_pb()));
+        assert_eq!(mock.rtr, Some(1000));
+        assert_eq!(mock.rcr, Some(3));
+        assert_eq!(mock.simr, Some(0x81));
+    }
+
+    #[test]
+    fn buffer_overallocation() {
+        let mut mock = MockRegisters::new();
+        let mut config: BringUpConfig = CONFIG;
+        config.tx_buf_sizes[7] = BufferSize::KB4;
+        assert_eq!(
+            mock.bring_up(&config, |_| ()),
+            Err(BringUpError::BufferOverallocation)
+        );
+        assert!(!mock.reset);
+    }
+
+    #[test]
+    fn reset_timeout() {
+        let mut mock = MockRegisters::new();
+        mock.reset_polls = usize::MAX;
+        assert_eq!(
+            mock.bring_up(&CONFIG, |_| ()),
+            Err(BringUpError::ResetTimeout)
+        );
+    }
+
+    #[test]
+    fn software_reset() {
+        let mut mock = MockRegisters::new();
+        let mut delays: Vec<u32> = Vec::new();
+        assert_eq!(mock.software_reset(|ms| delays.push(ms)), Ok(()));
+        assert!(mock.reset);
+        assert_eq!(delays, [1, 1]);
+
+        let mut mock = MockRegisters::new();
+        mock.reset_polls = usize::MAX;
+        let mut delays: usize = 0;
+        assert_eq!(
+            mock.software_reset(|_| delays += 1),
+            Err(w5500_hl::Error::Timeout)
+        );
+        assert_eq!(delays, 99);
+    }
+
+    #[test]
+    fn is_version_valid() {
+        let mut mock = MockRegisters::new();
+        mock.reset = true;
+        assert_eq!(mock.is_version_valid(), Ok(true));
+        mock.version = 0xFF;
+        assert_eq!(mock.is_version_valid(), Ok(false));
+    }
+
+    #[test]
+    fn probe() {
+        let mut mock = MockRegisters::new();
+        mock.reset = true;
+        assert_eq!(mock.probe(), Ok(()));
+        mock.version = 0x00;
+        assert_eq!(mock.probe(), Err(w5500_hl::Error::NotFound));
+    }
+
+    #[test]
+    fn version() {
+        let mut mock = MockRegisters::new();
+        mock.version = 0xFF;
+        assert_eq!(
+            mock.bring_up(&CONFIG, |_| ()),
+            Err(BringUpError::Version(0xFF))
+        );
+        assert_eq!(mock.shar, None);
+    }
+
+    #[test]
+    fn link_timeout() {
+        let mut mock = MockRegisters::new();
+        mock.link_polls = usize::MAX;
+        let mut config: BringUpConfig = CONFIG;
+        config.link_timeout = Some(Duration::from_millis(50));
+        let mut delays: usize = 0;
+        assert_eq!(
+            mock.bring_up(&config, |_| delays += 1),
+            Err(BringUpError::LinkTimeout)
+        );
+        assert_eq!(delays, 2 + 5);
+
+        let mut mock = MockRegisters::new();
+        mock.link_polls = usize::MAX;
+        config.link_timeout = None;
+        assert_eq!(mock.bring_up(&config, |_| ()), Ok(()));
+    }
+}
+
+mod socket_info {
+    use super::*;
+    use w5500_hl::ll::{net::Eui48Addr, SocketStatus};
+    use w5500_hl::SocketInfo;
+
+    const TEST_SOCKET: Sn = Sn::Sn5;
+
+    /// Socket register block, starting at SN_MR.
+    struct MockRegisters {
+        regs: [u8; 0x30],
+        reads: usize,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(block, TEST_SOCKET.block());
+            let start: usize = address.into();
+            data.copy_from_slice(&self.regs[start..start + data.len()]);
+            self.reads += 1;
+            Ok(())
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn socket_info() {
+        let mut regs: [u8; 0x30] = [0; 0x30];
+        regs[..0x12].copy_from_slice(&[
+            0x02, 0x00, 0x04, 0x22, 0x1F, 0x90, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 192, 168, 0, 2,
+            0x13, 0x88,
+        ]);
+        regs[0x20..0x2C].copy_from_slice(&[
+            0x08, 0x00, 0x01, 0x02, 0x01, 0x02, 0x00, 0x0C, 0xFF, 0xF0, 0x00, 0x0C,
+        ]);
+        let mut mock = MockRegisters { regs, reads: 0 };
+
+        let info: SocketInfo = mock.socket_info(TEST_SOCKET).unwrap();
+        assert_eq!(mock.reads, 2);
+        assert_eq!(info.sn, TEST_SOCKET);
+        assert_eq!(u8::from(info.mode), 0x02);
+        assert!(info.interrupt.recv_raised());
+        assert_eq!(info.status, Ok(SocketStatus::Udp));
+        assert_eq!(info.port, 8080);
+        assert_eq!(
+            info.dhar,
+            Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC)
+        );
+        assert_eq!(
+            info.dest,
+            SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 5000)
+        );
+        assert_eq!(info.tx_fsr, 2048);
+        assert_eq!(info.tx_rd, 0x0102);
+        assert_eq!(info.tx_wr, 0x0102);
+        assert_eq!(info.rx_rsr, 12);
+        assert_eq!(info.rx_rd, 0xFFF0);
+        assert_eq!(info.rx_wr, 0x000C);
+    }
+}
+
+mod socket_claims {
+    use super::*;
+    use w5500_hl::{SocketClaims, SocketHandle};
+
+    #[derive(Default)]
+    struct MockRegisters {
+        sn_cr: Vec<(Sn, SocketCommand)>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            self.sn_cr.push((socket, cmd));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn claim_socket() {
+        let sockets = SocketClaims::new(MockRegisters::default());
+
+        let sn2: SocketHandle<_> = sockets.claim_socket(Sn::Sn2).unwrap();
+        let sn5: SocketHandle<_> = sockets.claim_socket(Sn::Sn5).unwrap();
+        assert_eq!(sn2.sn(), Sn::Sn2);
+        assert!(sockets.is_claimed(Sn::Sn2));
+        assert!(sockets.claim_socket(Sn::Sn2).is_none());
+        assert!(!sockets.is_claimed(Sn::Sn0));
+
+        sn5.with(|w5500, sn| w5500.set_sn_cr(sn, SocketCommand::Open))
+            .unwrap();
+        assert_eq!(sockets.w5500().sn_cr, [(Sn::Sn5, SocketCommand::Open)]);
+
+        drop(sn2);
+        assert!(!sockets.is_claimed(Sn::Sn2));
+        assert!(sockets.is_claimed(Sn::Sn5));
+        assert_eq!(
+            sockets.w5500().sn_cr,
+            [
+                (Sn::Sn5, SocketCommand::Open),
+                (Sn::Sn2, SocketCommand::Close)
+            ]
+        );
+
+        let sn2: SocketHandle<_> = sockets.claim_socket(Sn::Sn2).unwrap();
+        drop(sn2);
+        drop(sn5);
+        assert_eq!(
+            SOCKETS.iter().filter(|sn| sockets.is_claimed(**sn)).count(),
+            0
+        );
+        assert_eq!(sockets.into_inner().sn_cr.len(), 4);
+    }
+
+    #[test]
+    fn drop_while_borrowed() {
+        let sockets = SocketClaims::new(MockRegisters::default());
+
+        let sn1: SocketHandle<_> = sockets.claim_socket(Sn::Sn1).unwrap();
+        let sn3: SocketHandle<_> = sockets.claim_socket(Sn::Sn3).unwrap();
+        sn3.with(|_, _| drop(sn1));
+        // the close is deferred to the next borrow
+        assert!(sockets.is_claimed(Sn::Sn1));
+
+        let sn1: SocketHandle<_> = sockets.claim_socket(Sn::Sn1).unwrap();
+        assert_eq!(sockets.w5500().sn_cr, [(Sn::Sn1, SocketCommand::Close)]);
+
+        let w5500 = sockets.w5500();
+        drop(sn1);
+        drop(sn3);
+        assert!(sockets.is_claimed(Sn::Sn1));
+        assert!(sockets.is_claimed(Sn::Sn3));
+        assert_eq!(w5500.sn_cr.len(), 1);
+        drop(w5500);
+
+        assert_eq!(
+            sockets.w5500().sn_cr,
+            [
+                (Sn::Sn1, SocketCommand::Close),
+                (Sn::Sn1, SocketCommand::Close),
+                (Sn::Sn3, SocketCommand::Close)
+            ]
+        );
+        assert!(!sockets.is_claimed(Sn::Sn1));
+        assert!(!sockets.is_claimed(Sn::Sn3));
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "embedded-io")]
+
+use embedded_io::{Error as _, ErrorKind, Read, Write};
+use std::convert::Infallible;
+use w5500_hl::{Error, Tcp};
+use w5500_ll::{Registers, Sn, SocketStatus};
+
+const TEST_SOCKET: Sn = Sn::Sn4;
+
+struct MockRegisters {
+    rx_buf: Vec<u8>,
+    tx_buf: Vec<u8>,
+    tx_fsr: u16,
+}
+
+impl Registers for MockRegisters {
+    type Error = Infallible;
+
+    fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(Ok(SocketStatus::Established))
+    }
+
+    fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(self.rx_buf.len().try_into().unwrap())
+    }
+
+    fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(0)
+    }
+
+    fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        let start: usize = ptr.into();
+        buf.copy_from_slice(&self.rx_buf[start..start + buf.len()]);
+        Ok(())
+    }
+
+    fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(self.tx_fsr)
+    }
+
+    fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        Ok(0)
+    }
+
+    fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+        assert_eq!(socket, TEST_SOCKET);
+        assert_eq!(usize::from(ptr), self.tx_buf.len());
+        self.tx_buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+fn new_mock() -> MockRegisters {
+    MockRegisters {
+        rx_buf: vec![1, 2, 3, 4, 5],
+        tx_buf: Vec::new(),
+        tx_fsr: 4,
+    }
+}
+
+#[test]
+fn tcp_reader() {
+    let mut mock = new_mock();
+    let mut reader = mock.tcp_reader(TEST_SOCKET).unwrap();
+    let mut buf: [u8; 3] = [0; 3];
+    assert_eq!(Read::read(&mut reader, &mut buf), Ok(3));
+    assert_eq!(buf, [1, 2, 3]);
+    assert_eq!(Read::read(&mut reader, &mut buf), Ok(2));
+    assert_eq!(buf[..2], [4, 5]);
+    assert_eq!(Read::read(&mut reader, &mut buf), Ok(0));
+}
+
+#[test]
+fn tcp_writer() {
+    let mut mock = new_mock();
+    let mut writer = mock.tcp_writer(TEST_SOCKET).unwrap();
+    assert_eq!(Write::write(&mut writer, &[1, 2, 3]), Ok(3));
+    assert_eq!(Write::write(&mut writer, &[4, 5, 6]), Ok(1));
+    assert_eq!(Write::write(&mut writer, &[7]), Ok(0));
+    assert_eq!(Write::flush(&mut writer), Ok(()));
+    assert_eq!(mock.tx_buf, [1, 2, 3, 4]);
+}
+
+#[test]
+fn error_kind() {
+    assert_eq!(
+        Error::<Infallible>::OutOfMemory.kind(),
+        ErrorKind::OutOfMemory
+    );
+    assert_eq!(Error::<Infallible>::SendTimeout.kind(), ErrorKind::TimedOut);
+    assert_eq!(Error::<Infallible>::Timeout.kind(), ErrorKind::TimedOut);
+    assert_eq!(
+        Error::<Infallible>::ConnectionRefused.kind(),
+        ErrorKind::ConnectionRefused
+    );
+    assert_eq!(
+        Error::<Infallible>::NotListening.kind(),
+        ErrorKind::NotConnected
+    );
+    assert_eq!(
+        Error::<Infallible>::NotConnected.kind(),
+        ErrorKind::NotConnected
+    );
+    assert_eq!(
+        Error::<Infallible>::BufferOverallocation.kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        Error::<Infallible>::Unsupported.kind(),
+        ErrorKind::Unsupported
+    );
+    assert_eq!(Error::<Infallible>::NotFound.kind(), ErrorKind::NotFound);
+    assert_eq!(
+        Error::<Infallible>::PortInUse(Sn::Sn0).kind(),
+        ErrorKind::AddrInUse
+    );
+    assert_eq!(Error::<Infallible>::WouldBlock.kind(), ErrorKind::Other);
+}
@@ -0,0 +1,266 @@
+use std::convert::Infallible;
+use w5500_hl::{Error, MacRaw};
+use w5500_ll::{Registers, Sn, SocketCommand, SocketStatus};
+
+/// Tests the macraw_recv method
+mod macraw_recv {
+    use super::*;
+
+    struct MockRegisters {
+        rx_buf: Vec<u8>,
+        rd: u16,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(Ok(SocketStatus::Macraw))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            let len: u16 = self.rx_buf.len().try_into().unwrap();
+            Ok(len - self.rd)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(self.rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            self.rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            let start: usize = ptr.into();
+            buf.copy_from_slice(&self.rx_buf[start..start + buf.len()]);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn macraw_recv() {
+        let mut mock = MockRegisters {
+            // two frames, the length in the header includes the header
+            rx_buf: vec![0x00, 0x05, 1, 2, 3, 0x00, 0x04, 4, 5],
+            rd: 0,
+            sn_cr: vec![SocketCommand::Recv, SocketCommand::Recv],
+        };
+
+        let mut buf: [u8; 8] = [0; 8];
+        assert_eq!(mock.macraw_recv(&mut buf), Ok(3));
+        assert_eq!(buf[..3], [1, 2, 3]);
+        assert_eq!(mock.rd, 5);
+
+        // excess bytes are discarded
+        let mut buf: [u8; 1] = [0];
+        assert_eq!(mock.macraw_recv(&mut buf), Ok(1));
+        assert_eq!(buf, [4]);
+        assert_eq!(mock.rd, 9);
+        assert!(mock.sn_cr.is_empty());
+
+        assert_eq!(mock.macraw_recv(&mut buf), Err(Error::WouldBlock));
+    }
+
+    #[test]
+    fn partial_frame() {
+        let mut mock = MockRegisters {
+            rx_buf: vec![0x00, 0x05, 1, 2],
+            rd: 0,
+            sn_cr: vec![],
+        };
+        let mut buf: [u8; 8] = [0; 8];
+        assert_eq!(mock.macraw_recv(&mut buf), Err(Error::WouldBlock));
+        assert_eq!(mock.rd, 0);
+    }
+}
+
+/// Tests the gratuitous_arp method
+mod gratuitous_arp {
+    use super::*;
+    use w5500_ll::net::{Eui48Addr, Ipv4Addr};
+
+    struct MockRegisters {
+        tx_buf: Vec<u8>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn shar(&mut self) -> Result<Eui48Addr, Self::Error> {
+            Ok(Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC))
+        }
+
+        fn sipr(&mut self) -> Result<Ipv4Addr, Self::Error> {
+            Ok(Ipv4Addr::new(192, 168, 0, 50))
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(Ok(SocketStatus::Macraw))
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(2048)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(0)
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            assert_eq!(ptr, 0);
+            self.tx_buf.extend_from_slice(buf);
+            Ok(())
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            assert_eq!(usize::from(ptr), self.tx_buf.len());
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn frame() {
+        #[rustfmt::skip]
+        const EXPECTED: [u8; 60] = [
+            // Ethernet destination, source, and type
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC,
+            0x08, 0x06,
+            // ARP request header
+            0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01,
+            // sender hardware and protocol address
+            0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC,
+            192, 168, 0, 50,
+            // target hardware and protocol address
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            192, 168, 0, 50,
+            // padding
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let mut mock = MockRegisters {
+            tx_buf: Vec::new(),
+            sn_cr: vec![SocketCommand::Send],
+        };
+        assert_eq!(mock.gratuitous_arp(), Ok(()));
+        assert_eq!(mock.tx_buf, EXPECTED);
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the macraw_set_promiscuous method
+mod macraw_set_promiscuous {
+    use super::*;
+    use w5500_ll::{Protocol, SocketMode};
+
+    struct MockRegisters {
+        sn_mr: SocketMode,
+        sn_sr: SocketStatus,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_mr(&mut self, socket: Sn) -> Result<SocketMode, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(self.sn_mr)
+        }
+
+        fn set_sn_mr(&mut self, socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            self.sn_mr = mode;
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            Ok(Ok(self.sn_sr))
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            self.sn_sr = match cmd {
+                SocketCommand::Close => SocketStatus::Closed,
+                SocketCommand::Open => SocketStatus::Macraw,
+                _ => unreachable!(),
+            };
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn toggle() {
+        let mut mock = MockRegisters {
+            sn_mr: SocketMode::DEFAULT
+                .set_protocol(Protocol::Macraw)
+                .enable_mfen()
+                .enable_mip6b(),
+            sn_sr: SocketStatus::Macraw,
+            sn_cr: vec![SocketCommand::Open, SocketCommand::Close],
+        };
+
+        mock.macraw_set_promiscuous(true).unwrap();
+        assert!(!mock.sn_mr.mfen_enabled());
+        assert!(mock.sn_mr.mip6b_enabled());
+        assert_eq!(mock.sn_sr, SocketStatus::Macraw);
+        assert!(mock.sn_cr.is_empty());
+
+        mock.sn_cr = vec![SocketCommand::Open, SocketCommand::Close];
+        mock.macraw_set_promiscuous(false).unwrap();
+        assert!(mock.sn_mr.mfen_enabled());
+        assert!(mock.sn_mr.mip6b_enabled());
+        assert!(mock.sn_cr.is_empty());
+    }
+}
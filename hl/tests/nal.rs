@@ -0,0 +1,334 @@
+#![cfg(feature = "embedded-nal")]
+
+use embedded_nal::{nb, UdpClientStack};
+use std::convert::Infallible;
+use w5500_hl::{Error, NalDevice};
+use w5500_ll::{Registers, Sn, SocketCommand, SocketStatus};
+
+struct MockRegisters {
+    sn_cr: Vec<(Sn, SocketCommand)>,
+}
+
+impl Registers for MockRegisters {
+    type Error = Infallible;
+
+    fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+        Ok(Ok(SocketStatus::Closed))
+    }
+
+    fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+        assert_eq!(
+            (socket, cmd),
+            self.sn_cr.pop().expect("Unexpected socket command")
+        );
+        Ok(())
+    }
+
+    fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn allocate_all() {
+    let mut stack = NalDevice::new(MockRegisters { sn_cr: vec![] });
+    for sn in w5500_ll::SOCKETS {
+        assert_eq!(stack.socket().unwrap().sn(), sn);
+    }
+    assert_eq!(stack.socket(), Err(Error::OutOfMemory));
+}
+
+#[test]
+fn allocate_with_sockets() {
+    let mut stack = NalDevice::with_sockets(MockRegisters { sn_cr: vec![] }, &[Sn::Sn2, Sn::Sn5]);
+    assert_eq!(stack.socket().unwrap().sn(), Sn::Sn2);
+    assert_eq!(stack.socket().unwrap().sn(), Sn::Sn5);
+    assert_eq!(stack.socket(), Err(Error::OutOfMemory));
+}
+
+#[test]
+fn close_releases() {
+    let mut stack = NalDevice::with_sockets(
+        MockRegisters {
+            sn_cr: vec![(Sn::Sn1, SocketCommand::Close)],
+        },
+        &[Sn::Sn1],
+    );
+    let socket = stack.socket().unwrap();
+    assert_eq!(stack.socket(), Err(Error::OutOfMemory));
+    stack.close(socket).unwrap();
+    assert_eq!(stack.socket().unwrap().sn(), Sn::Sn1);
+    assert!(stack.free().sn_cr.is_empty());
+}
+
+#[test]
+fn send_not_connected() {
+    let mut stack = NalDevice::new(MockRegisters { sn_cr: vec![] });
+    let mut socket = stack.socket().unwrap();
+    assert!(matches!(
+        stack.send(&mut socket, &[0]),
+        Err(nb::Error::Other(Error::NotConnected))
+    ));
+}
+
+#[test]
+fn not_bound() {
+    use embedded_nal::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpFullStack};
+
+    let remote = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8080));
+    let mut stack = NalDevice::new(MockRegisters { sn_cr: vec![] });
+    let mut socket = stack.socket().unwrap();
+    let mut buf: [u8; 8] = [0; 8];
+    assert!(matches!(
+        stack.receive(&mut socket, &mut buf),
+        Err(nb::Error::Other(Error::NotListening))
+    ));
+    assert!(matches!(
+        stack.send_to(&mut socket, remote, &[0]),
+        Err(nb::Error::Other(Error::NotListening))
+    ));
+}
+
+#[test]
+fn ipv6_unsupported() {
+    use embedded_nal::{Ipv6Addr, SocketAddr, SocketAddrV6, UdpFullStack};
+
+    let remote = SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0));
+    let mut stack = NalDevice::new(MockRegisters { sn_cr: vec![] });
+    let mut socket = stack.socket().unwrap();
+    assert_eq!(stack.connect(&mut socket, remote), Err(Error::Unsupported));
+    assert!(matches!(
+        stack.send_to(&mut socket, remote, &[0]),
+        Err(nb::Error::Other(Error::Unsupported))
+    ));
+}
+
+/// Tests the TCP stack against a mock that tracks the socket status.
+mod tcp {
+    use embedded_nal::{
+        nb, Ipv4Addr as NalIpv4Addr, SocketAddr, SocketAddrV4 as NalSocketAddrV4, TcpClientStack,
+        TcpFullStack,
+    };
+    use std::convert::Infallible;
+    use w5500_hl::{Error, NalDevice};
+    use w5500_ll::{
+        net::{Ipv4Addr, SocketAddrV4},
+        Registers, Sn, SocketCommand, SocketInterrupt, SocketMode, SocketStatus,
+    };
+
+    const PEER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 8080);
+
+    fn peer() -> SocketAddr {
+        SocketAddr::V4(NalSocketAddrV4::new(NalIpv4Addr::new(192, 168, 0, 2), 8080))
+    }
+
+    struct MockRegisters {
+        sn_sr: [SocketStatus; 8],
+        sn_port: [u16; 8],
+        sn_ir: [u8; 8],
+        rx: Vec<u8>,
+    }
+
+    impl MockRegisters {
+        fn new() -> Self {
+            Self {
+                sn_sr: [SocketStatus::Closed; 8],
+                sn_port: [0; 8],
+                sn_ir: [0; 8],
+                rx: Vec::new(),
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.sn_sr[usize::from(socket)]))
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            self.sn_sr[usize::from(socket)] = match cmd {
+                SocketCommand::Close => SocketStatus::Closed,
+                SocketCommand::Open => SocketStatus::Init,
+                SocketCommand::Connect => SocketStatus::SynSent,
+                SocketCommand::Listen => SocketStatus::Listen,
+                SocketCommand::Recv => return Ok(()),
+                cmd => panic!("Unexpected socket command {cmd:?}"),
+            };
+            Ok(())
+        }
+
+        fn sn_ir(&mut self, socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            Ok(SocketInterrupt::from(self.sn_ir[usize::from(socket)]))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir[usize::from(socket)] &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn sn_mr(&mut self, _socket: Sn) -> Result<SocketMode, Self::Error> {
+            Ok(SocketMode::DEFAULT)
+        }
+
+        fn set_sn_mr(&mut self, _socket: Sn, _mode: SocketMode) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn sn_port(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.sn_port[usize::from(socket)])
+        }
+
+        fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+            self.sn_port[usize::from(socket)] = port;
+            Ok(())
+        }
+
+        fn sn_dest(&mut self, _socket: Sn) -> Result<SocketAddrV4, Self::Error> {
+            Ok(PEER)
+        }
+
+        fn set_sn_dest(&mut self, _socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(addr, &PEER);
+            Ok(())
+        }
+
+        fn sn_rx_rsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.rx.len() as u16)
+        }
+
+        fn sn_rx_rd(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn sn_rx_buf(&mut self, _socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            let ptr: usize = ptr.into();
+            buf.copy_from_slice(&self.rx[ptr..ptr + buf.len()]);
+            Ok(())
+        }
+
+        fn set_sn_rx_rd(&mut self, _socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            self.rx.drain(..usize::from(ptr));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn connect_then_data() {
+        let mut stack = NalDevice::new(MockRegisters::new());
+        let mut socket = stack.socket().unwrap();
+        let mut buf: [u8; 8] = [0; 8];
+
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::WouldBlock)
+        ));
+        assert_eq!(stack.get_mut().sn_port[0], 49152);
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::WouldBlock)
+        ));
+        assert_eq!(stack.is_connected(&socket), Ok(false));
+
+        stack.get_mut().sn_sr[0] = SocketStatus::Established;
+        assert!(stack.connect(&mut socket, peer()).is_ok());
+        assert_eq!(stack.is_connected(&socket), Ok(true));
+
+        assert!(matches!(
+            stack.receive(&mut socket, &mut buf),
+            Err(nb::Error::WouldBlock)
+        ));
+
+        stack.get_mut().rx.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(stack.receive(&mut socket, &mut buf).ok(), Some(3));
+        assert_eq!(buf[..3], [1, 2, 3]);
+
+        // the remote host closed the connection
+        stack.get_mut().sn_sr[0] = SocketStatus::CloseWait;
+        assert_eq!(stack.receive(&mut socket, &mut buf).ok(), Some(0));
+    }
+
+    #[test]
+    fn connect_refused() {
+        let mut stack = NalDevice::new(MockRegisters::new());
+        let mut socket = stack.socket().unwrap();
+
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::WouldBlock)
+        ));
+        stack.get_mut().sn_sr[0] = SocketStatus::Closed;
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::Other(Error::ConnectionRefused))
+        ));
+        assert!(matches!(
+            stack.send(&mut socket, &[0]),
+            Err(nb::Error::Other(Error::NotConnected))
+        ));
+    }
+
+    #[test]
+    fn connect_timeout() {
+        let mut stack = NalDevice::new(MockRegisters::new());
+        let mut socket = stack.socket().unwrap();
+
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::WouldBlock)
+        ));
+        stack.get_mut().sn_sr[0] = SocketStatus::Closed;
+        stack.get_mut().sn_ir[0] = SocketInterrupt::TIMEOUT_MASK;
+        assert!(matches!(
+            stack.connect(&mut socket, peer()),
+            Err(nb::Error::Other(Error::Timeout))
+        ));
+        assert_eq!(stack.get_mut().sn_ir[0], 0);
+    }
+
+    #[test]
+    fn accept_moves_listener() {
+        let mut stack = NalDevice::with_sockets(MockRegisters::new(), &[Sn::Sn1, Sn::Sn2]);
+        let mut listener = stack.socket().unwrap();
+        stack.bind(&mut listener, 80).unwrap();
+        stack.listen(&mut listener).unwrap();
+        assert_eq!(stack.get_mut().sn_sr[1], SocketStatus::Listen);
+
+        assert!(matches!(
+            stack.accept(&mut listener),
+            Err(nb::Error::WouldBlock)
+        ));
+
+        stack.get_mut().sn_sr[1] = SocketStatus::Established;
+        let (connection, addr) = stack.accept(&mut listener).unwrap();
+        assert_eq!(addr, peer());
+        assert_eq!(connection.sn(), Sn::Sn1);
+        assert_eq!(listener.sn(), Sn::Sn2);
+        assert_eq!(stack.get_mut().sn_sr[2], SocketStatus::Listen);
+        assert_eq!(stack.get_mut().sn_port[2], 80);
+
+        // no sockets left for the listener
+        stack.get_mut().sn_sr[2] = SocketStatus::Established;
+        assert!(matches!(
+            stack.accept(&mut listener),
+            Err(nb::Error::Other(Error::OutOfMemory))
+        ));
+
+        stack.close(connection).unwrap();
+        assert_eq!(stack.accept(&mut listener).unwrap().0.sn(), Sn::Sn2);
+        assert_eq!(listener.sn(), Sn::Sn1);
+    }
+}
@@ -0,0 +1,119 @@
+use std::convert::Infallible;
+use w5500_hl::ll::{DuplexStatus, OperationMode, PhyCfg, Registers, SpeedStatus};
+use w5500_hl::Phy;
+
+struct MockRegisters {
+    phycfgr: u8,
+    writes: Vec<u8>,
+}
+
+impl Registers for MockRegisters {
+    type Error = Infallible;
+
+    fn phycfgr(&mut self) -> Result<PhyCfg, Self::Error> {
+        Ok(PhyCfg::from(self.phycfgr))
+    }
+
+    fn set_phycfgr(&mut self, phycfg: PhyCfg) -> Result<(), Self::Error> {
+        self.writes.push(u8::from(phycfg));
+        Ok(())
+    }
+
+    fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn link_up() {
+    let mut mock = MockRegisters {
+        phycfgr: PhyCfg::RESET,
+        writes: vec![],
+    };
+    assert_eq!(mock.link_up(), Ok(false));
+
+    // link bit set, reset bit cleared
+    mock.phycfgr = PhyCfg::LNK_MASK;
+    assert_eq!(mock.link_up(), Ok(true));
+
+    mock.phycfgr = PhyCfg::RESET | PhyCfg::LNK_MASK;
+    assert_eq!(mock.link_up(), Ok(true));
+
+    // every bit except the link bit set
+    mock.phycfgr = !PhyCfg::LNK_MASK;
+    assert_eq!(mock.link_up(), Ok(false));
+}
+
+#[test]
+fn speed_duplex() {
+    let mut mock = MockRegisters {
+        phycfgr: PhyCfg::RESET,
+        writes: vec![],
+    };
+    assert_eq!(
+        mock.speed_duplex(),
+        Ok((SpeedStatus::Mbps10, DuplexStatus::Half))
+    );
+
+    mock.phycfgr = PhyCfg::RESET | PhyCfg::SPD_MASK;
+    assert_eq!(
+        mock.speed_duplex(),
+        Ok((SpeedStatus::Mbps100, DuplexStatus::Half))
+    );
+
+    mock.phycfgr = PhyCfg::RESET | PhyCfg::DPX_MASK;
+    assert_eq!(
+        mock.speed_duplex(),
+        Ok((SpeedStatus::Mbps10, DuplexStatus::Full))
+    );
+
+    mock.phycfgr = PhyCfg::RESET | PhyCfg::SPD_MASK | PhyCfg::DPX_MASK | PhyCfg::LNK_MASK;
+    assert_eq!(
+        mock.speed_duplex(),
+        Ok((SpeedStatus::Mbps100, DuplexStatus::Full))
+    );
+}
+
+#[test]
+fn phy_reset() {
+    let mut mock = MockRegisters {
+        phycfgr: 0b11111111,
+        writes: vec![],
+    };
+    assert_eq!(mock.phy_reset(), Ok(()));
+    assert_eq!(mock.writes, vec![0b01111111, 0b11111111]);
+
+    // the reset bit is set after the pulse even if it read back as cleared
+    let mut mock = MockRegisters {
+        phycfgr: 0b01001000,
+        writes: vec![],
+    };
+    assert_eq!(mock.phy_reset(), Ok(()));
+    assert_eq!(mock.writes, vec![0b01001000, 0b11001000]);
+}
+
+#[test]
+fn set_phy_mode() {
+    let mut mock = MockRegisters {
+        phycfgr: PhyCfg::RESET,
+        writes: vec![],
+    };
+    assert_eq!(mock.set_phy_mode(OperationMode::FullDuplex100bt), Ok(()));
+    assert_eq!(mock.writes, vec![0b01011000, 0b11011000]);
+
+    let mut mock = MockRegisters {
+        phycfgr: PhyCfg::RESET,
+        writes: vec![],
+    };
+    assert_eq!(mock.set_phy_mode(OperationMode::HalfDuplex10bt), Ok(()));
+    assert_eq!(mock.writes, vec![0b01000000, 0b11000000]);
+    assert_eq!(
+        PhyCfg::from(mock.writes[1]).opmdc(),
+        OperationMode::HalfDuplex10bt
+    );
+    assert!(PhyCfg::from(mock.writes[1]).opmd());
+}
@@ -0,0 +1,901 @@
+use std::{convert::Infallible, time::Duration};
+use w5500_hl::{Error, Tcp};
+use w5500_ll::{
+    net::{Ipv4Addr, SocketAddrV4},
+    Registers, Sn, SocketCommand, SocketStatus,
+};
+
+/// Tests the tcp_try_accept method
+mod tcp_try_accept {
+    use super::*;
+
+    const PEER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 49152);
+
+    struct MockRegisters {
+        sn_sr: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(SocketStatus::try_from(self.sn_sr))
+        }
+
+        fn sn_dest(&mut self, _socket: Sn) -> Result<SocketAddrV4, Self::Error> {
+            Ok(PEER)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn tcp_try_accept() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Listen.into(),
+        };
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Ok(None));
+
+        mock.sn_sr = SocketStatus::SynRecv.into();
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Ok(None));
+
+        mock.sn_sr = SocketStatus::Established.into();
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Ok(Some(PEER)));
+
+        mock.sn_sr = SocketStatus::CloseWait.into();
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Ok(Some(PEER)));
+
+        mock.sn_sr = SocketStatus::Closed.into();
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Err(Error::NotListening));
+
+        mock.sn_sr = SocketStatus::Udp.into();
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Err(Error::NotListening));
+
+        mock.sn_sr = 0xFE;
+        assert_eq!(mock.tcp_try_accept(Sn::Sn0), Err(Error::NotListening));
+    }
+}
+
+/// Tests the tcp_accept method
+mod tcp_accept {
+    use super::*;
+
+    const PEER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 2), 49153);
+
+    struct MockRegisters {
+        sn_sr: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(SocketStatus::try_from(self.sn_sr))
+        }
+
+        fn sn_dest(&mut self, _socket: Sn) -> Result<SocketAddrV4, Self::Error> {
+            Ok(PEER)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn tcp_accept() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Listen.into(),
+        };
+        assert_eq!(mock.tcp_accept(Sn::Sn0), Ok(None));
+
+        mock.sn_sr = SocketStatus::Established.into();
+        assert_eq!(mock.tcp_accept(Sn::Sn0), Ok(Some(PEER)));
+
+        mock.sn_sr = SocketStatus::Closed.into();
+        assert_eq!(mock.tcp_accept(Sn::Sn0), Ok(None));
+    }
+}
+
+/// Tests partial reads with the tcp_read method
+mod tcp_read {
+    use super::*;
+    use std::mem::MaybeUninit;
+    use w5500_hl::io::Read;
+
+    const TEST_SOCKET: Sn = Sn::Sn4;
+
+    struct MockRegisters {
+        rx_buf: Vec<u8>,
+        rd: u16,
+        wr: u16,
+        sn_cr: Vec<SocketCommand>,
+        transfers: usize,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Established))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.wr.wrapping_sub(self.rd))
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.transfers += 1;
+            let len: u16 = self.rx_buf.len().try_into().unwrap();
+            buf.iter_mut().enumerate().for_each(|(idx, byte)| {
+                let offset: u16 = ptr.wrapping_add(idx.try_into().unwrap()) % len;
+                *byte = self.rx_buf[usize::from(offset)];
+            });
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn partial() {
+        let mut mock = MockRegisters {
+            rx_buf: (0..=255).collect(),
+            rd: 0xFFFE,
+            wr: 0x0003,
+            sn_cr: vec![SocketCommand::Recv, SocketCommand::Recv],
+            transfers: 0,
+        };
+
+        let mut buf: [u8; 3] = [0; 3];
+        assert_eq!(mock.tcp_read(TEST_SOCKET, &mut buf), Ok(3));
+        assert_eq!(buf, [0xFE, 0xFF, 0x00]);
+        assert_eq!(mock.rd, 0x0001);
+
+        assert_eq!(mock.tcp_read(TEST_SOCKET, &mut buf), Ok(2));
+        assert_eq!(buf[..2], [0x01, 0x02]);
+        assert_eq!(mock.rd, 0x0003);
+        assert!(mock.sn_cr.is_empty());
+
+        // no data, no RECV command
+        assert_eq!(mock.tcp_read(TEST_SOCKET, &mut buf), Ok(0));
+    }
+
+    #[test]
+    fn read_uninit() {
+        let mut mock = MockRegisters {
+            rx_buf: (0..=255).collect(),
+            rd: 0,
+            wr: 100,
+            sn_cr: Vec::new(),
+            transfers: 0,
+        };
+
+        let mut reader = mock.tcp_reader(TEST_SOCKET).unwrap();
+        let mut buf: [MaybeUninit<u8>; 70] = [MaybeUninit::uninit(); 70];
+        assert_eq!(reader.read_uninit(&mut buf), Ok(70));
+        assert!(buf
+            .iter()
+            .zip(0..)
+            .all(|(byte, n)| unsafe { byte.assume_init() } == n));
+
+        let mut buf: [MaybeUninit<u8>; 128] = [MaybeUninit::new(0xAA); 128];
+        assert_eq!(reader.read_uninit(&mut buf), Ok(30));
+        assert!(buf[..30]
+            .iter()
+            .zip(70..)
+            .all(|(byte, n)| unsafe { byte.assume_init() } == n));
+        assert!(buf[30..]
+            .iter()
+            .all(|byte| unsafe { byte.assume_init() } == 0xAA));
+
+        assert_eq!(reader.read_uninit(&mut buf), Ok(0));
+
+        // one transfer for each read with data
+        assert_eq!(mock.transfers, 2);
+    }
+}
+
+/// Tests the tcp_read_record method
+mod tcp_read_record {
+    use super::*;
+    use w5500_ll::BufferSize;
+
+    const TEST_SOCKET: Sn = Sn::Sn2;
+
+    struct MockRegisters {
+        rx_buf: Vec<u8>,
+        rd: u16,
+        wr: u16,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Established))
+        }
+
+        fn sn_rxbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(BufferSize::KB1))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.wr.wrapping_sub(self.rd))
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let len: u16 = self.rx_buf.len().try_into().unwrap();
+            buf.iter_mut().enumerate().for_each(|(idx, byte)| {
+                let offset: u16 = ptr.wrapping_add(idx.try_into().unwrap()) % len;
+                *byte = self.rx_buf[usize::from(offset)];
+            });
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    // 256 byte ring buffer with a record starting at 0xFE
+    fn rx_buf(record: &[u8]) -> Vec<u8> {
+        let mut rx_buf: Vec<u8> = vec![0; 256];
+        record.iter().enumerate().for_each(|(idx, byte)| {
+            rx_buf[(0xFE + idx) % 256] = *byte;
+        });
+        rx_buf
+    }
+
+    const RECORD: [u8; 8] = [0x17, 0x03, 0x03, 0x00, 0x03, 0xAA, 0xBB, 0xCC];
+
+    #[test]
+    fn complete() {
+        let mut mock = MockRegisters {
+            rx_buf: rx_buf(&RECORD),
+            rd: 0xFFFE,
+            wr: 0x0006,
+            sn_cr: vec![SocketCommand::Recv],
+        };
+
+        let mut header: [u8; 5] = [0; 5];
+        let mut body: [u8; 8] = [0; 8];
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Ok(3)
+        );
+        assert_eq!(header, RECORD[..5]);
+        assert_eq!(body[..3], RECORD[5..]);
+        assert_eq!(mock.rd, 0x0006);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut mock = MockRegisters {
+            rx_buf: rx_buf(&RECORD),
+            rd: 0xFFFE,
+            wr: 0x0001,
+            sn_cr: vec![],
+        };
+
+        let mut header: [u8; 5] = [0; 5];
+        let mut body: [u8; 8] = [0; 8];
+
+        // partial header
+        mock.wr = 0x0002;
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Err(Error::WouldBlock)
+        );
+
+        // partial body
+        mock.wr = 0x0005;
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Err(Error::WouldBlock)
+        );
+        assert_eq!(mock.rd, 0xFFFE);
+
+        mock.wr = 0x0006;
+        mock.sn_cr.push(SocketCommand::Recv);
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Ok(3)
+        );
+        assert_eq!(body[..3], RECORD[5..]);
+    }
+
+    #[test]
+    fn body_too_small() {
+        let mut mock = MockRegisters {
+            rx_buf: rx_buf(&RECORD),
+            rd: 0xFFFE,
+            wr: 0x0006,
+            sn_cr: vec![],
+        };
+
+        let mut header: [u8; 5] = [0; 5];
+        let mut body: [u8; 2] = [0; 2];
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Err(Error::OutOfMemory)
+        );
+        assert_eq!(mock.rd, 0xFFFE);
+    }
+
+    #[test]
+    fn larger_than_rx_buffer() {
+        // 1 KiB RX buffer can never fit a 5 + 1020 byte record
+        let mut mock = MockRegisters {
+            rx_buf: rx_buf(&[0x17, 0x03, 0x03, 0x03, 0xFC]),
+            rd: 0xFFFE,
+            wr: 0x0100,
+            sn_cr: vec![],
+        };
+
+        let mut header: [u8; 5] = [0; 5];
+        let mut body: [u8; 2048] = [0; 2048];
+        assert_eq!(
+            mock.tcp_read_record(TEST_SOCKET, &mut header, &mut body),
+            Err(Error::OutOfMemory)
+        );
+        assert_eq!(mock.rd, 0xFFFE);
+    }
+}
+
+/// Tests the tcp_poll_connect method
+mod tcp_poll_connect {
+    use super::*;
+    use w5500_ll::SocketInterrupt;
+
+    /// Returns the scripted socket status values in order, one per read.
+    struct MockRegisters {
+        sn_sr: Vec<SocketStatus>,
+        sn_ir: u8,
+        sn_ir_cleared: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.sn_sr.remove(0)))
+        }
+
+        fn sn_ir(&mut self, _socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            Ok(self.sn_ir.into())
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, _socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            let sn_ir: u8 = sn_ir.into();
+            self.sn_ir &= !sn_ir;
+            self.sn_ir_cleared |= sn_ir;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn poll_until_done(mock: &mut MockRegisters) -> Result<(), Error<Infallible>> {
+        loop {
+            match mock.tcp_poll_connect(Sn::Sn0) {
+                Err(Error::WouldBlock) => (),
+                result => return result,
+            }
+        }
+    }
+
+    #[test]
+    fn established() {
+        let mut mock = MockRegisters {
+            sn_sr: vec![
+                SocketStatus::Init,
+                SocketStatus::SynSent,
+                SocketStatus::Established,
+            ],
+            sn_ir: SocketInterrupt::CON_MASK,
+            sn_ir_cleared: 0,
+        };
+        assert_eq!(poll_until_done(&mut mock), Ok(()));
+        assert!(mock.sn_sr.is_empty());
+        assert_eq!(mock.sn_ir_cleared, 0);
+    }
+
+    #[test]
+    fn refused() {
+        let mut mock = MockRegisters {
+            sn_sr: vec![SocketStatus::SynSent, SocketStatus::Closed],
+            sn_ir: SocketInterrupt::DISCON_MASK,
+            sn_ir_cleared: 0,
+        };
+        assert_eq!(poll_until_done(&mut mock), Err(Error::ConnectionRefused));
+        assert!(mock.sn_sr.is_empty());
+        assert_eq!(mock.sn_ir_cleared, SocketInterrupt::DISCON_MASK);
+    }
+
+    #[test]
+    fn timeout() {
+        let mut mock = MockRegisters {
+            sn_sr: vec![
+                SocketStatus::SynSent,
+                SocketStatus::SynSent,
+                SocketStatus::SynSent,
+                SocketStatus::Closed,
+            ],
+            sn_ir: SocketInterrupt::TIMEOUT_MASK,
+            sn_ir_cleared: 0,
+        };
+        assert_eq!(poll_until_done(&mut mock), Err(Error::Timeout));
+        assert!(mock.sn_sr.is_empty());
+        assert_eq!(mock.sn_ir, 0);
+        assert_eq!(mock.sn_ir_cleared, SocketInterrupt::TIMEOUT_MASK);
+    }
+}
+
+/// Tests the tcp_probe method
+mod tcp_probe {
+    use super::*;
+    use w5500_ll::{SocketInterrupt, SocketMode};
+
+    const PORT: u16 = 49152;
+    const SERVICE: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 80);
+
+    /// How the remote host responds to the next connect command.
+    #[derive(Clone, Copy)]
+    enum Remote {
+        Accept,
+        Refuse,
+        Silent,
+    }
+
+    /// Simulates the socket state machine, the handshake completes after
+    /// `handshake_polls` reads of the socket status.
+    struct MockRegisters {
+        remote: Remote,
+        sn_sr: SocketStatus,
+        sn_ir: u8,
+        handshake_polls: u8,
+        connects: u8,
+        delays: u32,
+    }
+
+    impl MockRegisters {
+        fn new(remote: Remote) -> Self {
+            Self {
+                remote,
+                sn_sr: SocketStatus::Closed,
+                // stale interrupts from a previous connection
+                sn_ir: SocketInterrupt::TIMEOUT_MASK | SocketInterrupt::RECV_MASK,
+                handshake_polls: 0,
+                connects: 0,
+                delays: 0,
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            if socket != Sn::Sn0 {
+                return Ok(Ok(SocketStatus::Closed));
+            }
+            if self.sn_sr == SocketStatus::SynSent {
+                if self.handshake_polls == 0 {
+                    (self.sn_sr, self.sn_ir) = match self.remote {
+                        Remote::Accept => (SocketStatus::Established, SocketInterrupt::CON_MASK),
+                        Remote::Refuse => (SocketStatus::Closed, SocketInterrupt::DISCON_MASK),
+                        Remote::Silent => (SocketStatus::Closed, SocketInterrupt::TIMEOUT_MASK),
+                    };
+                } else {
+                    self.handshake_polls -= 1;
+                }
+            }
+            Ok(Ok(self.sn_sr))
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, Sn::Sn0);
+            match cmd {
+                SocketCommand::Close => self.sn_sr = SocketStatus::Closed,
+                SocketCommand::Open => self.sn_sr = SocketStatus::Init,
+                SocketCommand::Connect => {
+                    assert_eq!(self.sn_sr, SocketStatus::Init);
+                    assert_eq!(self.sn_ir, 0, "stale interrupts not cleared");
+                    self.sn_sr = SocketStatus::SynSent;
+                    self.handshake_polls = 2;
+                    self.connects += 1;
+                }
+                _ => panic!("Unexpected command {cmd:?}"),
+            }
+            Ok(())
+        }
+
+        fn sn_ir(&mut self, _socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            Ok(self.sn_ir.into())
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, _socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn sn_mr(&mut self, _socket: Sn) -> Result<SocketMode, Self::Error> {
+            Ok(SocketMode::DEFAULT)
+        }
+
+        fn set_sn_mr(&mut self, _socket: Sn, _mode: SocketMode) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn sn_port(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn set_sn_port(&mut self, _socket: Sn, port: u16) -> Result<(), Self::Error> {
+            assert_eq!(port, PORT);
+            Ok(())
+        }
+
+        fn set_sn_dest(&mut self, _socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(addr, &SERVICE);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn probe(mock: &mut MockRegisters) -> Result<bool, Error<Infallible>> {
+        let mut delays: u32 = 0;
+        let ret = mock.tcp_probe(Sn::Sn0, PORT, &SERVICE, |ms| {
+            assert_eq!(ms, 1);
+            delays += 1;
+        });
+        mock.delays += delays;
+        ret
+    }
+
+    #[test]
+    fn reachable() {
+        let mut mock = MockRegisters::new(Remote::Accept);
+        assert_eq!(probe(&mut mock), Ok(true));
+        assert_eq!(mock.connects, 1);
+        assert_eq!(mock.delays, 2);
+        assert_eq!(mock.sn_sr, SocketStatus::Closed);
+        assert_eq!(mock.sn_ir, 0);
+    }
+
+    #[test]
+    fn timeout() {
+        let mut mock = MockRegisters::new(Remote::Silent);
+        assert_eq!(probe(&mut mock), Ok(false));
+        assert_eq!(mock.sn_sr, SocketStatus::Closed);
+        assert_eq!(mock.sn_ir, 0);
+    }
+
+    #[test]
+    fn refused_then_reconnect() {
+        let mut mock = MockRegisters::new(Remote::Refuse);
+        assert_eq!(probe(&mut mock), Ok(false));
+        assert_eq!(mock.sn_sr, SocketStatus::Closed);
+        assert_eq!(mock.sn_ir, 0);
+
+        // the service came up, the previous probe must not affect this one
+        mock.remote = Remote::Accept;
+        assert_eq!(probe(&mut mock), Ok(true));
+        assert_eq!(mock.connects, 2);
+        assert_eq!(mock.sn_sr, SocketStatus::Closed);
+        assert_eq!(mock.sn_ir, 0);
+    }
+}
+
+/// Tests the TCP keepalive duration conversions
+mod tcp_keepalive {
+    use super::*;
+
+    struct MockRegisters {
+        sn_kpalvtr: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_kpalvtr(&mut self, _socket: Sn) -> Result<u8, Self::Error> {
+            Ok(self.sn_kpalvtr)
+        }
+
+        fn set_sn_kpalvtr(&mut self, _socket: Sn, kpalvtr: u8) -> Result<(), Self::Error> {
+            self.sn_kpalvtr = kpalvtr;
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn set_tcp_keepalive() {
+        let mut mock = MockRegisters { sn_kpalvtr: 0xAA };
+        [
+            (None, 0),
+            (Some(Duration::ZERO), 0),
+            (Some(Duration::from_millis(1)), 1),
+            (Some(Duration::from_secs(5)), 1),
+            (Some(Duration::from_millis(7499)), 1),
+            (Some(Duration::from_millis(7500)), 2),
+            (Some(Duration::from_secs(60)), 12),
+            (Some(Duration::from_secs(255 * 5)), 255),
+            (Some(Duration::from_secs(3600)), 255),
+            (Some(Duration::MAX), 255),
+        ]
+        .iter()
+        .for_each(|(interval, expected)| {
+            mock.set_tcp_keepalive(Sn::Sn0, *interval).unwrap();
+            assert_eq!(mock.sn_kpalvtr, *expected, "{interval:?}");
+        })
+    }
+
+    #[test]
+    fn tcp_keepalive() {
+        let mut mock = MockRegisters { sn_kpalvtr: 0 };
+        assert_eq!(mock.tcp_keepalive(Sn::Sn0), Ok(None));
+        mock.sn_kpalvtr = 1;
+        assert_eq!(
+            mock.tcp_keepalive(Sn::Sn0),
+            Ok(Some(Duration::from_secs(5)))
+        );
+        mock.sn_kpalvtr = 255;
+        assert_eq!(
+            mock.tcp_keepalive(Sn::Sn0),
+            Ok(Some(Duration::from_secs(1275)))
+        );
+    }
+}
+
+/// Tests the set_tcp_no_delay and tcp_no_delay methods
+mod tcp_no_delay {
+    use super::*;
+    use w5500_ll::{Protocol, SocketMode};
+
+    struct MockRegisters {
+        sn_mr: SocketMode,
+        sn_sr: SocketStatus,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_mr(&mut self, _socket: Sn) -> Result<SocketMode, Self::Error> {
+            Ok(self.sn_mr)
+        }
+
+        fn set_sn_mr(&mut self, _socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            self.sn_mr = mode;
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.sn_sr))
+        }
+
+        fn set_sn_cr(&mut self, _socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            self.sn_sr = match cmd {
+                SocketCommand::Close => SocketStatus::Closed,
+                SocketCommand::Open => SocketStatus::Init,
+                SocketCommand::Listen => SocketStatus::Listen,
+                cmd => panic!("Unexpected socket command {cmd:?}"),
+            };
+            Ok(())
+        }
+
+        fn set_sn_port(&mut self, _socket: Sn, _port: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    const TCP: SocketMode = SocketMode::DEFAULT.set_protocol(Protocol::Tcp);
+
+    #[test]
+    fn set_tcp_no_delay() {
+        let mut mock = MockRegisters {
+            sn_mr: TCP,
+            sn_sr: SocketStatus::Closed,
+        };
+        assert_eq!(mock.tcp_no_delay(Sn::Sn0), Ok(false));
+
+        mock.set_tcp_no_delay(Sn::Sn0, true).unwrap();
+        assert_eq!(mock.sn_mr, TCP.enable_nd());
+        assert_eq!(mock.tcp_no_delay(Sn::Sn0), Ok(true));
+
+        mock.set_tcp_no_delay(Sn::Sn0, false).unwrap();
+        assert_eq!(mock.sn_mr, TCP);
+        assert_eq!(mock.tcp_no_delay(Sn::Sn0), Ok(false));
+    }
+
+    #[test]
+    fn retained_by_listen() {
+        let mut mock = MockRegisters {
+            sn_mr: SocketMode::DEFAULT.enable_bcastb(),
+            sn_sr: SocketStatus::Closed,
+        };
+        mock.tcp_listen(Sn::Sn0, 80).unwrap();
+        assert_eq!(mock.sn_mr, TCP);
+
+        mock.set_tcp_no_delay(Sn::Sn0, true).unwrap();
+        mock.tcp_listen(Sn::Sn0, 80).unwrap();
+        assert_eq!(mock.sn_mr, TCP.enable_nd());
+    }
+}
+
+/// Tests the tcp_probe_alive and tcp_poll_alive methods
+mod tcp_probe_alive {
+    use super::*;
+    use w5500_ll::SocketInterrupt;
+
+    const TEST_SOCKET: Sn = Sn::Sn1;
+
+    struct MockRegisters {
+        sn_sr: SocketStatus,
+        sn_ir: u8,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(self.sn_sr))
+        }
+
+        fn sn_ir(&mut self, socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(SocketInterrupt::from(self.sn_ir))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_ir &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn alive() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Established,
+            // stale timeout from a previous operation
+            sn_ir: SocketInterrupt::TIMEOUT_MASK | SocketInterrupt::RECV_MASK,
+            sn_cr: vec![SocketCommand::SendKeep],
+        };
+        mock.tcp_probe_alive(TEST_SOCKET).unwrap();
+        assert!(mock.sn_cr.is_empty());
+        assert_eq!(mock.sn_ir, SocketInterrupt::RECV_MASK);
+        assert_eq!(mock.tcp_poll_alive(TEST_SOCKET), Ok(true));
+    }
+
+    #[test]
+    fn timeout() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Established,
+            sn_ir: 0,
+            sn_cr: vec![SocketCommand::SendKeep],
+        };
+        mock.tcp_probe_alive(TEST_SOCKET).unwrap();
+        mock.sn_ir = SocketInterrupt::TIMEOUT_MASK;
+        mock.sn_sr = SocketStatus::Closed;
+        assert_eq!(mock.tcp_poll_alive(TEST_SOCKET), Ok(false));
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Closed,
+            sn_ir: 0,
+            sn_cr: vec![],
+        };
+        assert_eq!(mock.tcp_poll_alive(TEST_SOCKET), Ok(false));
+    }
+}
@@ -0,0 +1,311 @@
+#![cfg(feature = "test-util")]
+
+use w5500_hl::{
+    io::{Read, Write},
+    ll::{
+        net::{Ipv4Addr, SocketAddrV4},
+        Registers, Sn, SocketInterrupt, SocketStatus,
+    },
+    testutil::Loopback,
+    Error, Tcp, Udp,
+};
+
+const IP: Ipv4Addr = Ipv4Addr::new(192, 168, 0, 2);
+
+fn new_loopback() -> Loopback {
+    let mut w5500 = Loopback::new();
+    w5500.set_sipr(&IP).unwrap();
+    w5500
+}
+
+/// Tests UDP datagrams between sockets
+mod udp {
+    use super::*;
+
+    #[test]
+    fn send_recv() {
+        let mut w5500 = new_loopback();
+        w5500.udp_bind(Sn::Sn0, 1000).unwrap();
+        w5500.udp_bind(Sn::Sn1, 1001).unwrap();
+
+        let dest: SocketAddrV4 = SocketAddrV4::new(IP, 1001);
+        assert_eq!(w5500.udp_send_to(Sn::Sn0, &[1, 2, 3], &dest), Ok(3));
+        assert_eq!(w5500.udp_send_to(Sn::Sn0, &[4, 5], &dest), Ok(2));
+        assert!(w5500.sn_ir(Sn::Sn0).unwrap().sendok_raised());
+        assert_eq!(w5500.sir().unwrap(), Sn::Sn0.bitmask() | Sn::Sn1.bitmask());
+
+        let mut buf: [u8; 8] = [0; 8];
+        let src: SocketAddrV4 = SocketAddrV4::new(IP, 1000);
+        assert_eq!(w5500.udp_recv_from(Sn::Sn1, &mut buf), Ok((3, src)));
+        assert_eq!(buf[..3], [1, 2, 3]);
+        assert_eq!(w5500.udp_recv_from(Sn::Sn1, &mut buf), Ok((2, src)));
+        assert_eq!(buf[..2], [4, 5]);
+        assert_eq!(
+            w5500.udp_recv_from(Sn::Sn1, &mut buf),
+            Err(Error::WouldBlock)
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        let mut w5500 = new_loopback();
+        w5500.udp_bind(Sn::Sn2, 1000).unwrap();
+        w5500.udp_bind(Sn::Sn3, 1001).unwrap();
+
+        let mut writer = w5500.udp_writer(Sn::Sn2).unwrap();
+        writer.write_u16_be(0x0102).unwrap();
+        writer.write_all(&[3, 4]).unwrap();
+        writer.udp_send_to(&SocketAddrV4::new(IP, 1001)).unwrap();
+
+        let mut reader = w5500.udp_reader(Sn::Sn3).unwrap();
+        assert_eq!(reader.header().len, 4);
+        assert_eq!(reader.read_array(), Ok([1, 2, 3, 4]));
+        reader.done().unwrap();
+        assert_eq!(w5500.sn_rx_rsr(Sn::Sn3), Ok(0));
+    }
+
+    #[test]
+    fn ring_wrap() {
+        let mut w5500 = new_loopback();
+        w5500.udp_bind(Sn::Sn0, 1000).unwrap();
+        w5500.udp_bind(Sn::Sn1, 1001).unwrap();
+        let dest: SocketAddrV4 = SocketAddrV4::new(IP, 1001);
+
+        // enough datagrams to wrap both 2 KiB buffers several times
+        let mut buf: [u8; 1000] = [0; 1000];
+        for n in 0..10_u8 {
+            let data: [u8; 1000] = [n; 1000];
+            assert_eq!(w5500.udp_send_to(Sn::Sn0, &data, &dest), Ok(1000));
+            assert_eq!(
+                w5500.udp_recv_from(Sn::Sn1, &mut buf),
+                Ok((1000, SocketAddrV4::new(IP, 1000)))
+            );
+            assert_eq!(buf, data);
+        }
+    }
+
+    #[test]
+    fn dropped() {
+        let mut w5500 = new_loopback();
+        w5500.udp_bind(Sn::Sn0, 1000).unwrap();
+        w5500.udp_bind(Sn::Sn1, 1001).unwrap();
+
+        // no socket on the destination port
+        let dest: SocketAddrV4 = SocketAddrV4::new(IP, 1002);
+        assert_eq!(w5500.udp_send_to(Sn::Sn0, &[1], &dest), Ok(1));
+        assert_eq!(w5500.sn_rx_rsr(Sn::Sn1), Ok(0));
+
+        // no space in the RX buffer
+        let dest: SocketAddrV4 = SocketAddrV4::new(IP, 1001);
+        let data: [u8; 1200] = [0; 1200];
+        assert_eq!(w5500.udp_send_to(Sn::Sn0, &data, &dest), Ok(1200));
+        assert_eq!(w5500.udp_send_to(Sn::Sn0, &data, &dest), Ok(1200));
+        assert_eq!(w5500.sn_rx_rsr(Sn::Sn1), Ok(1208));
+    }
+}
+
+/// Tests TCP connections between sockets
+mod tcp {
+    use super::*;
+
+    #[test]
+    fn connect() {
+        let mut w5500 = new_loopback();
+        w5500.tcp_listen(Sn::Sn0, 80).unwrap();
+        assert_eq!(w5500.sn_sr(Sn::Sn0), Ok(Ok(SocketStatus::Listen)));
+        w5500
+            .tcp_connect(Sn::Sn1, 1000, &SocketAddrV4::new(IP, 80))
+            .unwrap();
+        assert_eq!(w5500.tcp_poll_connect(Sn::Sn1), Ok(()));
+        assert_eq!(w5500.sn_sr(Sn::Sn0), Ok(Ok(SocketStatus::Established)));
+        assert_eq!(w5500.sn_dest(Sn::Sn0), Ok(SocketAddrV4::new(IP, 1000)));
+
+        assert_eq!(w5500.tcp_write(Sn::Sn1, &[1, 2, 3]), Ok(3));
+        assert_eq!(w5500.tcp_write(Sn::Sn0, &[4, 5]), Ok(2));
+        let mut buf: [u8; 8] = [0; 8];
+        assert_eq!(w5500.tcp_read(Sn::Sn0, &mut buf), Ok(3));
+        assert_eq!(buf[..3], [1, 2, 3]);
+        assert_eq!(w5500.tcp_read(Sn::Sn1, &mut buf), Ok(2));
+        assert_eq!(buf[..2], [4, 5]);
+
+        w5500.tcp_disconnect(Sn::Sn1).unwrap();
+        assert_eq!(w5500.sn_sr(Sn::Sn1), Ok(Ok(SocketStatus::Closed)));
+        assert_eq!(w5500.sn_sr(Sn::Sn0), Ok(Ok(SocketStatus::CloseWait)));
+        assert!(w5500.sn_ir(Sn::Sn0).unwrap().discon_raised());
+    }
+
+    #[test]
+    fn refused() {
+        let mut w5500 = new_loopback();
+        w5500
+            .tcp_connect(Sn::Sn1, 1000, &SocketAddrV4::new(IP, 80))
+            .unwrap();
+        assert_eq!(
+            w5500.tcp_poll_connect(Sn::Sn1),
+            Err(Error::ConnectionRefused)
+        );
+    }
+
+    #[test]
+    fn flow_control() {
+        let mut w5500 = new_loopback();
+        w5500.tcp_listen(Sn::Sn0, 80).unwrap();
+        w5500
+            .tcp_connect(Sn::Sn1, 1000, &SocketAddrV4::new(IP, 80))
+            .unwrap();
+
+        // fill the 2 KiB RX buffer of the server, and half of the TX buffer
+        let data: Vec<u8> = (0..3072_u16).map(|n| n as u8).collect();
+        let mut writer = w5500.tcp_writer(Sn::Sn1).unwrap();
+        writer.write_all(&data[..2048]).unwrap();
+        writer.send().unwrap();
+        w5500
+            .set_sn_ir(Sn::Sn1, SocketInterrupt::SENDOK_MASK)
+            .unwrap();
+        let mut writer = w5500.tcp_writer(Sn::Sn1).unwrap();
+        writer.write_all(&data[2048..]).unwrap();
+        writer.send().unwrap();
+        assert_eq!(w5500.sn_tx_fsr(Sn::Sn1), Ok(1024));
+        assert!(!w5500.sn_ir(Sn::Sn1).unwrap().sendok_raised());
+
+        let mut buf: Vec<u8> = vec![0; 3072];
+        assert_eq!(w5500.tcp_read(Sn::Sn0, &mut buf), Ok(2048));
+        assert_eq!(w5500.tcp_read(Sn::Sn0, &mut buf[2048..]), Ok(1024));
+        assert_eq!(buf, data);
+        assert!(w5500.sn_ir(Sn::Sn1).unwrap().sendok_raised());
+        assert_eq!(w5500.sn_tx_fsr(Sn::Sn1), Ok(2048));
+    }
+}
+
+/// Tests the UDP and TCP methods with fixed data length mode transfers
+mod fdm {
+    use super::*;
+    use std::convert::Infallible;
+    use w5500_hl::{Common, SocketInfo};
+    use w5500_ll::SnReg;
+
+    /// The 16-bit socket buffer pointer registers.
+    const PTRS: [SnReg; 6] = [
+        SnReg::TX_FSR0,
+        SnReg::TX_RD0,
+        SnReg::TX_WR0,
+        SnReg::RX_RSR0,
+        SnReg::RX_RD0,
+        SnReg::RX_WR0,
+    ];
+
+    /// Splits transfers into 4, 2, and 1 byte frames, the same as the FDM
+    /// implementations of `w5500-ll`.
+    struct Fdm {
+        w5500: Loopback,
+    }
+
+    impl Fdm {
+        fn new() -> Self {
+            Self {
+                w5500: new_loopback(),
+            }
+        }
+
+        fn frame_len(remain: usize) -> usize {
+            match remain {
+                4.. => 4,
+                2 | 3 => 2,
+                _ => 1,
+            }
+        }
+
+        fn check_frame(address: u16, block: u8, len: usize) {
+            // socket register block
+            if block & 0b11 == 0b01 {
+                for reg in PTRS {
+                    let first: bool = (address..address + len as u16).contains(&reg.addr());
+                    let second: bool = (address..address + len as u16).contains(&(reg.addr() + 1));
+                    assert_eq!(first, second, "{reg:?} split between frames");
+                }
+            }
+        }
+    }
+
+    impl Registers for Fdm {
+        type Error = Infallible;
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            let mut address: u16 = address;
+            let mut data: &mut [u8] = data;
+            while !data.is_empty() {
+                let len: usize = Self::frame_len(data.len());
+                Self::check_frame(address, block, len);
+                let (frame, rest) = data.split_at_mut(len);
+                self.w5500.read(address, block, frame)?;
+                address = address.wrapping_add(len as u16);
+                data = rest;
+            }
+            Ok(())
+        }
+
+        fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+            let mut address: u16 = address;
+            let mut data: &[u8] = data;
+            while !data.is_empty() {
+                let len: usize = Self::frame_len(data.len());
+                Self::check_frame(address, block, len);
+                let (frame, rest) = data.split_at(len);
+                self.w5500.write(address, block, frame)?;
+                address = address.wrapping_add(len as u16);
+                data = rest;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn udp() {
+        let mut w5500 = Fdm::new();
+        w5500.udp_bind(Sn::Sn0, 1000).unwrap();
+        w5500.udp_bind(Sn::Sn1, 1001).unwrap();
+        let dest: SocketAddrV4 = SocketAddrV4::new(IP, 1001);
+        let src: SocketAddrV4 = SocketAddrV4::new(IP, 1000);
+
+        // odd lengths, wrapping the socket buffers
+        let mut buf: [u8; 1023] = [0; 1023];
+        for n in 0..5_u8 {
+            let data: [u8; 1023] = [n; 1023];
+            assert_eq!(w5500.udp_send_to(Sn::Sn0, &data, &dest), Ok(1023));
+            assert_eq!(w5500.udp_recv_from(Sn::Sn1, &mut buf), Ok((1023, src)));
+            assert_eq!(buf, data);
+        }
+
+        let mut writer = w5500.udp_writer(Sn::Sn0).unwrap();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        writer.write_u32_le(0x0706_0504).unwrap();
+        writer.send().unwrap();
+        let mut reader = w5500.udp_reader(Sn::Sn1).unwrap();
+        assert_eq!(reader.read_array(), Ok([1, 2, 3, 4, 5, 6, 7]));
+        reader.done().unwrap();
+
+        let info: SocketInfo = w5500.socket_info(Sn::Sn1).unwrap();
+        assert_eq!(info.rx_rsr, 0);
+    }
+
+    #[test]
+    fn tcp() {
+        let mut w5500 = Fdm::new();
+        w5500.tcp_listen(Sn::Sn0, 80).unwrap();
+        w5500
+            .tcp_connect(Sn::Sn1, 1000, &SocketAddrV4::new(IP, 80))
+            .unwrap();
+        assert_eq!(w5500.tcp_poll_connect(Sn::Sn1), Ok(()));
+
+        let data: Vec<u8> = (0..1537_u16).map(|n| n as u8).collect();
+        let mut writer = w5500.tcp_writer(Sn::Sn1).unwrap();
+        writer.write_all(&data).unwrap();
+        writer.send().unwrap();
+
+        let mut buf: Vec<u8> = vec![0; data.len()];
+        let mut reader = w5500.tcp_reader(Sn::Sn0).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        reader.done().unwrap();
+        assert_eq!(buf, data);
+    }
+}
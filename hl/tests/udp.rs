@@ -2,10 +2,11 @@ use std::convert::Infallible;
 use w5500_hl::{Error, Udp};
 use w5500_ll::{
     net::{Ipv4Addr, SocketAddrV4},
-    Protocol, Registers, Sn, SocketCommand, SocketMode, SocketStatus,
+    BufferSize, Protocol, Registers, Sn, SocketCommand, SocketMode, SocketStatus,
 };
 
 /// Tests debug asserts that ensure the socket is opened as UDP.
+#[cfg(not(feature = "strict"))]
 mod socket_status_debug_assert {
     use super::*;
 
@@ -39,6 +40,13 @@ mod socket_status_debug_assert {
         test.udp_recv_from(Sn::Sn0, &mut buf).ok();
     }
 
+    #[test]
+    #[should_panic]
+    fn udp_recv_into() {
+        let mut test = MockRegisters {};
+        test.udp_recv_into(Sn::Sn0, |_| Ok(())).ok();
+    }
+
     #[test]
     #[should_panic]
     fn udp_peek_from() {
@@ -54,6 +62,34 @@ mod socket_status_debug_assert {
         test.udp_peek_from_header(Sn::Sn0).ok();
     }
 
+    #[test]
+    #[should_panic]
+    fn udp_peek_available() {
+        let mut test = MockRegisters {};
+        test.udp_peek_available(Sn::Sn0).ok();
+    }
+
+    #[test]
+    #[should_panic]
+    fn udp_drain() {
+        let mut test = MockRegisters {};
+        test.udp_drain(Sn::Sn0).ok();
+    }
+
+    #[test]
+    #[should_panic]
+    fn udp_rx_available() {
+        let mut test = MockRegisters {};
+        test.udp_rx_available(Sn::Sn0).ok();
+    }
+
+    #[test]
+    #[should_panic]
+    fn udp_tx_free() {
+        let mut test = MockRegisters {};
+        test.udp_tx_free(Sn::Sn0).ok();
+    }
+
     #[test]
     #[should_panic]
     fn udp_send_to() {
@@ -63,6 +99,14 @@ mod socket_status_debug_assert {
             .ok();
     }
 
+    #[test]
+    #[should_panic]
+    fn udp_send_all() {
+        let mut test = MockRegisters {};
+        let buf: [u8; 1] = [0];
+        test.udp_send_all(Sn::Sn0, &buf).ok();
+    }
+
     #[test]
     #[should_panic]
     fn udp_send() {
@@ -72,6 +116,200 @@ mod socket_status_debug_assert {
     }
 }
 
+/// Tests the socket status checks enabled by the strict feature.
+#[cfg(feature = "strict")]
+mod socket_status_strict {
+    use super::*;
+
+    struct MockRegisters {}
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_rx_rsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(1024)
+        }
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Closed))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    const INVALID_STATE: Error<Infallible> = Error::InvalidState {
+        expected: SocketStatus::Udp,
+        actual: Ok(SocketStatus::Closed),
+    };
+
+    #[test]
+    fn recv() {
+        let mut mock = MockRegisters {};
+        let mut buf: [u8; 1] = [0];
+        assert_eq!(mock.udp_recv_from(Sn::Sn0, &mut buf), Err(INVALID_STATE));
+        assert_eq!(mock.udp_recv_into(Sn::Sn0, |_| Ok(())), Err(INVALID_STATE));
+        assert_eq!(mock.udp_peek_from(Sn::Sn0, &mut buf), Err(INVALID_STATE));
+        assert_eq!(mock.udp_peek_from_header(Sn::Sn0), Err(INVALID_STATE));
+        assert!(matches!(mock.udp_reader(Sn::Sn0), Err(INVALID_STATE)));
+    }
+
+    #[test]
+    fn send() {
+        const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+        let mut mock = MockRegisters {};
+        let buf: [u8; 1] = [0];
+        assert_eq!(mock.udp_send_all(Sn::Sn0, &buf), Err(INVALID_STATE));
+        assert_eq!(
+            mock.udp_send_to_blocking(Sn::Sn0, &buf, &DEST, |_| {}),
+            Err(INVALID_STATE)
+        );
+    }
+
+    /// UDP socket without a destination.
+    struct Unconnected {}
+
+    impl Registers for Unconnected {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_dport(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn not_connected() {
+        let mut mock = Unconnected {};
+        let buf: [u8; 1] = [0];
+        assert_eq!(mock.udp_send_all(Sn::Sn0, &buf), Err(Error::NotConnected));
+    }
+}
+
+/// Tests the socket status checks of the checked methods.
+mod socket_status_checked {
+    use super::*;
+
+    struct MockRegisters {
+        sn_sr: SocketStatus,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_rx_rsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.sn_sr))
+        }
+
+        fn sn_dport(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    const INVALID_STATE: Error<Infallible> = Error::InvalidState {
+        expected: SocketStatus::Udp,
+        actual: Ok(SocketStatus::Closed),
+    };
+
+    #[test]
+    fn recv() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Closed,
+        };
+        assert!(matches!(
+            mock.udp_datagrams_checked(Sn::Sn0),
+            Err(INVALID_STATE)
+        ));
+        assert_eq!(mock.udp_peek_available_checked(Sn::Sn0), Err(INVALID_STATE));
+        assert_eq!(mock.udp_drain_checked(Sn::Sn0), Err(INVALID_STATE));
+        assert_eq!(mock.udp_rx_available_checked(Sn::Sn0), Err(INVALID_STATE));
+    }
+
+    /// The socket status is checked before the RX buffer, a closed socket is
+    /// not reported as having nothing to receive.
+    #[test]
+    fn recv_empty() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Closed,
+        };
+        assert_eq!(mock.udp_peek_available_checked(Sn::Sn0), Err(INVALID_STATE));
+
+        mock.sn_sr = SocketStatus::Udp;
+        assert_eq!(mock.udp_peek_available_checked(Sn::Sn0), Ok(None));
+    }
+
+    #[test]
+    fn send() {
+        const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Closed,
+        };
+        let buf: [u8; 1] = [0];
+        assert_eq!(mock.udp_send_checked(Sn::Sn0, &buf), Err(INVALID_STATE));
+        assert_eq!(
+            mock.udp_send_if_free_checked(Sn::Sn0, &buf),
+            Err(INVALID_STATE)
+        );
+        assert_eq!(
+            mock.udp_send_to_checked(Sn::Sn0, &buf, &DEST),
+            Err(INVALID_STATE)
+        );
+        assert_eq!(
+            mock.udp_send_to_if_free_checked(Sn::Sn0, &buf, &DEST),
+            Err(INVALID_STATE)
+        );
+        assert_eq!(
+            mock.udp_send_to_vectored_checked(Sn::Sn0, &[&buf], &DEST),
+            Err(INVALID_STATE)
+        );
+        assert_eq!(mock.udp_tx_free_checked(Sn::Sn0), Err(INVALID_STATE));
+    }
+
+    #[test]
+    fn not_connected() {
+        let mut mock = MockRegisters {
+            sn_sr: SocketStatus::Udp,
+        };
+        let buf: [u8; 1] = [0];
+        assert_eq!(
+            mock.udp_send_checked(Sn::Sn0, &buf),
+            Err(Error::NotConnected)
+        );
+        assert_eq!(
+            mock.udp_send_if_free_checked(Sn::Sn0, &buf),
+            Err(Error::NotConnected)
+        );
+    }
+}
+
 /// Tests blocking UDP functions return nb::WouldBlock
 mod udp_would_block_header {
     use super::*;
@@ -85,6 +323,10 @@ mod udp_would_block_header {
             Ok(5)
         }
 
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Udp))
+        }
+
         fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
             unimplemented!()
         }
@@ -110,6 +352,12 @@ mod udp_would_block_header {
         assert_eq!(mock.udp_peek_from_header(Sn::Sn0), Err(Error::WouldBlock));
     }
 
+    #[test]
+    fn udp_peek_available() {
+        let mut mock = MockRegisters {};
+        assert_eq!(mock.udp_peek_available(Sn::Sn0), Ok(None));
+    }
+
     #[test]
     fn udp_recv_from() {
         let mut mock = MockRegisters {};
@@ -119,6 +367,15 @@ mod udp_would_block_header {
             Err(Error::WouldBlock)
         );
     }
+
+    #[test]
+    fn udp_recv_into() {
+        let mut mock = MockRegisters {};
+        assert_eq!(
+            mock.udp_recv_into(Sn::Sn0, |_| Ok(())),
+            Err(Error::WouldBlock)
+        );
+    }
 }
 
 /// Tests the udp_bind method
@@ -190,3 +447,2565 @@ mod udp_bind {
         mock.udp_bind(TEST_SOCKET, TEST_PORT).unwrap();
     }
 }
+
+/// Tests the udp_bind_multicast method
+mod udp_bind_multicast {
+    use super::*;
+    use w5500_ll::net::Eui48Addr;
+
+    const TEST_SOCKET: Sn = Sn::Sn4;
+    const TEST_PORT: u16 = 1900;
+    const GROUP: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+
+    #[derive(Default)]
+    struct MockRegisters {
+        sn_sr: Option<SocketStatus>,
+        sn_dhar: Option<Eui48Addr>,
+        sn_dest: Option<SocketAddrV4>,
+        sn_mr: Option<SocketMode>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_sr = match cmd {
+                SocketCommand::Close => Some(SocketStatus::Closed),
+                SocketCommand::Open => {
+                    // the group must be configured before the IGMP join
+                    assert!(self.sn_dhar.is_some());
+                    assert!(self.sn_dest.is_some());
+                    Some(SocketStatus::Udp)
+                }
+                _ => panic!("Unexpected command {cmd:?}"),
+            };
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(self.sn_sr.expect("Unexpected socket status read")))
+        }
+
+        fn set_sn_dhar(&mut self, socket: Sn, dhar: &Eui48Addr) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_dhar = Some(*dhar);
+            Ok(())
+        }
+
+        fn set_sn_dest(&mut self, socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_dest = Some(*addr);
+            Ok(())
+        }
+
+        fn set_sn_mr(&mut self, socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_mr = Some(mode);
+            Ok(())
+        }
+
+        fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(port, TEST_PORT);
+            Ok(())
+        }
+
+        fn sn_port(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn udp_bind_multicast() {
+        let mut mock = MockRegisters::default();
+        mock.udp_bind_multicast(TEST_SOCKET, TEST_PORT, &GROUP)
+            .unwrap();
+        assert_eq!(
+            mock.sn_dhar,
+            Some(Eui48Addr::new(0x01, 0x00, 0x5E, 0x7F, 0xFF, 0xFA))
+        );
+        assert_eq!(mock.sn_dest, Some(GROUP));
+        assert_eq!(mock.sn_sr, Some(SocketStatus::Udp));
+        let mode: SocketMode = mock.sn_mr.unwrap();
+        assert_eq!(mode.protocol(), Ok(Protocol::Udp));
+        assert!(mode.multi_enabled());
+    }
+
+    #[test]
+    #[should_panic = "192.0.2.1 is not a multicast address"]
+    fn not_multicast() {
+        let mut mock = MockRegisters::default();
+        let group = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 1900);
+        mock.udp_bind_multicast(TEST_SOCKET, TEST_PORT, &group)
+            .unwrap();
+    }
+}
+
+/// Tests the udp_bind_checked method
+mod udp_bind_checked {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn3;
+    const TEST_PORT: u16 = 5353;
+
+    struct MockRegisters {
+        port: [u16; 8],
+        status: [SocketStatus; 8],
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl MockRegisters {
+        fn new() -> Self {
+            Self {
+                port: [0; 8],
+                status: [SocketStatus::Closed; 8],
+                sn_cr: Vec::new(),
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.status[usize::from(socket)] = match cmd {
+                SocketCommand::Close => SocketStatus::Closed,
+                SocketCommand::Open => SocketStatus::Udp,
+                _ => panic!("Unexpected socket command {cmd:?}"),
+            };
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+            self.port[usize::from(socket)] = port;
+            Ok(())
+        }
+
+        fn set_sn_mr(&mut self, socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(mode.protocol(), Ok(Protocol::Udp));
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(self.status[usize::from(socket)]))
+        }
+
+        fn sn_port(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.port[usize::from(socket)])
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn port_free() {
+        let mut mock = MockRegisters::new();
+        // closed sockets do not own the port
+        mock.port[1] = TEST_PORT;
+        mock.udp_bind_checked(TEST_SOCKET, TEST_PORT).unwrap();
+        assert_eq!(mock.sn_cr, [SocketCommand::Close, SocketCommand::Open]);
+        assert_eq!(mock.port[usize::from(TEST_SOCKET)], TEST_PORT);
+    }
+
+    #[test]
+    fn rebind() {
+        let mut mock = MockRegisters::new();
+        mock.udp_bind_checked(TEST_SOCKET, TEST_PORT).unwrap();
+        // the socket being bound does not conflict with itself
+        mock.udp_bind_checked(TEST_SOCKET, TEST_PORT).unwrap();
+    }
+
+    #[test]
+    fn port_in_use() {
+        let mut mock = MockRegisters::new();
+        mock.port[6] = TEST_PORT;
+        mock.status[6] = SocketStatus::Udp;
+        assert_eq!(
+            mock.udp_bind_checked(TEST_SOCKET, TEST_PORT),
+            Err(Error::PortInUse(Sn::Sn6))
+        );
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the udp_bind_or_next and udp_bind_ephemeral methods
+mod udp_bind_or_next {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn0;
+
+    struct MockRegisters {
+        sn_port: [u16; 8],
+        sn_sr: Vec<SocketStatus>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_port[usize::from(socket)] = port;
+            Ok(())
+        }
+
+        fn set_sn_mr(&mut self, socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(mode.protocol(), Ok(Protocol::Udp));
+            Ok(())
+        }
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            if socket == TEST_SOCKET {
+                Ok(Ok(self.sn_sr.pop().expect("Unexpected socket status read")))
+            } else {
+                Ok(Ok(SocketStatus::Udp))
+            }
+        }
+
+        fn sn_port(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            Ok(self.sn_port[usize::from(socket)])
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn new_mock(sn_port: [u16; 8]) -> MockRegisters {
+        MockRegisters {
+            sn_port,
+            sn_sr: vec![SocketStatus::Udp, SocketStatus::Closed],
+            sn_cr: vec![SocketCommand::Open, SocketCommand::Close],
+        }
+    }
+
+    #[test]
+    fn preferred_free() {
+        let mut mock = new_mock([0, 1000, 1001, 1002, 1003, 1004, 1005, 1006]);
+        assert_eq!(mock.udp_bind_or_next(TEST_SOCKET, 8080), Ok(8080));
+        assert_eq!(mock.sn_port[0], 8080);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn preferred_in_use() {
+        let mut mock = new_mock([0, 8080, 8081, 8083, 1003, 1004, 1005, 1006]);
+        assert_eq!(mock.udp_bind_or_next(TEST_SOCKET, 8080), Ok(8082));
+        assert_eq!(mock.sn_port[0], 8082);
+    }
+
+    #[test]
+    fn wraps_and_skips_zero() {
+        let mut mock = new_mock([0, u16::MAX, 1, 2, 1003, 1004, 1005, 1006]);
+        assert_eq!(mock.udp_bind_or_next(TEST_SOCKET, u16::MAX), Ok(3));
+        let mut mock = new_mock([0, 1, 1000, 1001, 1002, 1003, 1004, 1005]);
+        assert_eq!(mock.udp_bind_or_next(TEST_SOCKET, 0), Ok(2));
+    }
+
+    #[test]
+    fn ephemeral() {
+        let mut mock = new_mock([0, 8080, 49152, 49153, 49155, 1004, 1005, 1006]);
+        assert_eq!(mock.udp_bind_ephemeral(TEST_SOCKET), Ok(49154));
+        assert_eq!(mock.sn_port[0], 49154);
+        assert!(mock.sn_cr.is_empty());
+
+        // the current port of the socket being bound is not in use
+        let mut mock = new_mock([49152, 8080, 1000, 1001, 1002, 1003, 1004, 1005]);
+        assert_eq!(mock.udp_bind_ephemeral(TEST_SOCKET), Ok(49152));
+    }
+}
+
+/// Tests the udp_recv_into and udp_recv_from methods
+mod udp_recv_into {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn2;
+    const ORIGIN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 4321);
+    const RD: u16 = 0xFFF0;
+
+    struct MockRegisters {
+        rx: Vec<u8>,
+        new_rd: Option<u16>,
+        sn_cr: Vec<SocketCommand>,
+        rxbuf_size: BufferSize,
+    }
+
+    impl MockRegisters {
+        fn new(payload: &[u8]) -> Self {
+            let mut rx: Vec<u8> = Vec::new();
+            rx.extend_from_slice(&ORIGIN.ip().octets);
+            rx.extend_from_slice(&ORIGIN.port().to_be_bytes());
+            rx.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+            rx.extend_from_slice(payload);
+            Self {
+                rx,
+                new_rd: None,
+                sn_cr: vec![SocketCommand::Recv],
+                rxbuf_size: BufferSize::KB2,
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rx.len() as u16)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(RD)
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let offset: usize = ptr.wrapping_sub(RD).into();
+            buf.copy_from_slice(&self.rx[offset..offset + buf.len()]);
+            Ok(())
+        }
+
+        fn sn_rxbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(self.rxbuf_size))
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert!(self.new_rd.is_none(), "RX read pointer set twice");
+            self.new_rd = Some(ptr);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn chunked() {
+        let payload: Vec<u8> = (0..150).collect();
+        let mut mock = MockRegisters::new(&payload);
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        assert_eq!(
+            mock.udp_recv_into(TEST_SOCKET, |chunk| {
+                chunks.push(chunk.to_vec());
+                Ok(())
+            }),
+            Ok((150, ORIGIN))
+        );
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<usize>>(),
+            [64, 64, 22]
+        );
+        assert_eq!(chunks.concat(), payload);
+        assert_eq!(mock.new_rd, Some(RD.wrapping_add(8 + 150)));
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        let mut mock = MockRegisters::new(&[]);
+        assert_eq!(
+            mock.udp_recv_into(TEST_SOCKET, |_| panic!("sink called")),
+            Ok((0, ORIGIN))
+        );
+        assert_eq!(mock.new_rd, Some(RD.wrapping_add(8)));
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn empty_recv_from() {
+        let mut mock = MockRegisters::new(&[]);
+        assert_eq!(mock.udp_recv_from(TEST_SOCKET, &mut []), Ok((0, ORIGIN)));
+        assert_eq!(mock.new_rd, Some(RD.wrapping_add(8)));
+        assert!(mock.sn_cr.is_empty());
+
+        let mut mock = MockRegisters::new(&[]);
+        let mut buf: [u8; 4] = [0xAA; 4];
+        assert_eq!(mock.udp_recv_from(TEST_SOCKET, &mut buf), Ok((0, ORIGIN)));
+        assert_eq!(buf, [0xAA; 4]);
+        assert_eq!(mock.new_rd, Some(RD.wrapping_add(8)));
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn sink_error_keeps_datagram() {
+        let payload: Vec<u8> = (0..150).collect();
+        let mut mock = MockRegisters::new(&payload);
+        let mut calls: usize = 0;
+        assert_eq!(
+            mock.udp_recv_into(TEST_SOCKET, |_| {
+                calls += 1;
+                if calls == 2 {
+                    Err(Error::OutOfMemory)
+                } else {
+                    Ok(())
+                }
+            }),
+            Err(Error::OutOfMemory)
+        );
+        assert_eq!(calls, 2);
+        assert_eq!(mock.new_rd, None);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+
+        // the datagram is received again from the start
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        assert_eq!(
+            mock.udp_recv_into(TEST_SOCKET, |chunk| {
+                chunks.push(chunk.to_vec());
+                Ok(())
+            }),
+            Ok((150, ORIGIN))
+        );
+        assert_eq!(chunks.concat(), payload);
+        assert_eq!(mock.new_rd, Some(RD.wrapping_add(8 + 150)));
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn overflow_events() {
+        let payload: Vec<u8> = vec![0; 1024 - 8];
+        let mut mock = MockRegisters::new(&payload);
+        mock.rxbuf_size = BufferSize::KB1;
+        mock.udp_reset_overflow_events(TEST_SOCKET);
+        assert!(mock.udp_recv_into(TEST_SOCKET, |_| Ok(())).is_ok());
+        assert_eq!(mock.udp_overflow_events(TEST_SOCKET), 1);
+        assert_eq!(mock.udp_overflow_events(Sn::Sn0), 0);
+
+        // one byte of free space is not an overflow
+        let payload: Vec<u8> = vec![0; 1024 - 9];
+        let mut mock = MockRegisters::new(&payload);
+        mock.rxbuf_size = BufferSize::KB1;
+        assert!(mock.udp_recv_into(TEST_SOCKET, |_| Ok(())).is_ok());
+        assert_eq!(mock.udp_overflow_events(TEST_SOCKET), 1);
+    }
+}
+
+/// Tests the udp_reader, udp_reader_with, and udp_recv_datagram methods
+mod udp_reader {
+    use super::*;
+    use std::mem::MaybeUninit;
+    use w5500_hl::{
+        io::{Read, Seek},
+        Datagram,
+    };
+
+    const TEST_SOCKET: Sn = Sn::Sn5;
+    const ORIGIN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 4321);
+
+    /// Only the first `visible` bytes of `rx` have been received by the socket.
+    struct MockRegisters {
+        rx: Vec<u8>,
+        visible: usize,
+        sn_rx_rd: u16,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl MockRegisters {
+        fn new(payloads: &[&[u8]]) -> Self {
+            let mut rx: Vec<u8> = Vec::new();
+            for payload in payloads {
+                rx.extend_from_slice(&ORIGIN.to_bytes());
+                rx.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+                rx.extend_from_slice(payload);
+            }
+            Self {
+                visible: rx.len(),
+                rx,
+                sn_rx_rd: 0,
+                sn_cr: Vec::new(),
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok((self.visible - usize::from(self.sn_rx_rd)) as u16)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.sn_rx_rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_rx_rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.into();
+            assert!(start + buf.len() <= self.visible, "Read past RSR");
+            buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+            Ok(())
+        }
+
+        fn set_sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.into();
+            assert!(start >= usize::from(self.sn_rx_rd), "Write before SN_RX_RD");
+            self.rx[start..start + buf.len()].copy_from_slice(buf);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn partial_datagram() {
+        let mut mock = MockRegisters::new(&[&[1, 2, 3, 4, 5, 6]]);
+        // header and half of the payload
+        mock.visible = 8 + 3;
+        assert!(matches!(
+            mock.udp_reader(TEST_SOCKET),
+            Err(Error::WouldBlock)
+        ));
+        assert_eq!(mock.sn_rx_rd, 0);
+        assert!(mock.sn_cr.is_empty());
+
+        mock.visible = mock.rx.len();
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.stream_len(), 6);
+        let mut buf: [u8; 6] = [0; 6];
+        assert_eq!(reader.read(&mut buf), Ok(6));
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6]);
+        reader.done().unwrap();
+        assert_eq!(mock.sn_rx_rd, 8 + 6);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+    }
+
+    #[test]
+    fn queued_datagrams() {
+        let mut mock = MockRegisters::new(&[&[1, 2, 3], &[4, 5, 6, 7]]);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.header().origin, ORIGIN);
+        assert_eq!(reader.stream_len(), 3);
+        // read less than the whole datagram
+        let mut buf: [u8; 1] = [0];
+        assert_eq!(reader.read(&mut buf), Ok(1));
+        reader.done().unwrap();
+        assert_eq!(mock.sn_rx_rd, 8 + 3);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.stream_len(), 4);
+        let mut buf: [u8; 8] = [0; 8];
+        assert_eq!(reader.read(&mut buf), Ok(4));
+        assert_eq!(buf[..4], [4, 5, 6, 7]);
+        reader.done().unwrap();
+        assert_eq!(mock.sn_rx_rd, 8 + 3 + 8 + 4);
+
+        assert!(matches!(
+            mock.udp_reader(TEST_SOCKET),
+            Err(Error::WouldBlock)
+        ));
+    }
+
+    #[test]
+    fn read_array() {
+        let mut mock = MockRegisters::new(&[&[1, 2, 3, 4, 5]]);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.read_array::<2>(), Ok([1, 2]));
+        assert_eq!(reader.read_array::<4>(), Err(Error::UnexpectedEof));
+        // nothing is read on error
+        assert_eq!(reader.stream_position(), 2);
+        assert_eq!(reader.read_array(), Ok([3, 4, 5]));
+        assert_eq!(reader.read_array(), Ok([]));
+    }
+
+    #[test]
+    fn read_uninit() {
+        let payload: Vec<u8> = (0..100).collect();
+        let mut mock = MockRegisters::new(&[&payload]);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        let mut buf: [MaybeUninit<u8>; 128] = [MaybeUninit::new(0xAA); 128];
+        assert_eq!(reader.read_uninit(&mut buf), Ok(100));
+        assert!(buf[..100]
+            .iter()
+            .zip(payload.iter())
+            .all(|(byte, n)| unsafe { byte.assume_init() } == *n));
+        assert!(buf[100..]
+            .iter()
+            .all(|byte| unsafe { byte.assume_init() } == 0xAA));
+        assert_eq!(reader.read_uninit(&mut buf), Ok(0));
+    }
+
+    fn parse(
+        mock: &mut MockRegisters,
+        discard_on_error: bool,
+    ) -> Result<[u8; 4], Error<Infallible>> {
+        mock.udp_reader_with(TEST_SOCKET, discard_on_error, |reader| reader.read_array())
+    }
+
+    #[test]
+    fn udp_reader_with() {
+        let mut mock = MockRegisters::new(&[&[1, 2], &[3, 4, 5, 6]]);
+
+        // the datagram is kept without discard_on_error
+        assert_eq!(parse(&mut mock, false), Err(Error::UnexpectedEof));
+        assert_eq!(mock.sn_rx_rd, 0);
+        assert!(mock.sn_cr.is_empty());
+
+        assert_eq!(parse(&mut mock, true), Err(Error::UnexpectedEof));
+        assert_eq!(mock.sn_rx_rd, 10);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+
+        assert_eq!(parse(&mut mock, true), Ok([3, 4, 5, 6]));
+        assert_eq!(mock.sn_rx_rd, 22);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv, SocketCommand::Recv]);
+    }
+
+    #[test]
+    fn udp_recv_datagram() {
+        let mut mock = MockRegisters::new(&[&[1, 2, 3], &[4, 5, 6, 7, 8]]);
+
+        let datagram: Datagram<4> = mock.udp_recv_datagram(TEST_SOCKET).unwrap();
+        assert_eq!(datagram.origin(), ORIGIN);
+        assert_eq!(datagram.header().len, 3);
+        assert!(!datagram.was_truncated());
+        assert_eq!(*datagram, [1, 2, 3]);
+
+        let datagram: Datagram<4> = mock.udp_recv_datagram(TEST_SOCKET).unwrap();
+        assert_eq!(datagram.header().len, 5);
+        assert!(datagram.was_truncated());
+        assert_eq!(*datagram, [4, 5, 6, 7]);
+        // excess bytes are discarded
+        assert_eq!(mock.sn_rx_rd, 8 + 3 + 8 + 5);
+
+        assert!(matches!(
+            mock.udp_recv_datagram::<4>(TEST_SOCKET),
+            Err(Error::WouldBlock)
+        ));
+    }
+
+    #[test]
+    fn commit_partial() {
+        let mut mock = MockRegisters::new(&[&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], &[13]]);
+
+        // nothing read, nothing changes
+        let reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        reader.commit_partial().unwrap();
+        assert_eq!(mock.sn_rx_rd, 0);
+        assert!(mock.sn_cr.is_empty());
+
+        // less than a header length read
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        let mut buf: [u8; 2] = [0; 2];
+        reader.read_exact(&mut buf).unwrap();
+        reader.commit_partial().unwrap();
+        assert_eq!(mock.sn_rx_rd, 2);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.header().origin, ORIGIN);
+        assert_eq!(reader.header().len, 10);
+        let mut buf: [u8; 9] = [0; 9];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        reader.commit_partial().unwrap();
+        assert_eq!(mock.sn_rx_rd, 2 + 9);
+
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.header().len, 1);
+        let mut buf: [u8; 1] = [0];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [12]);
+        // at the end, same as done
+        reader.commit_partial().unwrap();
+        assert_eq!(mock.sn_rx_rd, 8 + 12);
+
+        let reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.header().len, 1);
+    }
+}
+
+/// Tests the UDP reader and the udp_recv_from method with a datagram that
+/// wraps around the end of the 16-bit socket buffer address space.
+mod udp_reader_wraparound {
+    use super::*;
+    use w5500_hl::io::{Read, Seek, SeekFrom};
+
+    const TEST_SOCKET: Sn = Sn::Sn7;
+    const ORIGIN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 4321);
+    const PAYLOAD: [u8; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    // the payload starts at 0xFFFC and ends at 0x0006
+    const SN_RX_RD: u16 = 0xFFF4;
+
+    struct MockRegisters {
+        rx: Vec<u8>,
+        rsr: u16,
+        sn_rx_rd: u16,
+        /// Pointer and length of each socket buffer read.
+        reads: Vec<(u16, usize)>,
+    }
+
+    impl MockRegisters {
+        fn new() -> Self {
+            Self::with_payload(SN_RX_RD, &PAYLOAD)
+        }
+
+        fn with_payload(sn_rx_rd: u16, payload: &[u8]) -> Self {
+            let mut datagram: Vec<u8> = Vec::new();
+            datagram.extend_from_slice(&ORIGIN.to_bytes());
+            datagram.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+            datagram.extend_from_slice(payload);
+
+            let mut rx: Vec<u8> = vec![0; 0x10000];
+            for (offset, byte) in datagram.iter().enumerate() {
+                rx[usize::from(sn_rx_rd.wrapping_add(offset as u16))] = *byte;
+            }
+
+            Self {
+                rx,
+                rsr: datagram.len() as u16,
+                sn_rx_rd,
+                reads: Vec::new(),
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rsr)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.sn_rx_rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_rx_rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.reads.push((ptr, buf.len()));
+            for (offset, byte) in buf.iter_mut().enumerate() {
+                *byte = self.rx[usize::from(ptr.wrapping_add(offset as u16))];
+            }
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, SocketCommand::Recv);
+            Ok(())
+        }
+
+        fn sn_rxbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(BufferSize::KB16))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn read_exact() {
+        let mut mock = MockRegisters::new();
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        assert_eq!(reader.header().origin, ORIGIN);
+        assert_eq!(reader.stream_len(), 10);
+
+        let mut buf: [u8; 3] = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0, 1, 2]);
+        assert_eq!(reader.stream_position(), 3);
+        assert_eq!(reader.remain(), 7);
+
+        // straddles the wrap
+        let mut buf: [u8; 4] = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [3, 4, 5, 6]);
+        assert_eq!(reader.stream_position(), 7);
+        assert_eq!(reader.remain(), 3);
+
+        let mut buf: [u8; 4] = [0; 4];
+        assert!(matches!(
+            reader.read_exact(&mut buf),
+            Err(Error::UnexpectedEof)
+        ));
+        assert_eq!(reader.stream_position(), 7);
+
+        let mut buf: [u8; 3] = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [7, 8, 9]);
+        assert_eq!(reader.stream_position(), 10);
+        assert_eq!(reader.remain(), 0);
+
+        reader.done().unwrap();
+        assert_eq!(mock.sn_rx_rd, 0x0006);
+    }
+
+    #[test]
+    fn udp_recv_from() {
+        let mut mock = MockRegisters::new();
+        let mut buf: [u8; 16] = [0; 16];
+        assert_eq!(mock.udp_recv_from(TEST_SOCKET, &mut buf), Ok((10, ORIGIN)));
+        assert_eq!(buf[..10], PAYLOAD);
+        assert_eq!(mock.sn_rx_rd, 0x0006);
+    }
+
+    #[test]
+    fn udp_recv_from_max_size() {
+        // largest datagram that fits in a 16 KiB socket buffer
+        const LEN: usize = 16 * 1024 - 8;
+        const SN_RX_RD: u16 = 0xFFF0;
+        let payload: Vec<u8> = (0..LEN).map(|n| (n % 251) as u8).collect();
+        let mut mock = MockRegisters::with_payload(SN_RX_RD, &payload);
+
+        let mut buf: Vec<u8> = vec![0; LEN];
+        assert_eq!(
+            mock.udp_recv_from(TEST_SOCKET, &mut buf),
+            Ok((LEN as u16, ORIGIN))
+        );
+        assert_eq!(buf, payload);
+        // the W5500 wraps the buffer address, the payload is read with a
+        // single transfer across the end of the address space
+        assert_eq!(mock.reads, [(0xFFF0, 8), (0xFFF8, LEN)]);
+        assert_eq!(mock.sn_rx_rd, 0x3FF0);
+    }
+
+    #[test]
+    fn seek() {
+        let mut mock = MockRegisters::new();
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+
+        reader.seek::<Infallible>(SeekFrom::End(-2)).unwrap();
+        assert_eq!(reader.stream_position(), 8);
+        assert_eq!(reader.remain(), 2);
+        let mut buf: [u8; 2] = [0; 2];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [8, 9]);
+
+        reader.seek::<Infallible>(SeekFrom::End(-10)).unwrap();
+        assert_eq!(reader.stream_position(), 0);
+
+        reader.seek::<Infallible>(SeekFrom::Start(5)).unwrap();
+        assert_eq!(reader.stream_position(), 5);
+        reader.seek::<Infallible>(SeekFrom::Current(-2)).unwrap();
+        assert_eq!(reader.stream_position(), 3);
+        reader.seek::<Infallible>(SeekFrom::Current(5)).unwrap();
+        assert_eq!(reader.stream_position(), 8);
+
+        reader.seek::<Infallible>(SeekFrom::End(0)).unwrap();
+        assert_eq!(reader.stream_position(), 10);
+        assert_eq!(reader.remain(), 0);
+
+        reader.rewind();
+        let mut buf: [u8; 10] = [0; 10];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, PAYLOAD);
+    }
+
+    #[test]
+    fn seek_out_of_range() {
+        let mut mock = MockRegisters::new();
+        let mut reader = mock.udp_reader(TEST_SOCKET).unwrap();
+        reader.seek::<Infallible>(SeekFrom::Start(4)).unwrap();
+
+        for pos in [
+            SeekFrom::Start(11),
+            SeekFrom::End(5),
+            SeekFrom::End(-11),
+            SeekFrom::Current(7),
+            SeekFrom::Current(-5),
+            SeekFrom::Current(-100),
+            SeekFrom::Current(i16::MAX),
+            SeekFrom::Current(i16::MIN),
+        ] {
+            assert!(
+                matches!(reader.seek::<Infallible>(pos), Err(Error::UnexpectedEof)),
+                "{pos:?}"
+            );
+            // the position is not changed
+            assert_eq!(reader.stream_position(), 4, "{pos:?}");
+        }
+
+        let mut buf: [u8; 6] = [0; 6];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [4, 5, 6, 7, 8, 9]);
+    }
+}
+
+/// Tests the udp_datagrams iterator
+mod udp_datagrams {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn6;
+
+    /// Only the first `visible` bytes of `rx` have been received by the socket.
+    struct MockRegisters {
+        rx: Vec<u8>,
+        visible: usize,
+        sn_rx_rd: u16,
+        sn_cr: Vec<SocketCommand>,
+        /// Number of payload bytes read from the socket buffer.
+        payload_read: usize,
+    }
+
+    impl MockRegisters {
+        fn new(datagrams: &[(u16, &[u8])]) -> Self {
+            let mut rx: Vec<u8> = Vec::new();
+            for (port, payload) in datagrams {
+                rx.extend_from_slice(&SocketAddrV4::new(Ipv4Addr::LOCALHOST, *port).to_bytes());
+                rx.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+                rx.extend_from_slice(payload);
+            }
+            Self {
+                visible: rx.len(),
+                rx,
+                sn_rx_rd: 0,
+                sn_cr: Vec::new(),
+                payload_read: 0,
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok((self.visible - usize::from(self.sn_rx_rd)) as u16)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.sn_rx_rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_rx_rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.into();
+            assert!(start + buf.len() <= self.visible, "Read past RSR");
+            buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+            if buf.len() != 8 {
+                self.payload_read += buf.len();
+            }
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn skip_and_read() {
+        let mut mock = MockRegisters::new(&[(1, &[1, 2, 3]), (2, &[4, 5, 6, 7]), (3, &[8])]);
+
+        let mut datagrams = mock.udp_datagrams(TEST_SOCKET).unwrap();
+        let mut buf: [u8; 2] = [0; 2];
+        // reading before the first datagram
+        assert_eq!(datagrams.read_payload(&mut buf), Ok(0));
+
+        assert_eq!(datagrams.next().unwrap().unwrap().origin.port(), 1);
+
+        let header = datagrams.next().unwrap().unwrap();
+        assert_eq!(header.origin.port(), 2);
+        assert_eq!(header.len, 4);
+        // truncated to the buffer length
+        assert_eq!(datagrams.read_payload(&mut buf), Ok(2));
+        assert_eq!(buf, [4, 5]);
+
+        assert_eq!(datagrams.next().unwrap().unwrap().origin.port(), 3);
+        assert!(datagrams.next().is_none());
+        assert!(datagrams.next().is_none());
+
+        // only the read payload was transferred
+        assert_eq!(mock.payload_read, 2);
+        assert_eq!(mock.sn_rx_rd, 8 + 3 + 8 + 4 + 8 + 1);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+    }
+
+    #[test]
+    fn partial_datagram() {
+        let mut mock = MockRegisters::new(&[(1, &[1, 2, 3]), (2, &[4, 5, 6, 7])]);
+        // second datagram is still arriving
+        mock.visible -= 2;
+
+        let mut datagrams = mock.udp_datagrams(TEST_SOCKET).unwrap();
+        assert_eq!(datagrams.next().unwrap().unwrap().origin.port(), 1);
+        assert!(datagrams.next().is_none());
+        assert_eq!(mock.sn_rx_rd, 8 + 3);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+    }
+
+    #[test]
+    fn done_early() {
+        let mut mock = MockRegisters::new(&[(1, &[1, 2, 3]), (2, &[4, 5, 6, 7])]);
+
+        let mut datagrams = mock.udp_datagrams(TEST_SOCKET).unwrap();
+        assert_eq!(datagrams.next().unwrap().unwrap().origin.port(), 1);
+        datagrams.done().unwrap();
+        assert_eq!(mock.sn_rx_rd, 8 + 3);
+        assert_eq!(mock.sn_cr, [SocketCommand::Recv]);
+
+        // dropped without calling done
+        {
+            let mut datagrams = mock.udp_datagrams(TEST_SOCKET).unwrap();
+            assert_eq!(datagrams.next().unwrap().unwrap().origin.port(), 2);
+        }
+        assert_eq!(mock.sn_rx_rd, 8 + 3);
+    }
+
+    #[test]
+    fn empty() {
+        let mut mock = MockRegisters::new(&[]);
+        assert!(mock.udp_datagrams(TEST_SOCKET).unwrap().next().is_none());
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the udp_relay method
+mod udp_relay {
+    use super::*;
+
+    const SRC: Sn = Sn::Sn0;
+    const DST: Sn = Sn::Sn1;
+    const ORIGIN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 2), 4321);
+    const UPSTREAM: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+
+    struct MockRegisters {
+        rx: Vec<u8>,
+        sn_rx_rd: u16,
+        tx: Vec<u8>,
+        tx_fsr: u16,
+        sn_tx_wr: u16,
+        dest: Option<SocketAddrV4>,
+        sn_cr: Vec<(Sn, SocketCommand)>,
+    }
+
+    impl MockRegisters {
+        fn new(payload: &[u8]) -> Self {
+            let mut rx: Vec<u8> = Vec::new();
+            rx.extend_from_slice(&ORIGIN.to_bytes());
+            rx.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+            rx.extend_from_slice(payload);
+            Self {
+                rx,
+                sn_rx_rd: 0,
+                tx: vec![0; 2048],
+                tx_fsr: 2048,
+                sn_tx_wr: 100,
+                dest: None,
+                sn_cr: Vec::new(),
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, SRC);
+            Ok((self.rx.len() - usize::from(self.sn_rx_rd)) as u16)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, SRC);
+            Ok(self.sn_rx_rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, SRC);
+            self.sn_rx_rd = ptr;
+            Ok(())
+        }
+
+        fn sn_rx_buf(&mut self, socket: Sn, ptr: u16, buf: &mut [u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, SRC);
+            assert!(buf.len() <= 64, "Chunk is too large");
+            let start: usize = ptr.into();
+            buf.copy_from_slice(&self.rx[start..start + buf.len()]);
+            Ok(())
+        }
+
+        fn sn_rxbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, SRC);
+            Ok(Ok(BufferSize::KB2))
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, DST);
+            Ok(self.tx_fsr)
+        }
+
+        fn sn_txbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, DST);
+            Ok(Ok(BufferSize::KB2))
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, DST);
+            Ok(self.sn_tx_wr)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, DST);
+            self.sn_tx_wr = ptr;
+            Ok(())
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, DST);
+            let start: usize = ptr.into();
+            self.tx[start..start + buf.len()].copy_from_slice(buf);
+            Ok(())
+        }
+
+        fn set_sn_dest(&mut self, socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(socket, DST);
+            self.dest = Some(*addr);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            self.sn_cr.push((socket, cmd));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn relay() {
+        let payload: Vec<u8> = (0..150).collect();
+        let mut mock = MockRegisters::new(&payload);
+        assert_eq!(mock.udp_relay(SRC, DST, &UPSTREAM), Ok(150));
+        assert_eq!(mock.tx[100..250], payload);
+        assert_eq!(mock.sn_tx_wr, 250);
+        assert_eq!(mock.dest, Some(UPSTREAM));
+        assert_eq!(mock.sn_rx_rd, 8 + 150);
+        assert_eq!(
+            mock.sn_cr,
+            [(DST, SocketCommand::Send), (SRC, SocketCommand::Recv)]
+        );
+
+        // queue is empty
+        assert_eq!(mock.udp_relay(SRC, DST, &UPSTREAM), Err(Error::WouldBlock));
+    }
+
+    #[test]
+    fn partial_datagram() {
+        let mut mock = MockRegisters::new(&[1, 2, 3, 4]);
+        mock.rx.pop();
+        assert_eq!(mock.udp_relay(SRC, DST, &UPSTREAM), Err(Error::WouldBlock));
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn tx_full() {
+        let mut mock = MockRegisters::new(&[1, 2, 3, 4]);
+        mock.tx_fsr = 3;
+        assert_eq!(mock.udp_relay(SRC, DST, &UPSTREAM), Err(Error::WouldBlock));
+        assert_eq!(mock.sn_rx_rd, 0);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn too_large() {
+        let mut mock = MockRegisters::new(&[0; 2049]);
+        assert_eq!(mock.udp_relay(SRC, DST, &UPSTREAM), Err(Error::OutOfMemory));
+        assert_eq!(mock.sn_rx_rd, 0);
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the Stats wrapper with UDP methods
+mod stats_wrapper {
+    use super::*;
+    use std::collections::HashMap;
+    use w5500_hl::{Common, SocketStats, Stats};
+    use w5500_ll::{SnReg, SocketInterrupt};
+
+    const TEST_SOCKET: Sn = Sn::Sn2;
+    const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+
+    /// The wrapper calls the default [`Registers`] methods of the wrapped
+    /// device, this mock simulates the memory of the W5500 instead of
+    /// overriding the register accessors.
+    #[derive(Default)]
+    struct MockRegisters {
+        mem: HashMap<(u8, u16), u8>,
+    }
+
+    impl MockRegisters {
+        fn set(&mut self, block: u8, address: u16, data: &[u8]) {
+            for (offset, byte) in data.iter().enumerate() {
+                self.mem
+                    .insert((block, address.wrapping_add(offset as u16)), *byte);
+            }
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            for (offset, byte) in data.iter_mut().enumerate() {
+                *byte = self
+                    .mem
+                    .get(&(block, address.wrapping_add(offset as u16)))
+                    .copied()
+                    .unwrap_or(0);
+            }
+            Ok(())
+        }
+
+        fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+            if block == TEST_SOCKET.block() && address == SnReg::IR.addr() {
+                // write 1 to clear
+                let ir: &mut u8 = self.mem.entry((block, address)).or_default();
+                *ir &= !data[0];
+            } else {
+                self.set(block, address, data);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn counters() {
+        let block: u8 = TEST_SOCKET.block();
+        let mut mock = MockRegisters::default();
+        mock.set(block, SnReg::SR.addr(), &[SocketStatus::Udp.into()]);
+        mock.set(block, SnReg::TX_FSR0.addr(), &2048_u16.to_be_bytes());
+        // start near the end of the pointer range
+        mock.set(block, SnReg::TX_WR0.addr(), &0xFFFE_u16.to_be_bytes());
+        mock.set(block, SnReg::RX_RSR0.addr(), &11_u16.to_be_bytes());
+        mock.set(TEST_SOCKET.rx_block(), 0, &DEST.to_bytes());
+        mock.set(TEST_SOCKET.rx_block(), 6, &[0, 3, 1, 2, 3]);
+        let mut w5500 = Stats::new(mock);
+
+        assert_eq!(w5500.udp_send_to(TEST_SOCKET, &[0; 10], &DEST), Ok(10));
+        assert_eq!(w5500.udp_send_to(TEST_SOCKET, &[0; 5], &DEST), Ok(5));
+
+        let mut buf: [u8; 3] = [0; 3];
+        assert_eq!(w5500.udp_recv_from(TEST_SOCKET, &mut buf), Ok((3, DEST)));
+
+        w5500
+            .get_mut()
+            .set(block, SnReg::IR.addr(), &[SocketInterrupt::TIMEOUT_MASK]);
+        assert_eq!(w5500.udp_poll_send(TEST_SOCKET), Err(Error::SendTimeout));
+        assert_eq!(w5500.udp_poll_send(TEST_SOCKET), Err(Error::WouldBlock));
+
+        assert_eq!(
+            w5500.stats(TEST_SOCKET),
+            SocketStats {
+                rx_bytes: 8 + 3,
+                tx_bytes: 15,
+                sends: 2,
+                send_timeouts: 1,
+            }
+        );
+        assert_eq!(w5500.stats(Sn::Sn0), SocketStats::default());
+
+        w5500.reset_stats(TEST_SOCKET);
+        assert_eq!(w5500.stats(TEST_SOCKET), SocketStats::default());
+    }
+
+    #[test]
+    fn abort_tx() {
+        let block: u8 = TEST_SOCKET.block();
+        let mut mock = MockRegisters::default();
+        mock.set(block, SnReg::SR.addr(), &[SocketStatus::Udp.into()]);
+        mock.set(block, SnReg::TX_FSR0.addr(), &2048_u16.to_be_bytes());
+        mock.set(block, SnReg::TX_WR0.addr(), &0xFFFC_u16.to_be_bytes());
+        let mut w5500 = Stats::new(mock);
+
+        // stage data across the pointer wrap, then discard it
+        let saved_wr: u16 = w5500.sn_tx_wr(TEST_SOCKET).unwrap();
+        w5500.set_sn_tx_buf(TEST_SOCKET, saved_wr, &[0; 6]).unwrap();
+        w5500
+            .set_sn_tx_wr(TEST_SOCKET, saved_wr.wrapping_add(6))
+            .unwrap();
+        assert_eq!(w5500.stats(TEST_SOCKET).tx_bytes, 0);
+        w5500.abort_tx(TEST_SOCKET, saved_wr).unwrap();
+        assert_eq!(w5500.stats(TEST_SOCKET), SocketStats::default());
+
+        assert_eq!(w5500.udp_send_to(TEST_SOCKET, &[0; 5], &DEST), Ok(5));
+        assert_eq!(
+            w5500.stats(TEST_SOCKET),
+            SocketStats {
+                tx_bytes: 5,
+                sends: 1,
+                ..SocketStats::default()
+            }
+        );
+    }
+}
+
+/// Tests the stable reads of the RX received size and TX free size
+mod stable_size_reads {
+    use super::*;
+
+    /// Returns the values in order, repeating the last value.
+    struct MockRegisters {
+        rsr: Vec<u16>,
+        fsr: Vec<u16>,
+        reads: usize,
+    }
+
+    fn next(values: &mut Vec<u16>) -> u16 {
+        if values.len() > 1 {
+            values.remove(0)
+        } else {
+            values[0]
+        }
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            self.reads += 1;
+            Ok(next(&mut self.rsr))
+        }
+
+        fn sn_tx_fsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            self.reads += 1;
+            Ok(next(&mut self.fsr))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn torn_read() {
+        let mut mock = MockRegisters {
+            rsr: vec![0x00FF, 0x0100, 0x0100],
+            fsr: vec![0x07FF, 0x0700, 0x0800, 0x0800],
+            reads: 0,
+        };
+        assert_eq!(mock.udp_rx_available(Sn::Sn0), Ok(0x0100));
+        assert_eq!(mock.reads, 3);
+        assert_eq!(mock.udp_tx_free(Sn::Sn0), Ok(0x0800));
+        assert_eq!(mock.reads, 7);
+    }
+
+    #[test]
+    fn bounded() {
+        let mut mock = MockRegisters {
+            rsr: (0..100).collect(),
+            fsr: vec![0],
+            reads: 0,
+        };
+        assert_eq!(mock.udp_rx_available(Sn::Sn0), Ok(7));
+        assert_eq!(mock.reads, 8);
+    }
+}
+
+/// Tests the Chunked wrapper
+mod chunked_wrapper {
+    use super::*;
+    use w5500_hl::Chunked;
+
+    #[derive(Default)]
+    struct MockRegisters {
+        reads: Vec<(u16, u8, usize)>,
+        writes: Vec<(u16, u8, Vec<u8>)>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            data.iter_mut()
+                .enumerate()
+                .for_each(|(idx, byte)| *byte = address.wrapping_add(idx as u16) as u8);
+            self.reads.push((address, block, data.len()));
+            Ok(())
+        }
+
+        fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+            self.writes.push((address, block, data.to_vec()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn split_write() {
+        let mut w5500 = Chunked::new(MockRegisters::default(), 4);
+        let data: [u8; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        w5500.set_sn_tx_buf(Sn::Sn1, 0xFFFE, &data).unwrap();
+        let block: u8 = Sn::Sn1.tx_block();
+        assert_eq!(
+            w5500.free().writes,
+            [
+                (0xFFFE, block, vec![0, 1, 2, 3]),
+                (0x0002, block, vec![4, 5, 6, 7]),
+                (0x0006, block, vec![8, 9]),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_read() {
+        let mut w5500 = Chunked::new(MockRegisters::default(), 3);
+        let mut buf: [u8; 7] = [0; 7];
+        w5500.sn_rx_buf(Sn::Sn1, 0x0100, &mut buf).unwrap();
+        assert_eq!(buf, [0, 1, 2, 3, 4, 5, 6]);
+        let block: u8 = Sn::Sn1.rx_block();
+        assert_eq!(
+            w5500.free().reads,
+            [(0x0100, block, 3), (0x0103, block, 3), (0x0106, block, 1)]
+        );
+    }
+
+    #[test]
+    fn short_transfer() {
+        let mut w5500 = Chunked::new(MockRegisters::default(), 4);
+        w5500.set_sn_port(Sn::Sn1, 8080).unwrap();
+        assert_eq!(w5500.get_mut().writes.len(), 1);
+    }
+}
+
+/// Tests the udp_max_send_len method
+mod udp_max_send_len {
+    use super::*;
+
+    struct MockRegisters {
+        txbuf_size: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_txbuf_size(&mut self, _socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            Ok(BufferSize::try_from(self.txbuf_size))
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn buffer_sizes() {
+        for (txbuf_size, expected) in [
+            (BufferSize::KB0, 0),
+            (BufferSize::KB2, 2048),
+            (BufferSize::KB16, 16384),
+        ] {
+            let mut mock = MockRegisters {
+                txbuf_size: txbuf_size.into(),
+            };
+            assert_eq!(mock.udp_max_send_len(Sn::Sn0), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let mut mock = MockRegisters { txbuf_size: 3 };
+        assert_eq!(mock.udp_max_send_len(Sn::Sn0), Ok(0));
+    }
+}
+
+/// Tests the udp_assert_peer method
+mod udp_assert_peer {
+    use super::*;
+
+    const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+
+    struct MockRegisters;
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_dest(&mut self, _socket: Sn) -> Result<SocketAddrV4, Self::Error> {
+            Ok(DEST)
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn matching() {
+        assert_eq!(MockRegisters.udp_assert_peer(Sn::Sn0, &DEST), Ok(true));
+    }
+
+    #[test]
+    fn mismatched() {
+        const OTHER_IP: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 2), 8081);
+        const OTHER_PORT: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8082);
+        assert_eq!(MockRegisters.udp_assert_peer(Sn::Sn0, &OTHER_IP), Ok(false));
+        assert_eq!(
+            MockRegisters.udp_assert_peer(Sn::Sn0, &OTHER_PORT),
+            Ok(false)
+        );
+    }
+}
+
+/// Tests the udp_drain method
+mod udp_drain {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn3;
+
+    struct MockRegisters {
+        rsr: u16,
+        rd: u16,
+        new_rd: Option<u16>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_rx_rsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rsr)
+        }
+
+        fn sn_rx_rd(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.rd)
+        }
+
+        fn set_sn_rx_rd(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert!(self.new_rd.is_none(), "RX read pointer set twice");
+            self.new_rd = Some(ptr);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let mut mock = MockRegisters {
+            rsr: 0,
+            rd: 0x1234,
+            new_rd: None,
+            sn_cr: vec![],
+        };
+        assert_eq!(mock.udp_drain(TEST_SOCKET), Ok(0));
+        assert_eq!(mock.new_rd, None);
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut mock = MockRegisters {
+            rsr: 0x0100,
+            rd: 0xFFC0,
+            new_rd: None,
+            sn_cr: vec![SocketCommand::Recv],
+        };
+        assert_eq!(mock.udp_drain(TEST_SOCKET), Ok(0x0100));
+        assert_eq!(mock.new_rd, Some(0x00C0));
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the udp_send_to_vectored method
+mod udp_send_to_vectored {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn1;
+    const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+
+    struct MockRegisters {
+        fsr: u16,
+        wr: u16,
+        tx_buf: Vec<(u16, Vec<u8>)>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn set_sn_dest(&mut self, socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(addr, &DEST);
+            Ok(())
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.fsr)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.wr)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.wr = ptr;
+            Ok(())
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.tx_buf.push((ptr, buf.to_vec()));
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn all() {
+        let mut mock = MockRegisters {
+            fsr: 2048,
+            wr: 0xFFFE,
+            tx_buf: Vec::new(),
+            sn_cr: vec![SocketCommand::Send],
+        };
+        assert_eq!(
+            mock.udp_send_to_vectored(TEST_SOCKET, &[&[1, 2, 3], &[], &[4, 5]], &DEST),
+            Ok(5)
+        );
+        assert_eq!(
+            mock.tx_buf,
+            vec![(0xFFFE, vec![1, 2, 3]), (0x0001, vec![4, 5])]
+        );
+        assert_eq!(mock.wr, 0x0003);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut mock = MockRegisters {
+            fsr: 4,
+            wr: 0,
+            tx_buf: Vec::new(),
+            sn_cr: vec![SocketCommand::Send],
+        };
+        assert_eq!(
+            mock.udp_send_to_vectored(TEST_SOCKET, &[&[1, 2, 3], &[4, 5], &[6]], &DEST),
+            Ok(4)
+        );
+        assert_eq!(mock.tx_buf, vec![(0, vec![1, 2, 3]), (3, vec![4])]);
+        assert_eq!(mock.wr, 4);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn full() {
+        let mut mock = MockRegisters {
+            fsr: 0,
+            wr: 0,
+            tx_buf: Vec::new(),
+            sn_cr: vec![],
+        };
+        assert_eq!(
+            mock.udp_send_to_vectored(TEST_SOCKET, &[&[1, 2, 3]], &DEST),
+            Ok(0)
+        );
+        assert!(mock.tx_buf.is_empty());
+    }
+}
+
+/// Tests the udp_arp_probe method
+mod udp_arp_probe {
+    use super::*;
+    use w5500_ll::SocketInterrupt;
+
+    const TEST_SOCKET: Sn = Sn::Sn7;
+    const GATEWAY: Ipv4Addr = Ipv4Addr::new(192, 168, 0, 1);
+
+    struct MockRegisters {
+        arp_resolves: bool,
+        sn_sr: SocketStatus,
+        sn_ir: u8,
+        port: u16,
+        dest: Option<SocketAddrV4>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            if socket == TEST_SOCKET {
+                Ok(Ok(self.sn_sr))
+            } else {
+                Ok(Ok(SocketStatus::Closed))
+            }
+        }
+
+        fn sn_port(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn set_sn_port(&mut self, socket: Sn, port: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.port = port;
+            Ok(())
+        }
+
+        fn set_sn_mr(&mut self, socket: Sn, mode: SocketMode) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(mode.protocol(), Ok(Protocol::Udp));
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            match cmd {
+                SocketCommand::Close => self.sn_sr = SocketStatus::Closed,
+                SocketCommand::Open => self.sn_sr = SocketStatus::Udp,
+                SocketCommand::Send => {
+                    self.sn_ir |= if self.arp_resolves {
+                        SocketInterrupt::SENDOK_MASK
+                    } else {
+                        SocketInterrupt::TIMEOUT_MASK
+                    }
+                }
+                _ => panic!("Unexpected socket command {cmd:?}"),
+            }
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn sn_ir(&mut self, socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(SocketInterrupt::from(self.sn_ir))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_ir &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn set_sn_dest(&mut self, socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.dest = Some(*addr);
+            Ok(())
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(2048)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(0)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(ptr, 1);
+            Ok(())
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, _ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(buf.len(), 1);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn new_mock(arp_resolves: bool) -> MockRegisters {
+        MockRegisters {
+            arp_resolves,
+            sn_sr: SocketStatus::Closed,
+            // stale interrupts from a previous send
+            sn_ir: SocketInterrupt::SENDOK_MASK | SocketInterrupt::TIMEOUT_MASK,
+            port: 0,
+            dest: None,
+            sn_cr: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn resolved() {
+        let mut mock = new_mock(true);
+        assert_eq!(mock.udp_arp_probe(TEST_SOCKET, &GATEWAY, |_| {}), Ok(true));
+        assert_eq!(mock.port, 49152);
+        assert_eq!(mock.dest, Some(SocketAddrV4::new(GATEWAY, 9)));
+        assert_eq!(
+            mock.sn_cr,
+            [
+                SocketCommand::Close,
+                SocketCommand::Open,
+                SocketCommand::Send,
+                SocketCommand::Close
+            ]
+        );
+        assert_eq!(mock.sn_sr, SocketStatus::Closed);
+        assert_eq!(mock.sn_ir, 0);
+    }
+
+    #[test]
+    fn timeout() {
+        let mut mock = new_mock(false);
+        assert_eq!(mock.udp_arp_probe(TEST_SOCKET, &GATEWAY, |_| {}), Ok(false));
+        assert_eq!(mock.sn_cr.last(), Some(&SocketCommand::Close));
+        assert_eq!(mock.sn_ir, 0);
+    }
+}
+
+/// Tests the udp_send_wol method
+mod udp_send_wol {
+    use super::*;
+    use w5500_ll::net::Eui48Addr;
+
+    const TEST_SOCKET: Sn = Sn::Sn1;
+
+    struct MockRegisters {
+        dest: Option<SocketAddrV4>,
+        tx_buf: Vec<u8>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn set_sn_dest(&mut self, socket: Sn, addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.dest = Some(*addr);
+            Ok(())
+        }
+
+        fn sn_dport(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.dest.map_or(0, |dest| dest.port()))
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(2048)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(0)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(usize::from(ptr), self.tx_buf.len());
+            Ok(())
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(ptr, 0);
+            self.tx_buf.extend_from_slice(buf);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn magic_packet() {
+        const MAC: Eui48Addr = Eui48Addr::new(0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC);
+        let mut mock = MockRegisters {
+            dest: None,
+            tx_buf: Vec::new(),
+            sn_cr: Vec::new(),
+        };
+        assert_eq!(mock.udp_send_wol(TEST_SOCKET, &MAC), Ok(()));
+        assert_eq!(mock.dest, Some(SocketAddrV4::new(Ipv4Addr::BROADCAST, 9)));
+        assert_eq!(mock.tx_buf.len(), 102);
+        assert_eq!(mock.tx_buf[..6], [0xFF; 6]);
+        mock.tx_buf[6..]
+            .chunks(6)
+            .for_each(|chunk| assert_eq!(chunk, MAC.octets));
+        assert_eq!(mock.sn_cr, [SocketCommand::Send]);
+    }
+
+    #[test]
+    #[should_panic = "MAC address is all zeros"]
+    fn unspecified() {
+        let mut mock = MockRegisters {
+            dest: None,
+            tx_buf: Vec::new(),
+            sn_cr: Vec::new(),
+        };
+        mock.udp_send_wol(TEST_SOCKET, &Eui48Addr::UNSPECIFIED).ok();
+    }
+}
+
+/// Tests the udp_send_all and udp_send_if_free methods
+mod udp_send_all {
+    use super::*;
+
+    const TEST_SOCKET: Sn = Sn::Sn2;
+
+    struct MockRegisters {
+        fsr: u16,
+        wr: u16,
+        tx_buf: Vec<(u16, Vec<u8>)>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_dport(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(8081)
+        }
+
+        fn sn_txbuf_size(&mut self, socket: Sn) -> Result<Result<BufferSize, u8>, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(Ok(BufferSize::KB2))
+        }
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.fsr)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(self.wr)
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.wr = ptr;
+            Ok(())
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.tx_buf.push((ptr, buf.to_vec()));
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            assert_eq!(cmd, self.sn_cr.pop().expect("Unexpected socket command"));
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn fits() {
+        let mut mock = MockRegisters {
+            fsr: 4,
+            wr: 0xFFFF,
+            tx_buf: Vec::new(),
+            sn_cr: vec![SocketCommand::Send],
+        };
+        assert_eq!(mock.udp_send_all(TEST_SOCKET, &[1, 2, 3, 4]), Ok(()));
+        assert_eq!(mock.tx_buf, vec![(0xFFFF, vec![1, 2, 3, 4])]);
+        assert_eq!(mock.wr, 0x0003);
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn would_block() {
+        let mut mock = MockRegisters {
+            fsr: 3,
+            wr: 0,
+            tx_buf: Vec::new(),
+            sn_cr: vec![],
+        };
+        assert_eq!(
+            mock.udp_send_all(TEST_SOCKET, &[1, 2, 3, 4]),
+            Err(Error::WouldBlock)
+        );
+        assert!(mock.tx_buf.is_empty());
+        assert_eq!(mock.wr, 0);
+    }
+
+    #[test]
+    fn out_of_memory() {
+        let mut mock = MockRegisters {
+            fsr: 2048,
+            wr: 0,
+            tx_buf: Vec::new(),
+            sn_cr: vec![],
+        };
+        let buf: Vec<u8> = vec![0; 2049];
+        assert_eq!(
+            mock.udp_send_all(TEST_SOCKET, &buf),
+            Err(Error::OutOfMemory)
+        );
+        assert!(mock.tx_buf.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        let mut mock = MockRegisters {
+            fsr: 0,
+            wr: 0,
+            tx_buf: Vec::new(),
+            sn_cr: vec![],
+        };
+        assert_eq!(mock.udp_send_all(TEST_SOCKET, &[]), Ok(()));
+        assert!(mock.tx_buf.is_empty());
+        assert_eq!(mock.wr, 0);
+    }
+
+    #[test]
+    fn if_free() {
+        let mut mock = MockRegisters {
+            fsr: 3,
+            wr: 0x1000,
+            tx_buf: Vec::new(),
+            sn_cr: vec![],
+        };
+        // not enough free space, nothing is written
+        assert_eq!(mock.udp_send_if_free(TEST_SOCKET, &[1, 2, 3, 4]), Ok(0));
+        assert_eq!(mock.udp_send_if_free(TEST_SOCKET, &[]), Ok(0));
+        assert!(mock.tx_buf.is_empty());
+        assert_eq!(mock.wr, 0x1000);
+
+        mock.sn_cr.push(SocketCommand::Send);
+        assert_eq!(mock.udp_send_if_free(TEST_SOCKET, &[1, 2, 3]), Ok(3));
+        assert_eq!(mock.tx_buf, vec![(0x1000, vec![1, 2, 3])]);
+        assert_eq!(mock.wr, 0x1003);
+        assert!(mock.sn_cr.is_empty());
+    }
+}
+
+/// Tests the udp_poll_send and udp_last_send_result methods
+mod udp_poll_send {
+    use super::*;
+    use w5500_hl::SendOutcome;
+    use w5500_ll::SocketInterrupt;
+
+    struct MockRegisters {
+        sn_ir: u8,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_ir(&mut self, _socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            Ok(SocketInterrupt::from(self.sn_ir))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, _socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn udp_poll_send() {
+        let mut mock = MockRegisters {
+            sn_ir: SocketInterrupt::RECV_MASK,
+        };
+        assert_eq!(mock.udp_poll_send(Sn::Sn0), Err(Error::WouldBlock));
+
+        mock.sn_ir |= SocketInterrupt::SENDOK_MASK;
+        assert_eq!(mock.udp_poll_send(Sn::Sn0), Ok(()));
+        assert_eq!(mock.sn_ir, SocketInterrupt::RECV_MASK);
+
+        mock.sn_ir |= SocketInterrupt::TIMEOUT_MASK;
+        assert_eq!(mock.udp_poll_send(Sn::Sn0), Err(Error::SendTimeout));
+        assert_eq!(mock.sn_ir, SocketInterrupt::RECV_MASK);
+    }
+
+    /// Socket that completes a send after a number of interrupt register
+    /// polls.
+    struct Sending {
+        polls: u8,
+        sn_ir: u8,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for Sending {
+        type Error = Infallible;
+
+        fn sn_sr(&mut self, _socket: Sn) -> Result<Result<SocketStatus, u8>, Self::Error> {
+            Ok(Ok(SocketStatus::Udp))
+        }
+
+        fn sn_ir(&mut self, _socket: Sn) -> Result<SocketInterrupt, Self::Error> {
+            match self.polls.checked_sub(1) {
+                Some(polls) => self.polls = polls,
+                None => self.sn_ir |= SocketInterrupt::SENDOK_MASK,
+            }
+            Ok(SocketInterrupt::from(self.sn_ir))
+        }
+
+        fn set_sn_ir<T: Into<u8>>(&mut self, _socket: Sn, sn_ir: T) -> Result<(), Self::Error> {
+            self.sn_ir &= !sn_ir.into();
+            Ok(())
+        }
+
+        fn set_sn_dest(&mut self, _socket: Sn, _addr: &SocketAddrV4) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn sn_tx_fsr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(8)
+        }
+
+        fn sn_tx_wr(&mut self, _socket: Sn) -> Result<u16, Self::Error> {
+            Ok(0)
+        }
+
+        fn set_sn_tx_buf(
+            &mut self,
+            _socket: Sn,
+            _ptr: u16,
+            _buf: &[u8],
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_sn_tx_wr(&mut self, _socket: Sn, _ptr: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, _socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn udp_send_to_blocking_delays() {
+        const DEST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 8081);
+        let mut mock = Sending {
+            polls: 3,
+            // stale send complete, cleared before sending
+            sn_ir: SocketInterrupt::SENDOK_MASK,
+            sn_cr: Vec::new(),
+        };
+        let mut delays: Vec<u32> = Vec::new();
+        assert_eq!(
+            mock.udp_send_to_blocking(Sn::Sn0, &[1, 2, 3], &DEST, |ms| delays.push(ms)),
+            Ok(3)
+        );
+        assert_eq!(delays, [1, 1, 1]);
+        assert_eq!(mock.sn_cr, [SocketCommand::Send]);
+        assert_eq!(mock.sn_ir, 0);
+    }
+
+    #[test]
+    fn udp_last_send_result() {
+        let mut mock = MockRegisters {
+            sn_ir: SocketInterrupt::RECV_MASK,
+        };
+        assert_eq!(mock.udp_last_send_result(Sn::Sn0), Ok(SendOutcome::Pending));
+
+        mock.sn_ir |= SocketInterrupt::SENDOK_MASK;
+        assert_eq!(mock.udp_last_send_result(Sn::Sn0), Ok(SendOutcome::Sent));
+        assert_eq!(mock.udp_last_send_result(Sn::Sn0), Ok(SendOutcome::Pending));
+
+        mock.sn_ir |= SocketInterrupt::TIMEOUT_MASK;
+        assert_eq!(mock.udp_last_send_result(Sn::Sn0), Ok(SendOutcome::Timeout));
+        assert_eq!(mock.udp_last_send_result(Sn::Sn0), Ok(SendOutcome::Pending));
+        assert_eq!(mock.sn_ir, SocketInterrupt::RECV_MASK);
+    }
+}
+
+/// Tests committing and aborting the UDP writer
+mod udp_writer {
+    use super::*;
+    use w5500_hl::io::{Seek, SeekFrom, Write};
+
+    const TEST_SOCKET: Sn = Sn::Sn6;
+    const WR: u16 = 0xFFFE;
+
+    struct MockRegisters {
+        tx_buf: Vec<u8>,
+        tx_wr: Vec<u16>,
+        sn_cr: Vec<SocketCommand>,
+    }
+
+    impl Registers for MockRegisters {
+        type Error = Infallible;
+
+        fn sn_tx_fsr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(8)
+        }
+
+        fn sn_tx_wr(&mut self, socket: Sn) -> Result<u16, Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            Ok(WR)
+        }
+
+        fn set_sn_tx_buf(&mut self, socket: Sn, ptr: u16, buf: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            let start: usize = ptr.wrapping_sub(WR).into();
+            assert!(start <= self.tx_buf.len(), "gap in the socket buffer");
+            let end: usize = start + buf.len();
+            if end > self.tx_buf.len() {
+                self.tx_buf.resize(end, 0);
+            }
+            self.tx_buf[start..end].copy_from_slice(buf);
+            Ok(())
+        }
+
+        fn set_sn_tx_wr(&mut self, socket: Sn, ptr: u16) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.tx_wr.push(ptr);
+            Ok(())
+        }
+
+        fn set_sn_cr(&mut self, socket: Sn, cmd: SocketCommand) -> Result<(), Self::Error> {
+            assert_eq!(socket, TEST_SOCKET);
+            self.sn_cr.push(cmd);
+            Ok(())
+        }
+
+        fn read(&mut self, _address: u16, _block: u8, _data: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn write(&mut self, _address: u16, _block: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn new_mock() -> MockRegisters {
+        MockRegisters {
+            tx_buf: Vec::new(),
+            tx_wr: Vec::new(),
+            sn_cr: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn abort() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        writer.abort();
+        assert_eq!(mock.tx_buf, [1, 2, 3]);
+        assert!(mock.tx_wr.is_empty());
+        assert!(mock.sn_cr.is_empty());
+    }
+
+    #[test]
+    fn send_segment() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        writer.send_segment().unwrap();
+        assert_eq!(writer.stream_position(), 0);
+        assert_eq!(writer.remain(), 5);
+
+        // the sent data can no longer be overwritten
+        writer.rewind();
+        writer.write_all(&[4, 5]).unwrap();
+        writer.send().unwrap();
+
+        assert_eq!(mock.tx_buf, [1, 2, 3, 4, 5]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(3), WR.wrapping_add(5)]);
+        assert_eq!(mock.sn_cr, [SocketCommand::Send, SocketCommand::Send]);
+    }
+
+    #[test]
+    fn write_integers() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        writer.write_u16_be(0x0102).unwrap();
+        writer.write_u16_le(0x0403).unwrap();
+        writer.write_u32_be(0x0506_0708).unwrap();
+        assert_eq!(writer.write_array([9]), Err(Error::OutOfMemory));
+        writer.rewind();
+        writer.write_u32_le(0x0c0b_0a09).unwrap();
+        writer.write_array([13, 14]).unwrap();
+        writer.send().unwrap();
+        assert_eq!(mock.tx_buf, [9, 10, 11, 12, 13, 14, 7, 8]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(8)]);
+    }
+
+    #[test]
+    fn overrun() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        assert_eq!(writer.remain(), 8);
+        writer.write_all(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(writer.remain(), 2);
+        assert_eq!(writer.write_all(&[7, 8, 9]), Err(Error::OutOfMemory));
+        assert_eq!(writer.remain(), 2);
+        assert_eq!(writer.write(&[7, 8, 9]), Ok(2));
+        assert_eq!(writer.remain(), 0);
+        assert_eq!(writer.write(&[9]), Ok(0));
+        writer.send().unwrap();
+        assert_eq!(mock.tx_buf, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(8)]);
+    }
+    #[test]
+    fn back_patch() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        // length placeholder
+        writer.write_all(&[0, 0]).unwrap();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        let len: u16 = writer.stream_position() - 2;
+        writer.seek::<Infallible>(SeekFrom::Start(0)).unwrap();
+        writer.write_all(&len.to_be_bytes()).unwrap();
+        assert_eq!(writer.stream_position(), 2);
+        writer.send().unwrap();
+        assert_eq!(mock.tx_buf, [0, 3, 1, 2, 3]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(5)]);
+    }
+
+    #[test]
+    fn back_patch_segment() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        writer.write_all(&[0, 1, 2]).unwrap();
+        writer.rewind();
+        writer.write_all(&[3]).unwrap();
+        writer.send_segment().unwrap();
+        assert_eq!(writer.stream_position(), 0);
+        assert_eq!(writer.remain(), 5);
+        writer.write_all(&[4]).unwrap();
+        writer.send().unwrap();
+        assert_eq!(mock.tx_buf, [3, 1, 2, 4]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(3), WR.wrapping_add(4)]);
+    }
+
+    #[test]
+    fn seek_unwritten() {
+        let mut mock = new_mock();
+        let mut writer = mock.udp_writer(TEST_SOCKET).unwrap();
+        assert_eq!(
+            writer.seek::<Infallible>(SeekFrom::Start(4)),
+            Err(Error::UnexpectedEof)
+        );
+        writer.write_all(&[1, 2, 3]).unwrap();
+        for pos in [SeekFrom::Start(4), SeekFrom::Current(1), SeekFrom::End(1)] {
+            assert_eq!(writer.seek::<Infallible>(pos), Err(Error::UnexpectedEof));
+        }
+        writer.seek::<Infallible>(SeekFrom::Start(1)).unwrap();
+        writer.seek::<Infallible>(SeekFrom::End(0)).unwrap();
+        assert_eq!(writer.stream_position(), 3);
+        writer.rewind();
+        writer.send().unwrap();
+        assert_eq!(mock.tx_buf, [1, 2, 3]);
+        assert_eq!(mock.tx_wr, [WR.wrapping_add(3)]);
+        assert_eq!(mock.sn_cr, [SocketCommand::Send]);
+    }
+}
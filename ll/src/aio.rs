@@ -1,8 +1,8 @@
 //! Asynchronous W5500 traits.
 use crate::{
     BufferSize, Eui48Addr, Interrupt, Ipv4Addr, Mode, PhyCfg, Reg, RxPtrs, Sn, SnReg, SocketAddrV4,
-    SocketCommand, SocketInterrupt, SocketInterruptMask, SocketMode, SocketStatus, TxPtrs,
-    COMMON_BLOCK_OFFSET,
+    SocketCommand, SocketInterrupt, SocketInterruptMask, SocketMode, SocketPtrs, SocketStatus,
+    TxPtrs, COMMON_BLOCK_OFFSET,
 };
 
 /// Asynchronous register IO.
@@ -2292,6 +2292,20 @@ pub trait Registers {
         })
     }
 
+    /// Get the socket TX and RX buffer pointers.
+    ///
+    /// This is equivalent to [`Registers::sn_tx_ptrs`],
+    /// [`Registers::sn_tx_rd`], and [`Registers::sn_rx_ptrs`] in a single
+    /// read transaction.
+    ///
+    /// See [`crate::Registers::sn_ptrs`] for more information.
+    async fn sn_ptrs(&mut self, sn: Sn) -> Result<SocketPtrs, Self::Error> {
+        let mut buf: [u8; 10] = [0; 10];
+        self.read(SnReg::TX_FSR0.addr(), sn.block(), &mut buf)
+            .await?;
+        Ok(SocketPtrs::from_be_bytes(buf))
+    }
+
     /// Get the socket interrupt mask.
     ///
     /// # Example
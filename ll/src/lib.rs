@@ -425,6 +425,39 @@ pub struct RxPtrs {
     pub rd: u16,
 }
 
+/// TX and RX socket buffer pointers.
+///
+/// Returned by [`Registers::sn_ptrs`] and [`aio::Registers::sn_ptrs`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SocketPtrs {
+    /// TX free size and write pointer.
+    pub tx: TxPtrs,
+    /// TX read pointer.
+    pub tx_rd: u16,
+    /// RX recieved size and read pointer.
+    pub rx: RxPtrs,
+}
+
+impl SocketPtrs {
+    /// Deserialize the socket buffer pointers from the registers starting at
+    /// [`SnReg::TX_FSR0`].
+    pub(crate) fn from_be_bytes(buf: [u8; 10]) -> Self {
+        let word = |idx: usize| u16::from_be_bytes([buf[idx], buf[idx + 1]]);
+        Self {
+            tx: TxPtrs {
+                fsr: word(0),
+                wr: word(4),
+            },
+            tx_rd: word(2),
+            rx: RxPtrs {
+                rsr: word(6),
+                rd: word(8),
+            },
+        }
+    }
+}
+
 /// W5500 register setters and getters.
 ///
 /// * All register getters are simply the name of the register.
@@ -2500,6 +2533,36 @@ pub trait Registers {
         })
     }
 
+    /// Get the socket TX and RX buffer pointers.
+    ///
+    /// This is equivalent to [`Registers::sn_tx_ptrs`],
+    /// [`Registers::sn_tx_rd`], and [`Registers::sn_rx_ptrs`] in a single
+    /// read transaction of the adjacent registers from [`SnReg::TX_FSR0`] to
+    /// [`SnReg::RX_RD1`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let spi = ehm1::spi::Mock::new(&[
+    /// #   ehm1::spi::Transaction::transaction_start(),
+    /// #   ehm1::spi::Transaction::write_vec(vec![0x00, 0x20, 0x08]),
+    /// #   ehm1::spi::Transaction::read_vec(vec![0x08, 0x00, 0, 0, 0, 0, 0, 0x0C, 0, 0]),
+    /// #   ehm1::spi::Transaction::transaction_end(),
+    /// # ]);
+    /// use w5500_ll::{eh1::vdm::W5500, Registers, Sn, SocketPtrs};
+    ///
+    /// let mut w5500 = W5500::new(spi);
+    /// let ptrs: SocketPtrs = w5500.sn_ptrs(Sn::Sn0)?;
+    /// assert_eq!(ptrs.tx.fsr, 2048);
+    /// assert_eq!(ptrs.rx.rsr, 12);
+    /// # Ok::<(), eh1::spi::ErrorKind>(())
+    /// ```
+    fn sn_ptrs(&mut self, sn: Sn) -> Result<SocketPtrs, Self::Error> {
+        let mut buf: [u8; 10] = [0; 10];
+        self.read(SnReg::TX_FSR0.addr(), sn.block(), &mut buf)?;
+        Ok(SocketPtrs::from_be_bytes(buf))
+    }
+
     /// Get the socket interrupt mask.
     ///
     /// # Example
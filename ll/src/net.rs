@@ -521,6 +521,47 @@ impl SocketAddrV4 {
     pub fn set_port(&mut self, new_port: u16) {
         self.port = new_port
     }
+
+    /// Creates a new socket address from 4 bytes of IPv4 address followed by
+    /// a 2 byte big endian port number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use w5500_ll::net::{Ipv4Addr, SocketAddrV4};
+    ///
+    /// let addr = SocketAddrV4::from_bytes([127, 0, 0, 1, 0x1F, 0x90]);
+    /// assert_eq!(addr, SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+    /// ```
+    pub const fn from_bytes(bytes: [u8; 6]) -> SocketAddrV4 {
+        SocketAddrV4 {
+            ip: Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]),
+            port: u16::from_be_bytes([bytes[4], bytes[5]]),
+        }
+    }
+
+    /// Returns the socket address as 4 bytes of IPv4 address followed by a
+    /// 2 byte big endian port number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use w5500_ll::net::{Ipv4Addr, SocketAddrV4};
+    ///
+    /// let addr = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080);
+    /// assert_eq!(addr.to_bytes(), [127, 0, 0, 1, 0x1F, 0x90]);
+    /// ```
+    pub const fn to_bytes(&self) -> [u8; 6] {
+        let port: [u8; 2] = self.port.to_be_bytes();
+        [
+            self.ip.octets[0],
+            self.ip.octets[1],
+            self.ip.octets[2],
+            self.ip.octets[3],
+            port[0],
+            port[1],
+        ]
+    }
 }
 
 impl ::core::fmt::Display for SocketAddrV4 {
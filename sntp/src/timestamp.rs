@@ -21,9 +21,21 @@ impl Timestamp {
         }
     }
 
+    /// Seconds since 1900-01-01 00:00:00 UTC.
+    ///
+    /// Timestamps with the most significant bit cleared are in the range
+    /// 2036-2104, these are reckoned from 2036-02-07 06:28:16 UTC as
+    /// described in RFC 4330 section 3.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use w5500_sntp::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::default().secs(), 1 << 32);
+    /// ```
     #[must_use]
-    #[cfg(any(feature = "chrono", feature = "time"))]
-    fn secs(&self) -> i64 {
+    pub fn secs(&self) -> i64 {
         let seconds_bits: u32 = (self.bits >> 32) as u32;
         // If bit 0 is set, the UTC time is in the range 1968-2036
         if seconds_bits & 0x8000_0000 != 0 {
@@ -42,6 +54,22 @@ impl Timestamp {
         ((self.bits & 0xFFFF_FFFF) * 1_000_000_000 / u64::from(u32::MAX)) as u32
     }
 
+    /// Seconds since the unix epoch, 1970-01-01 00:00:00 UTC.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use w5500_sntp::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::default().unix_secs(), 2085978496);
+    /// ```
+    #[must_use]
+    pub fn unix_secs(&self) -> i64 {
+        /// Seconds between 1900-01-01 and 1970-01-01.
+        const UNIX_OFFSET: i64 = 2_208_988_800;
+        self.secs() - UNIX_OFFSET
+    }
+
     /// Raw bits of the timestamp value.
     #[must_use]
     pub const fn to_bits(self) -> u64 {
@@ -168,6 +196,17 @@ mod tests {
         core::assert_eq!(pdt.second(), 48);
     }
 
+    #[test]
+    fn unix_secs() {
+        let timestamp: Timestamp = Timestamp {
+            bits: 0xe5_fd_82_24_23_ec_4b_12,
+        };
+        core::assert_eq!(timestamp.unix_secs(), 1649607588);
+
+        let timestamp: Timestamp = Timestamp { bits: 0 };
+        core::assert_eq!(timestamp.unix_secs(), 2085978496);
+    }
+
     #[test]
     fn chrono_zero() {
         let timestamp: Timestamp = Timestamp { bits: 0 };
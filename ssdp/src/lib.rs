@@ -0,0 +1,216 @@
+//! SSDP client for the [Wiznet W5500] SPI internet offload chip.
+//!
+//! The simple service discovery protocol (SSDP) is used by UPnP to discover
+//! devices and services on the local network.
+//!
+//! # Limitations
+//!
+//! * Only M-SEARCH requests are supported, NOTIFY advertisements are not
+//!   parsed.
+//! * Responses are passed to the caller as raw bytes.
+//!
+//! # Feature Flags
+//!
+//! All features are disabled by default.
+//!
+//! * `eh0`: Passthrough to [`w5500-hl`].
+//! * `eh1`: Passthrough to [`w5500-hl`].
+//! * `std`: Passthrough to [`w5500-hl`].
+//! * `defmt`: Enable formatting types with `defmt`. Also a passthrough to [`w5500-hl`].
+//!
+//! # Reference Documentation
+//!
+//! * [UPnP Device Architecture 1.1](https://openconnectivity.org/upnp-specs/UPnP-arch-DeviceArchitecture-v1.1.pdf)
+//!
+//! [`w5500-hl`]: https://crates.io/crates/w5500-hl
+//! [Wiznet W5500]: https://www.wiznet.io/product-item/w5500/
+#![cfg_attr(docsrs, feature(doc_cfg), feature(doc_auto_cfg))]
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use w5500_hl as hl;
+pub use w5500_hl::ll;
+
+use hl::{io::Write, Error, Udp, UdpWriter};
+use ll::{
+    net::{Ipv4Addr, SocketAddrV4},
+    Sn,
+};
+
+/// SSDP port.
+pub const SSDP_PORT: u16 = 1900;
+
+/// SSDP multicast address.
+pub const SSDP_ADDRESS: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
+
+const SSDP_SERVER: SocketAddrV4 = SocketAddrV4::new(SSDP_ADDRESS, SSDP_PORT);
+
+/// Search target for all devices and services.
+pub const ST_ALL: &str = "ssdp:all";
+
+/// Search target for root devices.
+pub const ST_ROOT_DEVICE: &str = "upnp:rootdevice";
+
+/// W5500 SSDP client.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Client {
+    sn: Sn,
+    port: u16,
+    deadline: Option<u32>,
+}
+
+impl Client {
+    /// Create a new SSDP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `sn` - The socket number to use for SSDP searches.
+    /// * `port` - SSDP source port.
+    ///   Use any free port greater than 1024 not in use by other W5500
+    ///   sockets.
+    ///   Responses are sent to this port.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use w5500_ssdp::{ll::Sn, Client};
+    ///
+    /// const SSDP_SRC_PORT: u16 = 41900;
+    ///
+    /// let ssdp_client: Client = Client::new(Sn::Sn3, SSDP_SRC_PORT);
+    /// ```
+    #[must_use]
+    pub const fn new(sn: Sn, port: u16) -> Self {
+        Self {
+            sn,
+            port,
+            deadline: None,
+        }
+    }
+
+    /// Send an M-SEARCH request.
+    ///
+    /// This joins the SSDP multicast group and sends the M-SEARCH request,
+    /// responses are collected with [`Client::poll`].
+    ///
+    /// # Arguments
+    ///
+    /// * `w5500` - The W5500 device that implements the [`Udp`] trait.
+    /// * `st` - The search target, for example [`ST_ALL`].
+    /// * `mx` - Maximum time to wait for responses in seconds.
+    ///   This is clamped to the range allowed by UPnP, 1 to 5 seconds.
+    /// * `monotonic_secs` - Number of seconds since an arbitrary point in
+    ///   time, this is used to end the search after `mx` seconds.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::OutOfMemory`]
+    pub fn search<W5500: Udp>(
+        &mut self,
+        w5500: &mut W5500,
+        st: &str,
+        mx: u8,
+        monotonic_secs: u32,
+    ) -> Result<(), Error<W5500::Error>> {
+        let mx: u8 = mx.clamp(1, 5);
+
+        w5500.set_sn_ttl(self.sn, 4)?;
+        w5500.udp_bind_multicast(self.sn, self.port, &SSDP_SERVER)?;
+
+        let mut writer: UdpWriter<W5500> = w5500.udp_writer(self.sn)?;
+        writer.write_all(b"M-SEARCH * HTTP/1.1\r\n")?;
+        writer.write_all(b"HOST: 239.255.255.250:1900\r\n")?;
+        writer.write_all(b"MAN: \"ssdp:discover\"\r\n")?;
+        writer.write_all(b"MX: ")?;
+        writer.write_all(&[b'0' + mx])?;
+        writer.write_all(b"\r\nST: ")?;
+        writer.write_all(st.as_bytes())?;
+        writer.write_all(b"\r\n\r\n")?;
+        writer.send()?;
+
+        self.deadline = Some(monotonic_secs.wrapping_add(mx.into()));
+        Ok(())
+    }
+
+    /// Collect responses to the M-SEARCH request sent with
+    /// [`Client::search`].
+    ///
+    /// `on_response` is called with each response, and the address of the
+    /// device that sent the response.
+    /// Responses that do not fit in `buf` are truncated.
+    ///
+    /// Call this method until it returns `Ok(())`.
+    ///
+    /// # Arguments
+    ///
+    /// * `w5500` - The W5500 device that implements the [`Udp`] trait.
+    /// * `monotonic_secs` - Number of seconds since an arbitrary point in
+    ///   time, this must use the same time base as [`Client::search`].
+    /// * `buf` - A buffer for reading responses.
+    /// * `on_response` - Called with each response.
+    ///
+    /// # Errors
+    ///
+    /// This method can only return:
+    ///
+    /// * [`Error::Other`]
+    /// * [`Error::WouldBlock`] until `mx` seconds have passed since the search
+    ///   was sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn monotonic_secs() -> u32 { 0 }
+    /// # fn discover<W5500: w5500_ssdp::hl::Udp>(mut w5500: W5500) -> Result<(), w5500_ssdp::hl::Error<W5500::Error>> {
+    /// use w5500_ssdp::{hl::Error, ll::Sn, Client, ST_ROOT_DEVICE};
+    ///
+    /// let mut client: Client = Client::new(Sn::Sn3, 41900);
+    /// client.search(&mut w5500, ST_ROOT_DEVICE, 2, monotonic_secs())?;
+    ///
+    /// let mut buf: [u8; 512] = [0; 512];
+    /// loop {
+    ///     match client.poll(&mut w5500, monotonic_secs(), &mut buf, |response, from| {
+    ///         // parse the response
+    ///     }) {
+    ///         Ok(()) => break,
+    ///         Err(Error::WouldBlock) => continue,
+    ///         Err(e) => return Err(e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn poll<W5500: Udp, F: FnMut(&[u8], SocketAddrV4)>(
+        &mut self,
+        w5500: &mut W5500,
+        monotonic_secs: u32,
+        buf: &mut [u8],
+        mut on_response: F,
+    ) -> Result<(), Error<W5500::Error>> {
+        let deadline: u32 = match self.deadline {
+            Some(deadline) => deadline,
+            None => return Ok(()),
+        };
+
+        loop {
+            match w5500.udp_recv_from(self.sn, buf) {
+                Ok((len, from)) => on_response(&buf[..usize::from(len)], from),
+                Err(Error::WouldBlock) => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        // wrapping comparison, the monotonic counter may overflow
+        if monotonic_secs.wrapping_sub(deadline) < u32::MAX / 2 {
+            self.deadline = None;
+            Ok(())
+        } else {
+            Err(Error::WouldBlock)
+        }
+    }
+}
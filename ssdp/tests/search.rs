@@ -0,0 +1,176 @@
+use std::{collections::HashMap, convert::Infallible};
+use w5500_ssdp::{
+    hl::Error,
+    ll::{
+        net::{Ipv4Addr, SocketAddrV4},
+        Registers, Sn, SnReg, SocketCommand, SocketStatus,
+    },
+    Client, SSDP_ADDRESS, SSDP_PORT, ST_ROOT_DEVICE,
+};
+
+const SSDP_SOCKET: Sn = Sn::Sn3;
+const SSDP_SRC_PORT: u16 = 41900;
+const DEVICE: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 10), 1900);
+
+/// Simulates the memory of the W5500 and the socket commands used by the
+/// client.
+#[derive(Default)]
+struct MockRegisters {
+    mem: HashMap<(u8, u16), u8>,
+    sent: Vec<Vec<u8>>,
+    rx_wr: u16,
+}
+
+impl MockRegisters {
+    fn get(&self, block: u8, address: u16) -> u8 {
+        self.mem.get(&(block, address)).copied().unwrap_or(0)
+    }
+
+    fn get_u16(&self, block: u8, address: u16) -> u16 {
+        u16::from_be_bytes([self.get(block, address), self.get(block, address + 1)])
+    }
+
+    fn set(&mut self, block: u8, address: u16, data: &[u8]) {
+        for (offset, byte) in data.iter().enumerate() {
+            self.mem
+                .insert((block, address.wrapping_add(offset as u16)), *byte);
+        }
+    }
+
+    /// Writes a datagram from `from` to the socket RX buffer.
+    fn recv_datagram(&mut self, from: &SocketAddrV4, data: &[u8]) {
+        let mut datagram: Vec<u8> = from.to_bytes().to_vec();
+        datagram.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+        datagram.extend_from_slice(data);
+        self.set(SSDP_SOCKET.rx_block(), self.rx_wr, &datagram);
+        self.rx_wr = self
+            .rx_wr
+            .wrapping_add(u16::try_from(datagram.len()).unwrap());
+        self.update_rx_rsr();
+    }
+
+    fn update_rx_rsr(&mut self) {
+        let block: u8 = SSDP_SOCKET.block();
+        let rx_rd: u16 = self.get_u16(block, SnReg::RX_RD0.addr());
+        let rsr: u16 = self.rx_wr.wrapping_sub(rx_rd);
+        self.set(block, SnReg::RX_RSR0.addr(), &rsr.to_be_bytes());
+    }
+
+    fn command(&mut self, cmd: SocketCommand) {
+        let block: u8 = SSDP_SOCKET.block();
+        match cmd {
+            SocketCommand::Close => {
+                self.set(block, SnReg::SR.addr(), &[SocketStatus::Closed.into()])
+            }
+            SocketCommand::Open => {
+                self.set(block, SnReg::SR.addr(), &[SocketStatus::Udp.into()]);
+                self.set(block, SnReg::TX_FSR0.addr(), &2048_u16.to_be_bytes());
+            }
+            SocketCommand::Send => {
+                let rd: u16 = self.get_u16(block, SnReg::TX_RD0.addr());
+                let wr: u16 = self.get_u16(block, SnReg::TX_WR0.addr());
+                let data: Vec<u8> = (rd..wr)
+                    .map(|address| self.get(SSDP_SOCKET.tx_block(), address))
+                    .collect();
+                self.sent.push(data);
+                self.set(block, SnReg::TX_RD0.addr(), &wr.to_be_bytes());
+            }
+            SocketCommand::Recv => self.update_rx_rsr(),
+            _ => panic!("Unexpected command {cmd:?}"),
+        }
+    }
+}
+
+impl Registers for MockRegisters {
+    type Error = Infallible;
+
+    fn read(&mut self, address: u16, block: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        for (offset, byte) in data.iter_mut().enumerate() {
+            *byte = self.get(block, address.wrapping_add(offset as u16));
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, address: u16, block: u8, data: &[u8]) -> Result<(), Self::Error> {
+        if block == SSDP_SOCKET.block() && address == SnReg::CR.addr() {
+            self.command(SocketCommand::try_from(data[0]).unwrap());
+        } else {
+            self.set(block, address, data);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn search() {
+    const RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\n\
+        CACHE-CONTROL: max-age=1800\r\n\
+        EXT:\r\n\
+        LOCATION: http://192.168.0.10:49152/description.xml\r\n\
+        SERVER: Linux/5.10 UPnP/1.0 device/1.0\r\n\
+        ST: upnp:rootdevice\r\n\
+        USN: uuid:2fac1234-31f8-11b4-a222-08002b34c003::upnp:rootdevice\r\n\
+        \r\n";
+
+    let mut w5500 = MockRegisters::default();
+    let mut client: Client = Client::new(SSDP_SOCKET, SSDP_SRC_PORT);
+
+    // mx is clamped to 5 seconds
+    client.search(&mut w5500, ST_ROOT_DEVICE, 9, 100).unwrap();
+
+    let block: u8 = SSDP_SOCKET.block();
+    assert_eq!(w5500.get(block, SnReg::SR.addr()), SocketStatus::Udp.into());
+    assert_eq!(w5500.get_u16(block, SnReg::PORT0.addr()), SSDP_SRC_PORT);
+    assert_eq!(w5500.get(block, SnReg::TTL.addr()), 4);
+    assert_eq!(
+        w5500.sn_dhar(SSDP_SOCKET).unwrap().octets,
+        [0x01, 0x00, 0x5E, 0x7F, 0xFF, 0xFA]
+    );
+    assert_eq!(
+        w5500.sn_dest(SSDP_SOCKET).unwrap(),
+        SocketAddrV4::new(SSDP_ADDRESS, SSDP_PORT)
+    );
+    assert!(w5500.sn_mr(SSDP_SOCKET).unwrap().multi_enabled());
+    assert_eq!(
+        w5500.sent,
+        [b"M-SEARCH * HTTP/1.1\r\n\
+            HOST: 239.255.255.250:1900\r\n\
+            MAN: \"ssdp:discover\"\r\n\
+            MX: 5\r\n\
+            ST: upnp:rootdevice\r\n\
+            \r\n"
+            .to_vec()]
+    );
+
+    let mut buf: [u8; 512] = [0; 512];
+    let mut responses: Vec<(Vec<u8>, SocketAddrV4)> = Vec::new();
+    let mut on_response = |response: &[u8], from: SocketAddrV4| {
+        responses.push((response.to_vec(), from));
+    };
+
+    assert_eq!(
+        client.poll(&mut w5500, 101, &mut buf, &mut on_response),
+        Err(Error::WouldBlock)
+    );
+
+    w5500.recv_datagram(&DEVICE, RESPONSE);
+    w5500.recv_datagram(&DEVICE, RESPONSE);
+    assert_eq!(
+        client.poll(&mut w5500, 104, &mut buf, &mut on_response),
+        Err(Error::WouldBlock)
+    );
+    assert_eq!(
+        client.poll(&mut w5500, 105, &mut buf, &mut on_response),
+        Ok(())
+    );
+    // the search has ended
+    assert_eq!(
+        client.poll(&mut w5500, 106, &mut buf, &mut on_response),
+        Ok(())
+    );
+
+    assert_eq!(
+        responses,
+        [(RESPONSE.to_vec(), DEVICE), (RESPONSE.to_vec(), DEVICE)]
+    );
+}
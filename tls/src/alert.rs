@@ -267,6 +267,7 @@ impl AlertDescription {
                 error!("W5500 unexpectedly blocked");
                 AlertDescription::InternalError
             }
+            _ => AlertDescription::InternalError,
         }
     }
 }